@@ -12,6 +12,7 @@ use structura::container::Container;
 use structura::container::border::BorderLayout;
 use structura::container::panel::Panel;
 use structura::container::row::Row;
+use structura::container::tabs::Tabs;
 use structura::geometry::{Point, Size};
 
 fn main() {
@@ -46,11 +47,21 @@ fn main() {
     row1.push(Box::new(button_set_text));
 
     let mut panel = Panel::new();
-    panel.push(Box::new(ComponentHandle::new(textarea1)));
+    panel.push(Box::new(Button::default().set_text("Panel Button".to_string())));
+
+    let mut tabs = Tabs::new()
+        .closable(true)
+        .reorderable(true)
+        .on_selection_change(|index| {
+            println!("tabs.on_selection_change({})", index);
+        });
+    tabs.add_tab("Text".to_string(), Box::new(ComponentHandle::new(textarea1)));
+    tabs.add_tab("Panel".to_string(), Box::new(panel));
 
     let mut main_container = BorderLayout::new();
+    main_container.set_splitters(true);
     main_container.set_north(Box::new(row1));
-    main_container.set_center(Box::new(panel));
+    main_container.set_center(Box::new(tabs));
 
     let mut application = Application::new(Box::new(main_container));
     application.run();
@@ -0,0 +1,153 @@
+//!
+//! Structura
+//!
+//! Animation frames.
+//!
+//! A `Component` that is animating calls `request_frame()`. The `Application` then redraws at
+//! `FRAME_INTERVAL`, calling `Component::animate()` on the whole tree before each redraw. A
+//! `Component` that wants another frame calls `request_frame()` again from `animate()`.
+//!
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+///
+/// Time between animation frames, about 60 frames per second.
+///
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+thread_local! {
+    static FRAME_REQUESTED: Cell<bool> = const { Cell::new(false) };
+}
+
+///
+/// Ask the `Application` for another animation frame.
+///
+pub fn request_frame() {
+    FRAME_REQUESTED.with(|requested| requested.set(true));
+}
+
+///
+/// Returns true if an animation frame has been requested since the last frame.
+///
+pub fn frame_requested() -> bool {
+    FRAME_REQUESTED.with(|requested| requested.get())
+}
+
+///
+/// Clear the frame request, returning whether one was made. Called by the `Application` at the
+/// start of each frame.
+///
+pub(crate) fn take_frame_request() -> bool {
+    FRAME_REQUESTED.with(|requested| requested.replace(false))
+}
+
+///
+/// Ease-out cubic curve: starts fast and slows down towards the end. `t` is from `0.0` to `1.0`.
+///
+pub fn ease_out(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t).powi(3)
+}
+
+///
+/// A value that moves from one number to another over a `Duration`, following `ease_out()`.
+///
+/// The clock starts at the first `Tween::update()` after `Tween::animate_to()`, so that a
+/// `Component` does not need the current time when it starts an animation.
+///
+#[derive(Debug, Clone)]
+pub struct Tween {
+    value: f64,
+    from: f64,
+    to: f64,
+    duration: Duration,
+    started: Option<Instant>,
+    running: bool,
+}
+
+impl Tween {
+    ///
+    /// Constructor, resting at `value`.
+    ///
+    pub fn new(value: f64, duration: Duration) -> Self {
+        Self {
+            value,
+            from: value,
+            to: value,
+            duration,
+            started: None,
+            running: false,
+        }
+    }
+
+    ///
+    /// Returns the current value.
+    ///
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    ///
+    /// Returns the value the `Tween` is moving to.
+    ///
+    pub fn target(&self) -> f64 {
+        self.to
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    ///
+    /// Start moving from the current value to `to`, and request an animation frame.
+    ///
+    pub fn animate_to(&mut self, to: f64) {
+        self.from = self.value;
+        self.to = to;
+        self.started = None;
+        self.running = self.from != to;
+        if self.running {
+            request_frame();
+        }
+    }
+
+    ///
+    /// Jump straight to `value`, stopping any animation.
+    ///
+    pub fn set(&mut self, value: f64) {
+        self.value = value;
+        self.from = value;
+        self.to = value;
+        self.running = false;
+    }
+
+    ///
+    /// Advance to the time `now`. Requests another frame while still running, and returns true
+    /// if the value changed.
+    ///
+    pub fn update(&mut self, now: Instant) -> bool {
+        if !self.running {
+            return false;
+        }
+        let started = *self.started.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(started);
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            elapsed.as_secs_f64() / self.duration.as_secs_f64()
+        };
+        if t >= 1.0 {
+            self.value = self.to;
+            self.running = false;
+        } else {
+            self.value = self.from + (self.to - self.from) * ease_out(t);
+            request_frame();
+        }
+        true
+    }
+}
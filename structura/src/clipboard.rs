@@ -0,0 +1,52 @@
+//!
+//! Structura: System clipboard.
+//!
+//! Errors, such as no clipboard being available, are reported on `stderr` and otherwise
+//! ignored: copying and pasting are never critical to the caller.
+//!
+
+use arboard::Clipboard;
+use std::cell::RefCell;
+
+thread_local! {
+    //
+    // On some platforms (e.g. X11), copied text is only available while the `Clipboard` that
+    // copied it is alive, so it is kept for the life of the thread.
+    //
+    static CLIPBOARD: RefCell<Option<Clipboard>> = const { RefCell::new(None) };
+}
+
+fn with_clipboard<T, F>(f: F) -> Option<T>
+where
+    F: FnOnce(&mut Clipboard) -> Result<T, arboard::Error>,
+{
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        if clipboard.is_none() {
+            match Clipboard::new() {
+                Ok(new_clipboard) => *clipboard = Some(new_clipboard),
+                Err(error) => {
+                    eprintln!("Error: clipboard unavailable: {error}");
+                    return None;
+                }
+            }
+        }
+        f(clipboard.as_mut()?)
+            .map_err(|error| eprintln!("Error: clipboard: {error}"))
+            .ok()
+    })
+}
+
+///
+/// Copy `text` to the clipboard.
+///
+pub fn set_text(text: &str) {
+    with_clipboard(|clipboard| clipboard.set_text(text));
+}
+
+///
+/// Returns the text on the clipboard, if any.
+///
+pub fn text() -> Option<String> {
+    with_clipboard(|clipboard| clipboard.get_text())
+}
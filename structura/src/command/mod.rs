@@ -0,0 +1,417 @@
+//!
+//! Structura
+//!
+//! Commands.
+//!
+//! A `Command` is an action of the application, e.g. "Save", that the user can run in several
+//! ways: from a menu item, a toolbar button, or a keyboard shortcut. Each of them triggers the
+//! same `Command`, which runs its callback:
+//!
+//! ```ignore
+//! let save = Command::new(|_| document.save()).shortcut("Ctrl+S");
+//! let menu = Menu::new().item("&Save", &save);
+//! ```
+//!
+//! - A `Command` can be disabled, e.g. while there is nothing to save. Disabled commands do
+//!   not run, and their menu items are greyed out.
+//! - A checkable `Command` toggles its checked state before running its callback. Commands in
+//!   a `CommandGroup` are radio commands: triggering one checks it, and unchecks the others.
+//! - The `Application` triggers the `Command` whose shortcut matches a key press, before the
+//!   focused `Component` sees the key. Shortcuts work for as long as their `Command` exists.
+//! - While a text input such as a `TextField` has focus, shortcuts without `Ctrl`, `Alt` or
+//!   `Super`, e.g. `Delete` or a letter, are left to it.
+//!
+//! `Command`s are cheap to clone: clones share the callback and state.
+//!
+
+use crate::event;
+use crate::event::focus;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use winit::event::{ElementState, KeyEvent};
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+thread_local! {
+    static SHORTCUTS: RefCell<Vec<Weak<CommandState>>> = const { RefCell::new(Vec::new()) };
+}
+
+///
+/// The names of the keys that are not characters, in shortcuts. The first name of a key is
+/// used to show it.
+///
+const NAMED_KEYS: &[(&str, NamedKey)] = &[
+    ("Enter", NamedKey::Enter),
+    ("Return", NamedKey::Enter),
+    ("Esc", NamedKey::Escape),
+    ("Escape", NamedKey::Escape),
+    ("Tab", NamedKey::Tab),
+    ("Space", NamedKey::Space),
+    ("Backspace", NamedKey::Backspace),
+    ("Del", NamedKey::Delete),
+    ("Delete", NamedKey::Delete),
+    ("Ins", NamedKey::Insert),
+    ("Insert", NamedKey::Insert),
+    ("Home", NamedKey::Home),
+    ("End", NamedKey::End),
+    ("PgUp", NamedKey::PageUp),
+    ("PageUp", NamedKey::PageUp),
+    ("PgDn", NamedKey::PageDown),
+    ("PageDown", NamedKey::PageDown),
+    ("Up", NamedKey::ArrowUp),
+    ("Down", NamedKey::ArrowDown),
+    ("Left", NamedKey::ArrowLeft),
+    ("Right", NamedKey::ArrowRight),
+    ("F1", NamedKey::F1),
+    ("F2", NamedKey::F2),
+    ("F3", NamedKey::F3),
+    ("F4", NamedKey::F4),
+    ("F5", NamedKey::F5),
+    ("F6", NamedKey::F6),
+    ("F7", NamedKey::F7),
+    ("F8", NamedKey::F8),
+    ("F9", NamedKey::F9),
+    ("F10", NamedKey::F10),
+    ("F11", NamedKey::F11),
+    ("F12", NamedKey::F12),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ShortcutKey {
+    /// A character key, in lowercase.
+    Character(String),
+    Named(NamedKey),
+}
+
+///
+/// A key with modifiers, e.g. `Ctrl+Shift+S`, that triggers a `Command`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    key: ShortcutKey,
+    modifiers: ModifiersState,
+}
+
+impl Shortcut {
+    ///
+    /// Constructor, for a character key with modifiers.
+    ///
+    pub fn character(key: char, modifiers: ModifiersState) -> Self {
+        Self {
+            key: ShortcutKey::Character(key.to_lowercase().collect()),
+            modifiers,
+        }
+    }
+
+    ///
+    /// Constructor, for a named key, e.g. `NamedKey::F5`, with modifiers.
+    ///
+    pub fn named(key: NamedKey, modifiers: ModifiersState) -> Self {
+        Self {
+            key: ShortcutKey::Named(key),
+            modifiers,
+        }
+    }
+
+    ///
+    /// Parse a shortcut such as `"Ctrl+S"`, `"Ctrl+Shift+Z"`, `"Alt+F4"` or `"Delete"`.
+    ///
+    /// The modifiers are `Ctrl`, `Shift`, `Alt` and `Super` (or `Cmd`), in any order and case.
+    /// Returns `None` if the text is not a valid shortcut.
+    ///
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (modifiers, key) = match text.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => text.rsplit_once('+').unwrap_or(("", text)),
+        };
+        let mut state = ModifiersState::empty();
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            state |= match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => ModifiersState::CONTROL,
+                "shift" => ModifiersState::SHIFT,
+                "alt" | "option" => ModifiersState::ALT,
+                "super" | "cmd" | "meta" | "win" => ModifiersState::SUPER,
+                _ => return None,
+            };
+        }
+        let key = key.trim();
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(Self::character(c, state)),
+            _ => NAMED_KEYS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|&(_, named)| Self::named(named, state)),
+        }
+    }
+
+    ///
+    /// Returns true if `event` is a press of the shortcut's key, with exactly its modifiers.
+    ///
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        if event.state != ElementState::Pressed || event::modifiers() != self.modifiers {
+            return false;
+        }
+        match (&self.key, &event.logical_key) {
+            (ShortcutKey::Named(named), Key::Named(key)) => named == key,
+            (ShortcutKey::Character(c), Key::Character(key)) => key.to_lowercase() == *c,
+            (ShortcutKey::Character(c), Key::Named(NamedKey::Space)) => c == " ",
+            _ => false,
+        }
+    }
+}
+
+///
+/// Shows the shortcut as it is written for `parse()`, e.g. `Ctrl+Shift+S`.
+///
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (ModifiersState::CONTROL, "Ctrl"),
+            (ModifiersState::ALT, "Alt"),
+            (ModifiersState::SHIFT, "Shift"),
+            (ModifiersState::SUPER, "Super"),
+        ];
+        for (modifier, name) in modifiers {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match &self.key {
+            ShortcutKey::Character(c) => write!(f, "{}", c.to_uppercase()),
+            ShortcutKey::Named(key) => {
+                let name = NAMED_KEYS
+                    .iter()
+                    .find(|(_, named)| named == key)
+                    .map_or("?", |(name, _)| name);
+                write!(f, "{name}")
+            }
+        }
+    }
+}
+
+/// The callback of a `Command`.
+type Action = Box<dyn FnMut(&Command)>;
+
+struct CommandState {
+    action: RefCell<Action>,
+    shortcut: RefCell<Option<Shortcut>>,
+    enabled: Cell<bool>,
+    checkable: Cell<bool>,
+    checked: Cell<bool>,
+    group: RefCell<Option<CommandGroup>>,
+}
+
+///
+/// An action of the application, triggered by menu items, buttons and keyboard shortcuts.
+///
+#[derive(Clone)]
+pub struct Command {
+    state: Rc<CommandState>,
+}
+
+impl Command {
+    ///
+    /// Constructor. `action` is called with the `Command` each time it is triggered, e.g. to
+    /// read its checked state.
+    ///
+    pub fn new<F: FnMut(&Command) + 'static>(action: F) -> Self {
+        Self {
+            state: Rc::new(CommandState {
+                action: RefCell::new(Box::new(action)),
+                shortcut: RefCell::new(None),
+                enabled: Cell::new(true),
+                checkable: Cell::new(false),
+                checked: Cell::new(false),
+                group: RefCell::new(None),
+            }),
+        }
+    }
+
+    ///
+    /// Set the keyboard shortcut, e.g. `"Ctrl+S"`. See `Shortcut::parse()`.
+    ///
+    pub fn shortcut(self, shortcut: &str) -> Self {
+        match Shortcut::parse(shortcut) {
+            Some(shortcut) => self.set_shortcut(Some(shortcut)),
+            None => eprintln!("Error: invalid shortcut \"{shortcut}\""),
+        }
+        self
+    }
+
+    ///
+    /// Make the command checkable, toggling its checked state each time it is triggered.
+    ///
+    pub fn checkable(self, checked: bool) -> Self {
+        self.state.checkable.set(true);
+        self.state.checked.set(checked);
+        self
+    }
+
+    ///
+    /// Make the command one of the radio commands of `group`, checked when triggered.
+    ///
+    pub fn radio(self, group: &CommandGroup, checked: bool) -> Self {
+        self.state.checkable.set(true);
+        group.join(&self);
+        *self.state.group.borrow_mut() = Some(group.clone());
+        self.set_checked(checked);
+        self
+    }
+
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    ///
+    /// Run the command, as the user would: update its checked state, then call its action.
+    /// Returns false if the command is disabled, or already running.
+    ///
+    pub fn trigger(&self) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+        let Ok(mut action) = self.state.action.try_borrow_mut() else {
+            return false;
+        };
+        if self.is_radio() {
+            self.set_checked(true);
+        } else if self.is_checkable() {
+            self.state.checked.set(!self.is_checked());
+        }
+        action(self);
+        true
+    }
+
+    pub fn get_shortcut(&self) -> Option<Shortcut> {
+        self.state.shortcut.borrow().clone()
+    }
+
+    ///
+    /// Set or remove the keyboard shortcut.
+    ///
+    pub fn set_shortcut(&self, shortcut: Option<Shortcut>) {
+        let registered = self.state.shortcut.borrow().is_some();
+        *self.state.shortcut.borrow_mut() = shortcut;
+        if !registered && self.state.shortcut.borrow().is_some() {
+            SHORTCUTS.with(|shortcuts| {
+                let mut shortcuts = shortcuts.borrow_mut();
+                shortcuts.retain(|command| command.strong_count() > 0);
+                shortcuts.push(Rc::downgrade(&self.state));
+            });
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.enabled.get()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.state.enabled.set(enabled);
+    }
+
+    pub fn is_checkable(&self) -> bool {
+        self.state.checkable.get()
+    }
+
+    ///
+    /// Returns true if the command is one of the radio commands of a `CommandGroup`.
+    ///
+    pub fn is_radio(&self) -> bool {
+        self.state.group.borrow().is_some()
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.state.checked.get()
+    }
+
+    ///
+    /// Set the checked state, without calling the action. Checking a radio command unchecks
+    /// the others of its group.
+    ///
+    pub fn set_checked(&self, checked: bool) {
+        self.state.checked.set(checked);
+        if checked && let Some(group) = self.state.group.borrow().as_ref() {
+            group.uncheck_others(&self.state);
+        }
+    }
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Command")
+            .field("shortcut", &self.get_shortcut())
+            .field("enabled", &self.is_enabled())
+            .field("checked", &self.is_checked())
+            .finish_non_exhaustive()
+    }
+}
+
+///
+/// A group of radio commands, of which at most one is checked.
+///
+#[derive(Clone, Default)]
+pub struct CommandGroup {
+    members: Rc<RefCell<Vec<Weak<CommandState>>>>,
+}
+
+impl CommandGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Returns the index of the checked command, in the order the commands joined the group.
+    ///
+    pub fn checked(&self) -> Option<usize> {
+        self.members
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .position(|command| command.checked.get())
+    }
+
+    fn join(&self, command: &Command) {
+        self.members
+            .borrow_mut()
+            .push(Rc::downgrade(&command.state));
+    }
+
+    fn uncheck_others(&self, checked: &Rc<CommandState>) {
+        for member in self.members.borrow().iter().filter_map(Weak::upgrade) {
+            if !Rc::ptr_eq(&member, checked) {
+                member.checked.set(false);
+            }
+        }
+    }
+}
+
+///
+/// Trigger the enabled `Command` whose shortcut matches `event`. Called by the `Application`
+/// for each key event. Returns true if a `Command` was triggered.
+///
+pub(crate) fn handle_shortcut(event: &KeyEvent) -> bool {
+    let command_modifiers = ModifiersState::CONTROL | ModifiersState::ALT | ModifiersState::SUPER;
+    if focus::text_input_focused() && !event::modifiers().intersects(command_modifiers) {
+        return false;
+    }
+    let commands: Vec<Command> = SHORTCUTS.with(|shortcuts| {
+        shortcuts
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|state| Command { state })
+            .collect()
+    });
+    commands
+        .iter()
+        .filter(|command| command.is_enabled())
+        .find(|command| {
+            command
+                .get_shortcut()
+                .is_some_and(|shortcut| shortcut.matches(event))
+        })
+        .is_some_and(Command::trigger)
+}
@@ -1,26 +1,188 @@
 //!
 //! # Structura Component: Button.
 //!
-//! A basic clickable button with text.
+//! A basic clickable button with text, an icon, or both.
+//!
+//! ```ignore
+//! let save = Button::default()
+//!     .set_text("Save".to_string())
+//!     .icon(Icon::file())
+//!     .icon_placement(IconPlacement::Above)
+//!     .command(&save_command);
+//! ```
+//!
+//! - The content of a `Button` is a `ButtonContent`: text, an `Icon`, or an icon placed on
+//!   one side of the text.
+//! - A `Button` can trigger a `Command`, the same as a menu item or keyboard shortcut. It is
+//!   disabled while the `Command` is, and shows as pressed while a checkable `Command` is
+//!   checked, making it a toggle button.
+//! - Clicking a `Button` does not give it keyboard focus, but its owner can, e.g. a `Dialog`
+//!   moving the focus with `Tab`. `Space` then clicks it.
 //!
 
-use crate::component::{Component, ComponentState};
+use crate::command::Command;
+use crate::component::icon::Icon;
+use crate::component::menu::DISABLED_ALPHA;
+use crate::component::{Component, ComponentState, metrics};
 use crate::event::MouseInput;
-use crate::geometry::{Point, Size};
-use crate::view::BufferContext;
-use rusttype::{Scale, point};
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::{BufferContext, blend_color};
+use rusttype::Scale;
+use winit::event::ElementState;
+use winit::keyboard::{Key, NamedKey};
+
+/// Color of the text and drawn icons.
+const TEXT_COLOR: u32 = 0xFFFFFF;
+
+/// Space between the content and the edge of the button.
+const PADDING: f64 = 10.0;
+
+/// Space between the icon and the text.
+const ICON_GAP: f64 = 6.0;
+
+///
+/// Where the `Icon` of a `Button` is placed, relative to its text.
+///
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum IconPlacement {
+    #[default]
+    Left,
+    Right,
+    Above,
+    Below,
+}
+
+///
+/// What a `Button` shows.
+///
+#[derive(Debug, Clone)]
+pub enum ButtonContent {
+    Text(String),
+    Icon(Icon),
+    IconText {
+        icon: Icon,
+        text: String,
+        placement: IconPlacement,
+    },
+}
+
+impl ButtonContent {
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            ButtonContent::Text(text) | ButtonContent::IconText { text, .. } => Some(text),
+            ButtonContent::Icon(_) => None,
+        }
+    }
+
+    pub fn icon(&self) -> Option<&Icon> {
+        match self {
+            ButtonContent::Icon(icon) | ButtonContent::IconText { icon, .. } => Some(icon),
+            ButtonContent::Text(_) => None,
+        }
+    }
+
+    ///
+    /// Returns the size of the content, without padding.
+    ///
+    pub fn size(&self) -> Size {
+        let line_height = metrics::line_height() as f64;
+        let (width, height) = match self {
+            ButtonContent::Text(text) => (metrics::text_width(text) as f64, line_height),
+            ButtonContent::Icon(icon) => (icon.width() as f64, icon.height() as f64),
+            ButtonContent::IconText {
+                icon,
+                text,
+                placement,
+            } => {
+                let (icon_width, icon_height) = (icon.width() as f64, icon.height() as f64);
+                let text_width = metrics::text_width(text) as f64;
+                match placement {
+                    IconPlacement::Left | IconPlacement::Right => (
+                        icon_width + ICON_GAP + text_width,
+                        icon_height.max(line_height),
+                    ),
+                    IconPlacement::Above | IconPlacement::Below => (
+                        icon_width.max(text_width),
+                        icon_height + ICON_GAP + line_height,
+                    ),
+                }
+            }
+        };
+        Size {
+            width: width.ceil() as u32,
+            height: height.ceil() as u32,
+        }
+    }
+
+    ///
+    /// Draw the content centered in `bounds`.
+    ///
+    fn draw_centered(&self, context: &mut BufferContext, bounds: &Rectangle, color: u32) {
+        let size = self.size();
+        let (width, height) = (size.width as f64, size.height as f64);
+        let x = bounds.point.x + ((bounds.size.width as f64 - width) / 2.0).round();
+        let y = bounds.point.y + ((bounds.size.height as f64 - height) / 2.0).round();
+        let line_height = context.line_height() as f64;
+        match self {
+            ButtonContent::Text(text) => context.draw_text(text, x, y, color),
+            ButtonContent::Icon(icon) => icon.draw(context, x, y, color),
+            ButtonContent::IconText {
+                icon,
+                text,
+                placement,
+            } => {
+                let (icon_width, icon_height) = (icon.width() as f64, icon.height() as f64);
+                let text_width = context.text_width(text) as f64;
+                let (icon_x, icon_y, text_x, text_y) = match placement {
+                    IconPlacement::Left => (
+                        x,
+                        y + (height - icon_height) / 2.0,
+                        x + icon_width + ICON_GAP,
+                        y + (height - line_height) / 2.0,
+                    ),
+                    IconPlacement::Right => (
+                        x + text_width + ICON_GAP,
+                        y + (height - icon_height) / 2.0,
+                        x,
+                        y + (height - line_height) / 2.0,
+                    ),
+                    IconPlacement::Above => (
+                        x + (width - icon_width) / 2.0,
+                        y,
+                        x + (width - text_width) / 2.0,
+                        y + icon_height + ICON_GAP,
+                    ),
+                    IconPlacement::Below => (
+                        x + (width - icon_width) / 2.0,
+                        y + line_height + ICON_GAP,
+                        x + (width - text_width) / 2.0,
+                        y,
+                    ),
+                };
+                icon.draw(context, icon_x.round(), icon_y.round(), color);
+                context.draw_text(text, text_x, text_y, color);
+            }
+        }
+    }
+}
 
 ///
-/// A basic Button component with text.
+/// A basic Button component with text, an icon, or both.
 ///
 pub struct Button {
     position: Point,
     size: Size,
-    text: String,
+    content: ButtonContent,
+
+    /// The placement used when an icon is added to text, or text to an icon.
+    placement: IconPlacement,
+    command: Option<Command>,
     component_state: ComponentState,
     on_mouse_over: Option<Box<dyn FnMut()>>,
     on_mouse_click: Option<Box<dyn FnMut()>>,
     mouse_dragging: bool,
+    focus_id: FocusId,
 }
 
 impl Default for Button {
@@ -34,12 +196,15 @@ impl Default for Button {
                 width: 200,
                 height: 60,
             },
-            text: "button".to_string(),
+            content: ButtonContent::Text("button".to_string()),
+            placement: IconPlacement::default(),
+            command: None,
             component_state: ComponentState::Active,
             //component_style: ComponentStyle::default(),
             on_mouse_over: None,
             on_mouse_click: None,
             mouse_dragging: false,
+            focus_id: FocusId::new(),
         }
     }
 }
@@ -61,12 +226,15 @@ impl Button {
             // background_color: 0x0077CC, // blue
             // border_color: 0x000000,
             // border_width: 2,
-            text,
+            content: ButtonContent::Text(text),
+            placement: IconPlacement::default(),
+            command: None,
             component_state: ComponentState::Active,
             //component_style: ComponentStyle::default(),
             on_mouse_click: None,
             on_mouse_over: None,
             mouse_dragging: false,
+            focus_id: FocusId::new(),
         }
     }
 
@@ -78,15 +246,118 @@ impl Button {
     }
 
     ///
-    /// Set the `text` displayed on the button.
-    ///
-    /// TODO: Button contents should be separate from the Button definition, with TextButton and ImageButton/IconButton options.
+    /// Set the `text` displayed on the button, next to its icon if it has one.
     ///
     pub fn set_text(mut self, text: String) -> Self {
-        self.text = text;
+        self.content = match self.content {
+            ButtonContent::Icon(icon) | ButtonContent::IconText { icon, .. } => {
+                ButtonContent::IconText {
+                    icon,
+                    text,
+                    placement: self.placement,
+                }
+            }
+            ButtonContent::Text(_) => ButtonContent::Text(text),
+        };
+        self
+    }
+
+    ///
+    /// Set the `icon` displayed on the button, next to its text if it has any.
+    ///
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.content = match self.content {
+            ButtonContent::Text(text) | ButtonContent::IconText { text, .. } => {
+                ButtonContent::IconText {
+                    icon,
+                    text,
+                    placement: self.placement,
+                }
+            }
+            ButtonContent::Icon(_) => ButtonContent::Icon(icon),
+        };
+        self
+    }
+
+    ///
+    /// Set where the icon is placed, relative to the text.
+    ///
+    pub fn icon_placement(mut self, placement: IconPlacement) -> Self {
+        self.placement = placement;
+        if let ButtonContent::IconText { placement, .. } = &mut self.content {
+            *placement = self.placement;
+        }
+        self
+    }
+
+    pub fn content(mut self, content: ButtonContent) -> Self {
+        self.set_content(content);
+        self
+    }
+
+    pub fn get_content(&self) -> &ButtonContent {
+        &self.content
+    }
+
+    pub fn set_content(&mut self, content: ButtonContent) {
+        if let ButtonContent::IconText { placement, .. } = &content {
+            self.placement = *placement;
+        }
+        self.content = content;
+    }
+
+    ///
+    /// Trigger `command` when the button is clicked, after the `on_click` handler.
+    ///
+    /// The button is disabled while the `command` is, and a checkable `command` makes it a
+    /// toggle button, shown pressed while the `command` is checked.
+    ///
+    pub fn command(mut self, command: &Command) -> Self {
+        self.command = Some(command.clone());
         self
     }
 
+    pub fn get_command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
+    ///
+    /// Returns the size that fits the content, with padding.
+    ///
+    pub fn preferred_size(&self) -> Size {
+        let size = self.content.size();
+        Size {
+            width: size.width + (2.0 * PADDING) as u32,
+            height: size.height + (2.0 * PADDING) as u32,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.component_state != ComponentState::Disabled
+            && self.command.as_ref().is_none_or(Command::is_enabled)
+    }
+
+    ///
+    /// Returns true if the button triggers a checked `Command`.
+    ///
+    pub fn is_checked(&self) -> bool {
+        self.command.as_ref().is_some_and(Command::is_checked)
+    }
+
+    ///
+    /// Returns the state the button is drawn in: disabled with its `Command`, and pressed
+    /// while its `Command` is checked.
+    ///
+    fn draw_state(&self) -> ComponentState {
+        if !self.is_enabled() {
+            ComponentState::Disabled
+        } else if self.is_checked() {
+            ComponentState::Pressed
+        } else {
+            self.component_state.clone()
+        }
+    }
+
     // fn update_state(&mut self, cursor_x: f64, cursor_y: f64, mouse_pressed: bool) {
     //     if self.contains(cursor_x, cursor_y) {
     //         self.component_state = if mouse_pressed {
@@ -123,148 +394,81 @@ impl Button {
         self.fill_background(context);
         self.draw_border(context);
 
-        // Text rendering parameters
+        let mut color = TEXT_COLOR;
+        if !self.is_enabled() {
+            let style = context.theme.style_for(&ComponentState::Disabled);
+            color = blend_color(style.back_color.value, style.fore_color.value, DISABLED_ALPHA);
+        }
+        let ButtonContent::Text(text) = &self.content else {
+            let bounds = Rectangle::new(
+                self.position.x,
+                self.position.y,
+                self.size.width,
+                self.size.height,
+            );
+            self.content.draw_centered(context, &bounds, color);
+            return;
+        };
         let font_scale = Scale::uniform(context.font_size);
         let v_metrics = context.font.v_metrics(font_scale);
-
-        let screen_width = context.screen_size.width as usize;
-
-        let start_x = self.position.x + 10.0;
+        let start_x = self.position.x + PADDING;
         let start_y =
-            self.position.y + (self.size.height as f64 / 2.0) + (v_metrics.ascent / 2.0) as f64;
-
-        //
-        // TODO: Move to text rendering component
-        //
-        let glyphs: Vec<_> = context
-            .font
-            .layout(
-                &self.text[..],
-                font_scale,
-                point(start_x as f32, start_y as f32),
-            )
-            .collect();
-
-        for glyph in glyphs {
-            if let Some(bb) = glyph.pixel_bounding_box() {
-                glyph.draw(|gx, gy, v| {
-                    let x = gx as i32 + bb.min.x;
-                    let y = gy as i32 + bb.min.y;
-                    if x >= 0
-                        && x < context.screen_size.width as i32
-                        && y >= 0
-                        && (y as usize) < context.buffer.len() / screen_width
-                    {
-                        let idx = y as usize * screen_width + x as usize;
-                        context.buffer[idx] = Self::basic_aa(context.buffer[idx], 0xFFFFFF, v);
-                    }
-                });
-            }
-        }
+            self.position.y + (self.size.height as f64 / 2.0) - (v_metrics.ascent / 2.0) as f64;
+        context.draw_text(text, start_x, start_y, color);
     }
 
     ///
     /// Fill in the background of the Button.
     ///
     fn fill_background(&self, context: &mut BufferContext) {
-        let screen_width = context.screen_size.width as usize;
-        let screen_height = context.screen_size.height as usize;
-        let bw = context.theme.style_for(&self.component_state).border_width;
-        let x0 = self.position.x as usize;
-        let y0 = self.position.y as usize;
-        let x1 = self.position.x as usize + self.size.width as usize;
-        let y1 = self.position.y as usize + self.size.height as usize;
-        let fill_x0 = (x0 + bw).min(screen_width);
-        let fill_y0 = (y0 + bw).min(screen_height);
-        let fill_x1 = x1.saturating_sub(bw).min(screen_width);
-        let fill_y1 = y1.saturating_sub(bw).min(screen_height);
-        //
-        // TODO: Separate ComponentStyles per ComponentState
-        //
-        let background_color = context
-            .theme
-            .style_for(&self.component_state)
-            .back_color
-            .value;
-        for y in fill_y0..fill_y1 {
-            for x in fill_x0..fill_x1 {
-                let idx = y * screen_width + x;
-                if idx < context.buffer.len() {
-                    context.buffer[idx] = background_color;
-                }
-            }
-        }
+        let style = context.theme.style_for(&self.draw_state());
+        let bw = style.border_width as u32;
+        context.fill_rect(
+            self.position.x + bw as f64,
+            self.position.y + bw as f64,
+            self.size.width.saturating_sub(bw * 2),
+            self.size.height.saturating_sub(bw * 2),
+            style.back_color.value,
+        );
     }
 
     ///
     /// Draw the Button border.
     ///
     fn draw_border(&self, context: &mut BufferContext) {
-        let bw = context.theme.style_for(&self.component_state).border_width;
-        let x0 = self.position.x as usize;
-        let y0 = self.position.y as usize;
-        let x1 = self.position.x as usize + self.size.width as usize;
-        let y1 = self.position.y as usize + self.size.height as usize;
-        let screen_width = context.screen_size.width as usize;
-        let screen_height = context.screen_size.height as usize;
-        let clipped_x0 = x0.min(screen_width);
-        let clipped_y0 = y0.min(screen_height);
-        let clipped_x1 = x1.min(screen_width);
-        let clipped_y1 = y1.min(screen_height);
-        let border_color = context
-            .theme
-            .style_for(&self.component_state)
-            .border_color
-            .value;
-        for y in clipped_y0..clipped_y1 {
-            for x in clipped_x0..clipped_x1 {
-                let is_top = y < y0 + bw;
-                let is_bottom = y >= y1.saturating_sub(bw);
-                let is_left = x < x0 + bw;
-                let is_right = x >= x1.saturating_sub(bw);
-
-                if is_top || is_bottom || is_left || is_right {
-                    let idx = y * screen_width + x;
-                    if idx < context.buffer.len() {
-                        context.buffer[idx] = border_color;
-                    }
-                }
-            }
-        }
-    }
-
-    fn basic_aa(bg: u32, fg: u32, alpha: f32) -> u32 {
-        let inv = 1.0 - alpha;
-
-        let br = ((bg >> 16) & 0xFF) as f32;
-        let bg_ = ((bg >> 8) & 0xFF) as f32;
-        let bb = (bg & 0xFF) as f32;
-
-        let fr = ((fg >> 16) & 0xFF) as f32;
-        let fg_ = ((fg >> 8) & 0xFF) as f32;
-        let fb = (fg & 0xFF) as f32;
-
-        let r = (br * inv + fr * alpha).round() as u32;
-        let g = (bg_ * inv + fg_ * alpha).round() as u32;
-        let b = (bb * inv + fb * alpha).round() as u32;
-
-        (r << 16) | (g << 8) | b
+        let style = context.theme.style_for(&self.draw_state());
+        context.draw_border(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+            style.border_width,
+            style.border_color.value,
+        );
     }
 
     fn handle_event_on_click(&mut self) {
         match self.component_state {
             ComponentState::Active => {}
             ComponentState::Hovered => {}
-            ComponentState::Pressed => {
-                if let Some(handler) = self.on_mouse_click.as_mut() {
-                    handler();
-                }
-            }
+            ComponentState::Pressed => self.click(),
             ComponentState::Focused => {}
             ComponentState::Disabled => {}
         }
     }
 
+    ///
+    /// Call the `on_click` handler and trigger the `Command`.
+    ///
+    fn click(&mut self) {
+        if let Some(handler) = self.on_mouse_click.as_mut() {
+            handler();
+        }
+        if let Some(command) = self.command.as_ref() {
+            command.trigger();
+        }
+    }
+
     fn handle_event_on_mouse_over(&mut self) {
         if let Some(handler) = self.on_mouse_over.as_mut() {
             handler();
@@ -278,6 +482,13 @@ impl Button {
         self.on_mouse_click = Some(Box::new(f));
         self
     }
+
+    ///
+    /// Returns the `FocusId` of the `Button`, e.g. for the `Tab` order of a `Dialog`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
 }
 
 impl Component for Button {
@@ -285,7 +496,8 @@ impl Component for Button {
         //
         // TODO: BUG: Need to be able to handle the case where the button is DISABLED.
         //
-        if self.component_state == ComponentState::Disabled {
+        if !self.is_enabled() {
+            self.mouse_dragging = false;
             return;
         }
         self.component_state = ComponentState::Active;
@@ -322,14 +534,33 @@ impl Component for Button {
     ) {
     }
 
-    fn handle_keyboard_event(&mut self, _event: &winit::event::KeyEvent) {
-        //
-        // Button does nothing on keyboard events.
-        //
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if focus::is_focused(self.focus_id)
+            && self.is_enabled()
+            && event.state == ElementState::Pressed
+            && event.logical_key == Key::Named(NamedKey::Space)
+        {
+            self.click();
+        }
     }
 
     fn draw(&self, context: &mut BufferContext) {
         self.draw_button(context);
+        if focus::is_focused(self.focus_id) {
+            let color = context
+                .theme
+                .style_for(&ComponentState::Focused)
+                .border_color
+                .value;
+            context.draw_border(
+                self.position.x + 2.0,
+                self.position.y + 2.0,
+                self.size.width.saturating_sub(4),
+                self.size.height.saturating_sub(4),
+                1,
+                color,
+            );
+        }
     }
 
     fn set_position(&mut self, x: f64, y: f64) {
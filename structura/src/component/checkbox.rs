@@ -0,0 +1,410 @@
+//!
+//! # Structura Component: Checkbox.
+//!
+//! A box that is checked, unchecked or, optionally, indeterminate, followed by a label.
+//!
+//! ```ignore
+//! let remember = Checkbox::new("Remember me")
+//!     .checked(true)
+//!     .on_change(|state| println!("{state:?}"));
+//! ```
+//!
+//! - Clicking the box or its label toggles it, as does `Space` while it has focus.
+//! - A tri-state `Checkbox` cycles from unchecked to checked to indeterminate. The
+//!   indeterminate state can always be set in code, e.g. for a "select all" box.
+//! - It is drawn with the theme's style for its state: hovered, pressed, focused or disabled.
+//!
+
+use crate::component::metrics;
+use crate::component::{Component, ComponentState};
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use winit::event::ElementState;
+use winit::keyboard::{Key, NamedKey};
+
+/// Space around the indicator and label.
+pub(crate) const PADDING: f64 = 4.0;
+
+/// Space between the indicator and the label.
+pub(crate) const LABEL_GAP: f64 = 8.0;
+
+///
+/// The state of a `Checkbox`.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum CheckState {
+    #[default]
+    Unchecked,
+    Checked,
+
+    /// Neither checked nor unchecked, e.g. for a box that stands for a partly checked list.
+    Indeterminate,
+}
+
+///
+/// Checkbox control with a label.
+///
+pub struct Checkbox {
+    position: Point,
+    size: Size,
+    text: String,
+    state: CheckState,
+    tri_state: bool,
+    enabled: bool,
+    pressed: bool,
+    focus_id: FocusId,
+    component_state: ComponentState,
+    on_change: Option<Box<dyn FnMut(CheckState)>>,
+}
+
+impl Checkbox {
+    ///
+    /// Constructor, sized to fit the label `text`.
+    ///
+    pub fn new(text: &str) -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: fit_size(text),
+            text: text.to_string(),
+            state: CheckState::Unchecked,
+            tri_state: false,
+            enabled: true,
+            pressed: false,
+            focus_id: FocusId::new(),
+            component_state: ComponentState::Active,
+            on_change: None,
+        }
+    }
+
+    ///
+    /// Set the initial state to checked or unchecked.
+    ///
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.state = if checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        };
+        self
+    }
+
+    ///
+    /// Set the initial state.
+    ///
+    pub fn state(mut self, state: CheckState) -> Self {
+        self.state = state;
+        self
+    }
+
+    ///
+    /// Let the user cycle through the indeterminate state as well.
+    ///
+    pub fn tri_state(mut self, tri_state: bool) -> Self {
+        self.tri_state = tri_state;
+        self
+    }
+
+    ///
+    /// Enable or disable the `Checkbox`. A disabled `Checkbox` ignores input.
+    ///
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    ///
+    /// Add event handler, called with the new state when the user changes it.
+    ///
+    pub fn on_change<F: FnMut(CheckState) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    pub fn check_state(&self) -> CheckState {
+        self.state
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.state == CheckState::Checked
+    }
+
+    ///
+    /// Change the state. Does not call `on_change`.
+    ///
+    pub fn set_state(&mut self, state: CheckState) {
+        self.state = state;
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.pressed = false;
+        self.component_state = if enabled {
+            ComponentState::Active
+        } else {
+            focus::clear_focus(self.focus_id);
+            ComponentState::Disabled
+        };
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    ///
+    /// Returns the `FocusId` of the `Checkbox`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    ///
+    /// Move to the next state, as if clicked, and call `on_change`.
+    ///
+    pub fn toggle(&mut self) {
+        self.state = match self.state {
+            CheckState::Unchecked => CheckState::Checked,
+            CheckState::Checked if self.tri_state => CheckState::Indeterminate,
+            CheckState::Checked | CheckState::Indeterminate => CheckState::Unchecked,
+        };
+        if let Some(handler) = self.on_change.as_mut() {
+            handler(self.state);
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+}
+
+impl Component for Checkbox {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if !self.enabled {
+            return;
+        }
+        let inside = self.bounds().contains(input.position.x, input.position.y);
+        if input.pressed {
+            if inside {
+                focus::set_focus(self.focus_id);
+                self.pressed = true;
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+        }
+        if input.just_released {
+            if self.pressed && inside {
+                self.toggle();
+            }
+            self.pressed = false;
+        }
+        self.component_state = match (inside, self.pressed) {
+            (true, true) => ComponentState::Pressed,
+            (true, false) => ComponentState::Hovered,
+            _ => ComponentState::Active,
+        };
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if self.enabled
+            && focus::is_focused(self.focus_id)
+            && event.state == ElementState::Pressed
+            && !event.repeat
+            && event.logical_key == Key::Named(NamedKey::Space)
+        {
+            self.toggle();
+        }
+    }
+
+    ///
+    /// Keeps the `CheckState`.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<CheckState>() {
+            self.state = *state;
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = focus::is_focused(self.focus_id);
+        let style = context
+            .theme
+            .style_for(&display_state(&self.component_state, focused));
+        let indicator = indicator_bounds(self.position, &self.size);
+        let (x, y) = (indicator.point.x, indicator.point.y);
+        let size = indicator.size.width;
+
+        context.fill_rect(x, y, size, size, style.edit_back_color.value);
+        context.draw_border(
+            x,
+            y,
+            size,
+            size,
+            style.border_width.max(1),
+            style.border_color.value,
+        );
+        let mark = style.edit_text_color.value;
+        let s = size as f64;
+        match self.state {
+            CheckState::Unchecked => {}
+            CheckState::Checked => {
+                //
+                // A tick, thickened by drawing it a few times a pixel apart.
+                //
+                let thickness = (s / 8.0).max(2.0) as i32;
+                for i in 0..thickness {
+                    let dy = i as f64;
+                    context.draw_line(
+                        x + s * 0.22,
+                        y + s * 0.5 + dy,
+                        x + s * 0.42,
+                        y + s * 0.7 + dy,
+                        mark,
+                    );
+                    context.draw_line(
+                        x + s * 0.42,
+                        y + s * 0.7 + dy,
+                        x + s * 0.78,
+                        y + s * 0.28 + dy,
+                        mark,
+                    );
+                }
+            }
+            CheckState::Indeterminate => {
+                let bar = (s / 6.0).max(2.0);
+                context.fill_rect(
+                    x + s * 0.25,
+                    y + (s - bar) / 2.0,
+                    (s * 0.5).round() as u32,
+                    bar.round() as u32,
+                    mark,
+                );
+            }
+        }
+
+        draw_label(
+            context,
+            &self.text,
+            self.position,
+            &self.size,
+            &style,
+            focused,
+        );
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
+
+///
+/// Returns the state to style a `Checkbox` or `RadioButton` with: the mouse state, or
+/// `Focused` when the mouse is elsewhere and the `Component` has focus.
+///
+pub(crate) fn display_state(state: &ComponentState, focused: bool) -> ComponentState {
+    match state {
+        ComponentState::Active if focused => ComponentState::Focused,
+        state => state.clone(),
+    }
+}
+
+///
+/// Returns the size that fits an indicator and the label `text`.
+///
+pub(crate) fn fit_size(text: &str) -> Size {
+    let line_height = metrics::line_height() as f64;
+    let indicator = indicator_size(line_height);
+    Size {
+        width: (PADDING * 2.0 + indicator + LABEL_GAP + metrics::text_width(text) as f64).ceil()
+            as u32,
+        height: (line_height + PADDING * 2.0).ceil() as u32,
+    }
+}
+
+///
+/// Returns the width and height of the indicator for text of `line_height`.
+///
+fn indicator_size(line_height: f64) -> f64 {
+    (line_height * 0.7).round()
+}
+
+///
+/// Returns the square bounds of the indicator, vertically centered at the left.
+///
+pub(crate) fn indicator_bounds(position: Point, size: &Size) -> Rectangle {
+    let indicator = indicator_size(metrics::line_height() as f64);
+    Rectangle::new(
+        (position.x + PADDING).round(),
+        (position.y + (size.height as f64 - indicator) / 2.0).round(),
+        indicator as u32,
+        indicator as u32,
+    )
+}
+
+///
+/// Draw the label after the indicator, with a focus border around it when `focused`.
+///
+pub(crate) fn draw_label(
+    context: &mut BufferContext,
+    text: &str,
+    position: Point,
+    size: &Size,
+    style: &crate::component::style::ComponentStyle,
+    focused: bool,
+) {
+    let indicator = indicator_bounds(position, size);
+    let line_height = context.line_height() as f64;
+    let text_x = indicator.point.x + indicator.size.width as f64 + LABEL_GAP;
+    let text_y = position.y + (size.height as f64 - line_height) / 2.0;
+    let bounds = Rectangle::new(position.x, position.y, size.width, size.height);
+    context.with_clip(&bounds, |context| {
+        context.draw_text(text, text_x, text_y, style.fore_color.value);
+        if focused {
+            let width = context.text_width(text).ceil() as u32;
+            context.draw_border(
+                text_x - 2.0,
+                text_y,
+                width + 4,
+                line_height as u32,
+                1,
+                style.cursor_color.value,
+            );
+        }
+    });
+}
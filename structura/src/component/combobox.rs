@@ -0,0 +1,799 @@
+//!
+//! # Structura Component: ComboBox.
+//!
+//! A dropdown that shows the selected item, and opens a list of items to choose from.
+//!
+//! ```ignore
+//! let country = ComboBox::new(countries, |country: &Country| country.name.clone())
+//!     .placeholder("Country")
+//!     .on_selection_changed(|_, country| println!("{}", country.code));
+//! ```
+//!
+//! - The items can be of any type, shown as text by the display closure.
+//! - The list opens in a layer above other content, so it is never clipped by the containers
+//!   around the `ComboBox`. It closes when an item is chosen, on `Escape`, or on a click
+//!   elsewhere.
+//! - `Up` / `Down`, `Home` / `End` move through the items, `Alt+Down` or `Space` open the list,
+//!   and `Enter` chooses the highlighted item. Typing the start of an item's text jumps to
+//!   it.
+//! - In editable mode, the `ComboBox` holds a `TextField`. Typing filters the list to the items
+//!   containing the text.
+//!
+
+use crate::component::textfield::TextField;
+use crate::component::{Component, ComponentState};
+use crate::event::focus::{self, FocusId};
+use crate::event::{self, MouseInput};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::layer::{self, Layer, LayerId, Placement};
+use crate::view::{BufferContext, blend_color};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::event::{ElementState, MouseScrollDelta};
+use winit::keyboard::{Key, NamedKey};
+
+/// Most items visible in the list at once. Longer lists scroll.
+const MAX_VISIBLE_ITEMS: usize = 8;
+
+/// Space above and below the text of each item, and left of all text.
+const PADDING: f64 = 8.0;
+
+/// Time after a key press after which type-ahead starts a new search.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Opacity of the placeholder text, blended over the background.
+const PLACEHOLDER_ALPHA: f32 = 0.45;
+
+/// Shows an item as text.
+type Display<T> = Box<dyn Fn(&T) -> String>;
+
+/// Called with the index and item when the selection changes.
+type SelectionHandler<T> = Box<dyn FnMut(usize, &T)>;
+
+///
+/// The list shared between a `ComboBox` and its popup.
+///
+struct PopupState {
+    /// The items in the list, as item index and text.
+    rows: RefCell<Vec<(usize, String)>>,
+    highlighted: Cell<Option<usize>>,
+    first_row: Cell<usize>,
+
+    /// The item clicked in the popup, for the `ComboBox` to select.
+    chosen: Cell<Option<usize>>,
+
+    /// Set when a click outside closed the popup, so that a click on the `ComboBox` closes
+    /// the list rather than opening it again.
+    dismissed: Cell<bool>,
+    layer: Cell<Option<LayerId>>,
+    row_height: f64,
+}
+
+impl PopupState {
+    fn visible_rows(&self) -> usize {
+        self.rows.borrow().len().min(MAX_VISIBLE_ITEMS)
+    }
+
+    ///
+    /// Highlight `row`, scrolling it into view.
+    ///
+    fn highlight(&self, row: usize) {
+        let count = self.rows.borrow().len();
+        if count == 0 {
+            self.highlighted.set(None);
+            return;
+        }
+        let row = row.min(count - 1);
+        self.highlighted.set(Some(row));
+        let first = self.first_row.get();
+        if row < first {
+            self.first_row.set(row);
+        } else if row >= first + MAX_VISIBLE_ITEMS {
+            self.first_row.set(row + 1 - MAX_VISIBLE_ITEMS);
+        }
+    }
+
+    fn scroll_by(&self, rows: isize) {
+        let count = self.rows.borrow().len();
+        let last = count.saturating_sub(MAX_VISIBLE_ITEMS) as isize;
+        let first = (self.first_row.get() as isize + rows).clamp(0, last);
+        self.first_row.set(first as usize);
+    }
+}
+
+///
+/// The list of items, shown in a layer while a `ComboBox` is open.
+///
+struct ComboPopup {
+    state: Rc<PopupState>,
+    position: Point,
+    width: u32,
+}
+
+impl ComboPopup {
+    fn row_at(&self, point: Point) -> Option<usize> {
+        let size = self.get_size();
+        let bounds = Rectangle::new(self.position.x, self.position.y, size.width, size.height);
+        if !bounds.contains(point.x, point.y) {
+            return None;
+        }
+        let row = ((point.y - self.position.y - 1.0) / self.state.row_height).max(0.0) as usize;
+        let row = self.state.first_row.get() + row;
+        (row < self.state.rows.borrow().len()).then_some(row)
+    }
+}
+
+impl Component for ComboPopup {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let Some(row) = self.row_at(input.position) else {
+            return;
+        };
+        self.state.highlighted.set(Some(row));
+        if input.pressed {
+            let item = self.state.rows.borrow()[row].0;
+            self.state.chosen.set(Some(item));
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+        let rows = match delta {
+            MouseScrollDelta::LineDelta(_, y) => -y.round() as isize,
+            MouseScrollDelta::PixelDelta(p) => (-p.y / self.state.row_height).round() as isize,
+        };
+        self.state.scroll_by(rows);
+    }
+
+    fn handle_keyboard_event(&mut self, _event: &winit::event::KeyEvent) {}
+
+    fn draw(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let size = self.get_size();
+        let (x, y) = (self.position.x, self.position.y);
+        context.fill_rect(x, y, size.width, size.height, style.edit_back_color.value);
+        let highlighted = context.theme.style_for(&ComponentState::Hovered);
+        let line_height = context.line_height() as f64;
+        let rows = self.state.rows.borrow();
+        let first = self.state.first_row.get();
+        let bounds = Rectangle::new(x, y, size.width, size.height);
+        context.with_clip(&bounds, |context| {
+            for (i, (_, text)) in rows.iter().enumerate().skip(first).take(MAX_VISIBLE_ITEMS) {
+                let row_y = y + 1.0 + (i - first) as f64 * self.state.row_height;
+                let mut color = style.edit_text_color.value;
+                if self.state.highlighted.get() == Some(i) {
+                    let (row_width, row_height) =
+                        (size.width.saturating_sub(2), self.state.row_height as u32);
+                    let back = highlighted.back_color.value;
+                    context.fill_rect(x + 1.0, row_y, row_width, row_height, back);
+                    color = highlighted.fore_color.value;
+                }
+                let text_y = row_y + (self.state.row_height - line_height) / 2.0;
+                context.draw_text(text, x + PADDING, text_y, color);
+            }
+        });
+        context.draw_border(x, y, size.width, size.height, 1, style.border_color.value);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, _height: usize) {
+        self.width = width as u32;
+    }
+
+    ///
+    /// Tall enough for the visible rows, and the border.
+    ///
+    fn get_size(&self) -> Size {
+        Size {
+            width: self.width,
+            height: (self.state.visible_rows() as f64 * self.state.row_height + 2.0).ceil() as u32,
+        }
+    }
+}
+
+///
+/// The state of a `ComboBox` kept by `Component::save_state()`.
+///
+struct ComboBoxState {
+    selected: Option<usize>,
+    text: String,
+}
+
+///
+/// ComboBox control for choosing one of a list of items of type `T`.
+///
+pub struct ComboBox<T> {
+    position: Point,
+    size: Size,
+    items: Vec<T>,
+    display: Display<T>,
+    selected: Option<usize>,
+    placeholder: String,
+
+    /// The text editor, in editable mode.
+    field: Option<TextField>,
+
+    /// Set by the `TextField` when its text is edited.
+    text_edited: Rc<Cell<bool>>,
+
+    /// Whether the list is filtered by the text, i.e. the text was edited since the last
+    /// selection.
+    filtering: bool,
+    popup: Rc<PopupState>,
+    type_ahead: String,
+    last_key: Option<Instant>,
+    focus_id: FocusId,
+    component_state: ComponentState,
+    on_selection_changed: Option<SelectionHandler<T>>,
+}
+
+impl<T: 'static> ComboBox<T> {
+    ///
+    /// Constructor, with the `items` to choose from, shown as text by `display`.
+    ///
+    pub fn new<F: Fn(&T) -> String + 'static>(items: Vec<T>, display: F) -> Self {
+        let row_height = (crate::component::metrics::line_height() as f64 + PADDING).ceil();
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 300,
+                height: TextField::new().get_size().height,
+            },
+            items,
+            display: Box::new(display),
+            selected: None,
+            placeholder: String::new(),
+            field: None,
+            text_edited: Rc::new(Cell::new(false)),
+            filtering: false,
+            popup: Rc::new(PopupState {
+                rows: RefCell::new(Vec::new()),
+                highlighted: Cell::new(None),
+                first_row: Cell::new(0),
+                chosen: Cell::new(None),
+                dismissed: Cell::new(false),
+                layer: Cell::new(None),
+                row_height,
+            }),
+            type_ahead: String::new(),
+            last_key: None,
+            focus_id: FocusId::new(),
+            component_state: ComponentState::Active,
+            on_selection_changed: None,
+        }
+    }
+
+    ///
+    /// Select the item at `index` initially.
+    ///
+    pub fn selected(mut self, index: usize) -> Self {
+        self.set_selected(Some(index));
+        self
+    }
+
+    ///
+    /// Let the user type free text, which filters the list.
+    ///
+    pub fn editable(mut self, editable: bool) -> Self {
+        self.field = editable.then(|| {
+            let edited = self.text_edited.clone();
+            let mut field = TextField::new()
+                .placeholder(&self.placeholder)
+                .on_text_change(move |_| edited.set(true));
+            if let Some(text) = self.selected_text() {
+                field.set_text(&text);
+            }
+            field
+        });
+        self.layout_field();
+        self
+    }
+
+    ///
+    /// Text shown in a dimmed color while nothing is selected.
+    ///
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_string();
+        if let Some(field) = self.field.take() {
+            self.field = Some(field.placeholder(placeholder));
+        }
+        self
+    }
+
+    ///
+    /// Add event handler, called with the index and item when the user selects an item.
+    ///
+    pub fn on_selection_changed<F: FnMut(usize, &T) + 'static>(mut self, f: F) -> Self {
+        self.on_selection_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    ///
+    /// Replace the items, clearing the selection and closing the list.
+    ///
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.close();
+        self.items = items;
+        self.selected = None;
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected_item(&self) -> Option<&T> {
+        self.selected.and_then(|index| self.items.get(index))
+    }
+
+    ///
+    /// Select the item at `index`, or none. Does not call `on_selection_changed`.
+    ///
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|&index| index < self.items.len());
+        self.filtering = false;
+        if let Some(text) = self.selected_text()
+            && let Some(field) = self.field.as_mut()
+        {
+            field.set_text(&text);
+        }
+    }
+
+    ///
+    /// Returns the text shown: the text typed in editable mode, or the selected item's text.
+    ///
+    pub fn text(&self) -> String {
+        match self.field.as_ref() {
+            Some(field) => field.text().to_string(),
+            None => self.selected_text().unwrap_or_default(),
+        }
+    }
+
+    ///
+    /// Returns the `FocusId` of the `ComboBox`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        match self.field.as_ref() {
+            Some(field) => field.focus_id(),
+            None => self.focus_id,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.popup.layer.get().is_some()
+    }
+
+    ///
+    /// Open the list, highlighting the selected item.
+    ///
+    pub fn open(&mut self) {
+        self.close();
+        self.update_rows();
+        if self.popup.rows.borrow().is_empty() {
+            return;
+        }
+        let selected_row = self
+            .popup
+            .rows
+            .borrow()
+            .iter()
+            .position(|(index, _)| Some(*index) == self.selected);
+        self.popup.first_row.set(0);
+        self.popup.highlight(selected_row.unwrap_or(0));
+
+        let bounds = self.bounds();
+        let mut popup = ComboPopup {
+            state: self.popup.clone(),
+            position: Point { x: 0.0, y: 0.0 },
+            width: self.size.width,
+        };
+        popup.set_position(bounds.point.x, bounds.point.y + bounds.size.height as f64);
+        let state = self.popup.clone();
+        let layer = Layer::new(Box::new(popup))
+            .anchored(bounds, Placement::Below)
+            .light_dismiss(true)
+            .keyboard_input(false)
+            .on_dismiss(move || {
+                state.layer.set(None);
+                state.dismissed.set(true);
+            });
+        self.popup.layer.set(Some(layer::open(layer)));
+    }
+
+    pub fn close(&mut self) {
+        if let Some(layer) = self.popup.layer.take() {
+            layer::close(layer);
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    ///
+    /// Returns the bounds of the arrow button at the right.
+    ///
+    fn arrow_bounds(&self) -> Rectangle {
+        let width = self.size.height.min(self.size.width);
+        Rectangle::new(
+            self.position.x + (self.size.width - width) as f64,
+            self.position.y,
+            width,
+            self.size.height,
+        )
+    }
+
+    fn layout_field(&mut self) {
+        let arrow_width = self.arrow_bounds().size.width;
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width - arrow_width, self.size.height);
+        if let Some(field) = self.field.as_mut() {
+            field.set_position(x, y);
+            field.set_size(width as usize, height as usize);
+        }
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        self.selected_item().map(|item| (self.display)(item))
+    }
+
+    ///
+    /// Fill the list with the items, filtered by the text while filtering.
+    ///
+    fn update_rows(&self) {
+        let filter = match self.field.as_ref() {
+            Some(field) if self.filtering => field.text().to_lowercase(),
+            _ => String::new(),
+        };
+        let rows = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (index, (self.display)(item)))
+            .filter(|(_, text)| text.to_lowercase().contains(&filter))
+            .collect();
+        *self.popup.rows.borrow_mut() = rows;
+    }
+
+    ///
+    /// Select the item at `index` on behalf of the user, calling `on_selection_changed`.
+    ///
+    fn choose(&mut self, index: usize) {
+        let changed = self.selected != Some(index);
+        self.set_selected(Some(index));
+        if changed && let Some(handler) = self.on_selection_changed.as_mut() {
+            handler(index, &self.items[index]);
+        }
+    }
+
+    ///
+    /// Move the highlight in the open list, or the selection when closed, to `row`.
+    ///
+    fn move_to(&mut self, row: impl Fn(Option<usize>, usize) -> usize) {
+        if self.is_open() {
+            let count = self.popup.rows.borrow().len();
+            if count > 0 {
+                self.popup
+                    .highlight(row(self.popup.highlighted.get(), count));
+            }
+        } else if !self.items.is_empty() {
+            self.choose(row(self.selected, self.items.len()).min(self.items.len() - 1));
+        }
+    }
+
+    ///
+    /// Returns true if a type-ahead search is under way, so that `Space` is part of it.
+    ///
+    fn typing(&self) -> bool {
+        self.last_key
+            .is_some_and(|last| last.elapsed() <= TYPE_AHEAD_TIMEOUT)
+    }
+
+    ///
+    /// Jump to the next item starting with the text typed within `TYPE_AHEAD_TIMEOUT`.
+    ///
+    fn type_ahead(&mut self, text: &str) {
+        if !self.typing() {
+            self.type_ahead.clear();
+        }
+        let now = Instant::now();
+        self.last_key = Some(now);
+        self.type_ahead.push_str(&text.to_lowercase());
+
+        let open = self.is_open();
+        let labels: Vec<String> = if open {
+            self.popup
+                .rows
+                .borrow()
+                .iter()
+                .map(|(_, text)| text.to_lowercase())
+                .collect()
+        } else {
+            self.items
+                .iter()
+                .map(|item| (self.display)(item).to_lowercase())
+                .collect()
+        };
+        let current = if open {
+            self.popup.highlighted.get()
+        } else {
+            self.selected
+        };
+        //
+        // A new search starts after the current item, so that typing the same letter again
+        // cycles through the items starting with it.
+        //
+        let start = match current {
+            Some(current) if self.type_ahead.chars().count() == 1 => current + 1,
+            Some(current) => current,
+            None => 0,
+        };
+        let count = labels.len();
+        let found = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&i| labels[i].starts_with(&self.type_ahead));
+        match found {
+            Some(row) if open => self.popup.highlight(row),
+            Some(index) => self.choose(index),
+            None => {}
+        }
+    }
+
+    ///
+    /// Choose the highlighted item in the open list, and close it.
+    ///
+    fn choose_highlighted(&mut self) {
+        let item = self
+            .popup
+            .highlighted
+            .get()
+            .and_then(|row| self.popup.rows.borrow().get(row).map(|(index, _)| *index));
+        self.close();
+        if let Some(index) = item {
+            self.choose(index);
+        }
+    }
+
+    ///
+    /// Handle the keys that drive the list. Returns true if `key` was used.
+    ///
+    fn handle_list_key(&mut self, key: &Key) -> bool {
+        let open = self.is_open();
+        match key {
+            Key::Named(NamedKey::ArrowDown) if event::modifiers().alt_key() => {
+                if !open {
+                    self.open();
+                }
+            }
+            Key::Named(NamedKey::ArrowDown) => {
+                self.move_to(|current, _| current.map_or(0, |current| current + 1));
+            }
+            Key::Named(NamedKey::ArrowUp) => {
+                self.move_to(|current, _| current.map_or(0, |current| current.saturating_sub(1)));
+            }
+            Key::Named(NamedKey::Home) if self.field.is_none() || open => {
+                self.move_to(|_, _| 0);
+            }
+            Key::Named(NamedKey::End) if self.field.is_none() || open => {
+                self.move_to(|_, count| count - 1);
+            }
+            Key::Named(NamedKey::PageDown) if open => {
+                self.move_to(|current, _| current.map_or(0, |c| c + MAX_VISIBLE_ITEMS));
+            }
+            Key::Named(NamedKey::PageUp) if open => {
+                self.move_to(|current, _| {
+                    current.map_or(0, |c| c.saturating_sub(MAX_VISIBLE_ITEMS))
+                });
+            }
+            Key::Named(NamedKey::Enter) if open => self.choose_highlighted(),
+            Key::Named(NamedKey::Escape) if open => self.close(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl<T> Drop for ComboBox<T> {
+    fn drop(&mut self) {
+        if let Some(layer) = self.popup.layer.take() {
+            layer::close(layer);
+        }
+    }
+}
+
+impl<T: 'static> Component for ComboBox<T> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if let Some(field) = self.field.as_mut() {
+            field.handle_mouse_event(input);
+        }
+        let inside = self.bounds().contains(input.position.x, input.position.y);
+        if input.pressed {
+            let dismissed = self.popup.dismissed.replace(false);
+            if inside {
+                focus::set_focus(self.focus_id());
+                let on_arrow = self
+                    .arrow_bounds()
+                    .contains(input.position.x, input.position.y);
+                if dismissed || self.is_open() {
+                    self.close();
+                } else if self.field.is_none() || on_arrow {
+                    self.filtering = false;
+                    self.open();
+                }
+            } else if !input.is_elsewhere() {
+                focus::clear_focus(self.focus_id);
+            }
+        }
+        if let Some(index) = self.popup.chosen.take() {
+            self.close();
+            self.choose(index);
+            focus::set_focus(self.focus_id());
+        }
+        self.component_state = if inside {
+            ComponentState::Hovered
+        } else {
+            ComponentState::Active
+        };
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if !focus::is_focused(self.focus_id()) {
+            return;
+        }
+        if event.state == ElementState::Pressed && self.handle_list_key(&event.logical_key) {
+            return;
+        }
+        let Some(field) = self.field.as_mut() else {
+            if event.state != ElementState::Pressed {
+                return;
+            }
+            let modifiers = event::modifiers();
+            match &event.logical_key {
+                Key::Named(NamedKey::Space) if !self.is_open() => self.open(),
+                Key::Named(NamedKey::Space) if self.typing() => self.type_ahead(" "),
+                Key::Named(NamedKey::Space) => self.choose_highlighted(),
+                Key::Character(text) if !modifiers.control_key() && !modifiers.alt_key() => {
+                    self.type_ahead(text);
+                }
+                _ => {}
+            }
+            return;
+        };
+
+        field.handle_keyboard_event(event);
+        if self.text_edited.replace(false) {
+            self.filtering = true;
+            self.update_rows();
+            let empty = self.popup.rows.borrow().is_empty();
+            if empty {
+                self.close();
+            } else {
+                //
+                // Reopen, so the popup is placed again for its new height.
+                //
+                self.open();
+            }
+        }
+    }
+
+    ///
+    /// Keeps the selected index and, in editable mode, the text.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(ComboBoxState {
+            selected: self.selected,
+            text: self.text(),
+        }))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<ComboBoxState>() {
+            self.set_selected(state.selected);
+            if let Some(field) = self.field.as_mut() {
+                field.set_text(&state.text);
+            }
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = focus::is_focused(self.focus_id());
+        let state = match &self.component_state {
+            ComponentState::Active if focused => ComponentState::Focused,
+            state => state.clone(),
+        };
+        let style = context.theme.style_for(&state);
+        let (x, y, w, h) = (
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        );
+
+        match self.field.as_ref() {
+            Some(field) => field.draw(context),
+            None => {
+                context.fill_rect(x, y, w, h, style.back_color.value);
+                let line_height = context.line_height() as f64;
+                let text_y = y + (h as f64 - line_height) / 2.0;
+                let (text, color) = match self.selected_text() {
+                    Some(text) => (text, style.fore_color.value),
+                    None => {
+                        let back = style.back_color.value;
+                        let dimmed = blend_color(back, style.fore_color.value, PLACEHOLDER_ALPHA);
+                        (self.placeholder.clone(), 0xFF000000 | dimmed)
+                    }
+                };
+                let text_bounds = Rectangle::new(x, y, w - self.arrow_bounds().size.width, h);
+                context.with_clip(&text_bounds, |context| {
+                    context.draw_text(&text, x + PADDING, text_y, color);
+                });
+                context.draw_border(x, y, w, h, style.border_width, style.border_color.value);
+            }
+        }
+
+        //
+        // The arrow button: a chevron pointing down, or up while the list is open.
+        //
+        let arrow = self.arrow_bounds();
+        let (ax, ay) = (arrow.point.x, arrow.point.y);
+        let (aw, ah) = (arrow.size.width, arrow.size.height);
+        context.fill_rect(ax, ay, aw, ah, style.back_color.value);
+        context.draw_border(ax, ay, aw, ah, style.border_width, style.border_color.value);
+        let (cx, cy) = (ax + aw as f64 / 2.0, ay + ah as f64 / 2.0);
+        let half = (aw as f64 / 6.0).round();
+        let dir = if self.is_open() { -1.0 } else { 1.0 };
+        for offset in 0..2 {
+            let cy = cy + offset as f64 - dir * half / 2.0;
+            let tip = cy + dir * half;
+            context.draw_line(cx - half, cy, cx, tip, style.fore_color.value);
+            context.draw_line(cx, tip, cx + half, cy, style.fore_color.value);
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.layout_field();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.layout_field();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
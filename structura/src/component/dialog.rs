@@ -0,0 +1,790 @@
+//!
+//! # Structura Component: Dialog.
+//!
+//! A modal dialog with a title, content and a row of buttons, shown centered above the window.
+//!
+//! ```ignore
+//! confirm("Quit", "Discard the unsaved changes?")
+//!     .on_result(|discard| if discard { quit() })
+//!     .show();
+//!
+//! // Or, from async code:
+//! let name = prompt("Rename", "New name:", "untitled").show().await;
+//! ```
+//!
+//! - `Enter` chooses the default button, the first with `ButtonRole::Accept`. `Escape`
+//!   chooses the cancel button, the first with `ButtonRole::Reject`, and closes the dialog
+//!   even when there is none.
+//! - While the dialog is open, input does not reach the window below it. `Tab` and
+//!   `Shift+Tab` move the focus through the parts of the content added with `focus()`, then
+//!   the buttons, and `Enter` or `Space` press the focused button. The focus from before the
+//!   dialog opened is given back when it closes.
+//! - The result is passed to the `on_result()` handler, and to the `DialogFuture` returned by
+//!   `show()`, which can be awaited from any thread.
+//! - A `validate()` handler can keep the dialog open, e.g. while the content is not valid, and
+//!   a `DialogHandle` closes it from elsewhere, e.g. after asking for confirmation.
+//! - `message_box()`, `confirm()` and `prompt()` build the common dialogs.
+//! - Dialogs are drawn on a `Layer` inside the application window. `Application` drives a
+//!   single winit window and surface, so dialogs in separate windows are left for when it can
+//!   manage more than one.
+//!
+
+use crate::component::button::Button;
+use crate::component::label::Label;
+use crate::component::textfield::TextField;
+use crate::component::{Component, ComponentHandle, ComponentState, metrics};
+use crate::event::focus::{self, FocusId};
+use crate::event::{self, MouseInput};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::layer::{self, Layer, LayerId};
+use crate::view::BufferContext;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+use winit::event::{ElementState, KeyEvent, MouseScrollDelta, TouchPhase};
+use winit::keyboard::{Key, NamedKey};
+
+/// Space between the content and the edge of the dialog.
+const PADDING: f64 = 16.0;
+
+/// Space above and below the title.
+const TITLE_PADDING: f64 = 8.0;
+
+/// Space between the buttons.
+const BUTTON_SPACING: f64 = 8.0;
+
+const MIN_WIDTH: u32 = 320;
+const MIN_BUTTON_WIDTH: u32 = 96;
+
+/// Messages wider than this wrap.
+const MAX_MESSAGE_WIDTH: u32 = 480;
+
+/// Width of the outline around the focused button.
+const FOCUS_WIDTH: usize = 2;
+
+///
+/// What choosing a button of a `Dialog` means.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ButtonRole {
+    /// Confirms the dialog. The first `Accept` button is the default, chosen by `Enter`.
+    Accept,
+
+    /// Cancels the dialog. The first `Reject` button is chosen by `Escape`.
+    Reject,
+    Other,
+}
+
+///
+/// How a `Dialog` was closed.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DialogResult {
+    /// The index of the chosen button, or `None` when closed by `Escape` without a `Reject`
+    /// button.
+    pub button: Option<usize>,
+    pub role: ButtonRole,
+}
+
+impl DialogResult {
+    pub fn is_accepted(&self) -> bool {
+        self.role == ButtonRole::Accept
+    }
+}
+
+/// Maps the `DialogResult` to the result of a `Dialog`.
+type ResultMap<T> = Box<dyn FnOnce(DialogResult) -> T>;
+
+/// Delivers the `DialogResult` of a closed `Dialog`.
+type FinishHandler = Box<dyn FnOnce(DialogResult)>;
+
+/// Decides whether a `Dialog` may close with a `DialogResult`.
+type Validator = Box<dyn FnMut(DialogResult) -> bool>;
+
+///
+/// What an open dialog needs to close, shared by its panel and each `DialogHandle`.
+///
+#[derive(Default)]
+struct DialogCore {
+    layer_id: Cell<Option<LayerId>>,
+    finish: RefCell<Option<FinishHandler>>,
+
+    /// The focus of the dialog and its content, cleared on close.
+    focus_ids: RefCell<Vec<FocusId>>,
+
+    /// The focus before the dialog opened, given back on close.
+    previous_focus: Cell<Option<FocusId>>,
+}
+
+impl DialogCore {
+    fn close(&self, result: DialogResult) {
+        let Some(finish) = self.finish.borrow_mut().take() else {
+            return;
+        };
+        if let Some(id) = self.layer_id.get() {
+            layer::close(id);
+        }
+        for id in self.focus_ids.borrow().iter() {
+            focus::clear_focus(*id);
+        }
+        if let Some(previous) = self.previous_focus.get() {
+            focus::set_focus(previous);
+        }
+        finish(result);
+    }
+}
+
+///
+/// Closes a `Dialog` from outside its buttons, e.g. once its content has the answer.
+///
+#[derive(Clone)]
+pub struct DialogHandle {
+    core: Rc<DialogCore>,
+}
+
+impl DialogHandle {
+    ///
+    /// Close the dialog with `result`, without calling the `validate()` handler.
+    ///
+    pub fn close(&self, result: DialogResult) {
+        self.core.close(result);
+    }
+
+    ///
+    /// Returns `true` while the dialog is shown.
+    ///
+    pub fn is_open(&self) -> bool {
+        self.core.layer_id.get().is_some() && self.core.finish.borrow().is_some()
+    }
+}
+
+///
+/// A modal dialog, built and then opened with `show()`.
+///
+/// The result is a `DialogResult` unless mapped to another type with `map()`, e.g. `bool` for
+/// `confirm()`.
+///
+pub struct Dialog<T = DialogResult> {
+    title: String,
+    message: Option<String>,
+    content: Option<Box<dyn Component>>,
+    content_focus: Vec<FocusId>,
+    buttons: Vec<(String, ButtonRole)>,
+    validate: Option<Validator>,
+    map: ResultMap<T>,
+    on_result: Option<Box<dyn FnOnce(T)>>,
+    core: Rc<DialogCore>,
+}
+
+impl Dialog {
+    ///
+    /// Constructor, for a dialog without content or buttons.
+    ///
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            message: None,
+            content: None,
+            content_focus: vec![],
+            buttons: vec![],
+            validate: None,
+            map: Box::new(|result| result),
+            on_result: None,
+            core: Rc::new(DialogCore::default()),
+        }
+    }
+}
+
+impl<T: Clone + 'static> Dialog<T> {
+    ///
+    /// Text shown above the content. Long messages wrap.
+    ///
+    pub fn message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    ///
+    /// The `Component` shown below the message, stretched to the width of the dialog.
+    ///
+    pub fn content(mut self, content: Box<dyn Component>) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    ///
+    /// Add a `FocusId` of the content to the `Tab` order, after those added before. The first
+    /// is focused when the dialog opens.
+    ///
+    pub fn focus(mut self, focus_id: FocusId) -> Self {
+        self.content_focus.push(focus_id);
+        self
+    }
+
+    ///
+    /// Add a button to the right of the button row.
+    ///
+    pub fn button(mut self, text: &str, role: ButtonRole) -> Self {
+        self.buttons.push((text.to_string(), role));
+        self
+    }
+
+    ///
+    /// Map the result to another type. Replaces the `on_result()` handler, so call it first.
+    ///
+    pub fn map<U, F: FnOnce(T) -> U + 'static>(self, f: F) -> Dialog<U> {
+        let map = self.map;
+        Dialog {
+            title: self.title,
+            message: self.message,
+            content: self.content,
+            content_focus: self.content_focus,
+            buttons: self.buttons,
+            validate: self.validate,
+            map: Box::new(move |result| f(map(result))),
+            on_result: None,
+            core: self.core,
+        }
+    }
+
+    ///
+    /// Add event handler, called before the dialog closes with a button, `Enter` or `Escape`.
+    /// Returning `false` keeps the dialog open, e.g. while the content is not valid.
+    ///
+    pub fn validate<F: FnMut(DialogResult) -> bool + 'static>(mut self, f: F) -> Self {
+        self.validate = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Returns a `DialogHandle` to close the dialog once shown.
+    ///
+    pub fn handle(&self) -> DialogHandle {
+        DialogHandle {
+            core: self.core.clone(),
+        }
+    }
+
+    ///
+    /// Add event handler, called with the result when the dialog closes.
+    ///
+    pub fn on_result<F: FnOnce(T) + 'static>(mut self, f: F) -> Self {
+        self.on_result = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Open the dialog above all other layers. Returns a future for the result.
+    ///
+    pub fn show(self) -> DialogFuture<T> {
+        let slot = Arc::new(Mutex::new(ResultSlot {
+            result: None,
+            waker: None,
+        }));
+        let finish = {
+            let slot = slot.clone();
+            let map = self.map;
+            let on_result = self.on_result;
+            move |result| {
+                let result = map(result);
+                {
+                    let mut slot = slot.lock().unwrap();
+                    slot.result = Some(result.clone());
+                    if let Some(waker) = slot.waker.take() {
+                        waker.wake();
+                    }
+                }
+                if let Some(handler) = on_result {
+                    handler(result);
+                }
+            }
+        };
+        *self.core.finish.borrow_mut() = Some(Box::new(finish));
+        let panel = DialogPanel::new(
+            self.title,
+            self.message,
+            self.content,
+            self.content_focus,
+            self.buttons,
+            self.validate,
+            self.core.clone(),
+        );
+        let layer = Layer::new(Box::new(panel)).modal(true).centered(true);
+        self.core.layer_id.set(Some(layer::open(layer)));
+        DialogFuture { slot }
+    }
+}
+
+struct ResultSlot<T> {
+    result: Option<T>,
+    waker: Option<Waker>,
+}
+
+///
+/// The result of a `Dialog`, ready once it closes.
+///
+/// The dialog itself lives on the UI thread, but the future can be sent to and awaited on any
+/// thread, e.g. in a `tokio` task.
+///
+pub struct DialogFuture<T> {
+    slot: Arc<Mutex<ResultSlot<T>>>,
+}
+
+impl<T> DialogFuture<T> {
+    ///
+    /// Returns `true` once the dialog has closed.
+    ///
+    pub fn is_ready(&self) -> bool {
+        self.slot.lock().unwrap().result.is_some()
+    }
+}
+
+impl<T> Future for DialogFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut slot = self.slot.lock().unwrap();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+///
+/// Show a message with an OK button.
+///
+pub fn message_box(title: &str, message: &str) -> Dialog<()> {
+    Dialog::new(title)
+        .message(message)
+        .button("OK", ButtonRole::Accept)
+        .map(|_| ())
+}
+
+///
+/// Ask a question with OK and Cancel buttons. The result is `true` for OK.
+///
+pub fn confirm(title: &str, message: &str) -> Dialog<bool> {
+    Dialog::new(title)
+        .message(message)
+        .button("OK", ButtonRole::Accept)
+        .button("Cancel", ButtonRole::Reject)
+        .map(|result| result.is_accepted())
+}
+
+///
+/// Ask for a line of text, starting with `text` selected. The result is `None` on Cancel.
+///
+pub fn prompt(title: &str, message: &str, text: &str) -> Dialog<Option<String>> {
+    let mut field = TextField::new();
+    field.set_text(text);
+    field.select_all();
+    let focus_id = field.focus_id();
+    let field = Rc::new(RefCell::new(field));
+    Dialog::new(title)
+        .message(message)
+        .content(Box::new(ComponentHandle::new(field.clone())))
+        .focus(focus_id)
+        .button("OK", ButtonRole::Accept)
+        .button("Cancel", ButtonRole::Reject)
+        .map(move |result| {
+            result
+                .is_accepted()
+                .then(|| field.borrow().text().to_string())
+        })
+}
+
+///
+/// The `Component` of an open `Dialog`, in a modal layer.
+///
+struct DialogPanel {
+    position: Point,
+    size: Size,
+    title: String,
+    message: Option<Label>,
+    content: Option<Box<dyn Component>>,
+    content_focus: Vec<FocusId>,
+    buttons: Vec<(Button, ButtonRole)>,
+
+    /// Set by the `on_click` of a button.
+    clicked: Rc<Cell<Option<usize>>>,
+
+    /// The button with keyboard focus, while the dialog itself has focus.
+    focused_button: Option<usize>,
+    focus_id: FocusId,
+    validate: Option<Validator>,
+    core: Rc<DialogCore>,
+}
+
+impl DialogPanel {
+    fn new(
+        title: String,
+        message: Option<String>,
+        content: Option<Box<dyn Component>>,
+        content_focus: Vec<FocusId>,
+        buttons: Vec<(String, ButtonRole)>,
+        validate: Option<Validator>,
+        core: Rc<DialogCore>,
+    ) -> Self {
+        let message = message.map(|message| {
+            let mut label = Label::new(&message.replace('&', "&&"));
+            if label.get_size().width > MAX_MESSAGE_WIDTH {
+                label = label.wrap(true);
+                let height = label.height_for_width(MAX_MESSAGE_WIDTH);
+                label.set_size(MAX_MESSAGE_WIDTH as usize, height as usize);
+            }
+            label
+        });
+        let clicked = Rc::new(Cell::new(None));
+        let buttons = buttons
+            .into_iter()
+            .enumerate()
+            .map(|(index, (text, role))| {
+                let clicked = clicked.clone();
+                let mut button = Button::default()
+                    .set_text(text)
+                    .on_click(move || clicked.set(Some(index)));
+                let size = button.preferred_size();
+                let width = size.width.max(MIN_BUTTON_WIDTH);
+                button.set_size(width as usize, size.height as usize);
+                (button, role)
+            })
+            .collect();
+        let mut panel = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            title,
+            message,
+            content,
+            content_focus,
+            buttons,
+            clicked,
+            focused_button: None,
+            focus_id: FocusId::new(),
+            validate,
+            core,
+        };
+        panel.core.previous_focus.set(focus::focused());
+        let mut focus_ids = vec![panel.focus_id];
+        focus_ids.extend(&panel.content_focus);
+        *panel.core.focus_ids.borrow_mut() = focus_ids;
+        panel.fit();
+        match panel.content_focus.first() {
+            Some(&content_focus) => focus::set_focus(content_focus),
+            None => panel.focus_button(panel.default_button().unwrap_or(0)),
+        }
+        panel
+    }
+
+    fn title_height() -> f64 {
+        (metrics::line_height() as f64 + 2.0 * TITLE_PADDING).ceil()
+    }
+
+    fn button_row_size(&self) -> Size {
+        let count = self.buttons.len() as f64;
+        let width = self
+            .buttons
+            .iter()
+            .map(|(button, _)| button.get_size().width as f64)
+            .sum::<f64>()
+            + BUTTON_SPACING * (count - 1.0).max(0.0);
+        let height = self
+            .buttons
+            .iter()
+            .map(|(button, _)| button.get_size().height)
+            .max()
+            .unwrap_or(0);
+        Size {
+            width: width.ceil() as u32,
+            height,
+        }
+    }
+
+    ///
+    /// Size the dialog to fit the title, message, content and buttons.
+    ///
+    fn fit(&mut self) {
+        let row = self.button_row_size();
+        let widths = [
+            metrics::text_width(&self.title).ceil() as u32,
+            self.message.as_ref().map_or(0, |m| m.get_size().width),
+            self.content.as_ref().map_or(0, |c| c.get_size().width),
+            row.width,
+        ];
+        let inner_width = widths.into_iter().max().unwrap_or(0) as f64;
+        let width = (inner_width + 2.0 * PADDING).ceil() as u32;
+
+        let mut height = Self::title_height() + PADDING;
+        if let Some(message) = self.message.as_ref() {
+            height += message.get_size().height as f64 + PADDING;
+        }
+        if let Some(content) = self.content.as_ref() {
+            height += content.get_size().height as f64 + PADDING;
+        }
+        if row.height > 0 {
+            height += row.height as f64 + PADDING;
+        }
+        self.size = Size {
+            width: width.max(MIN_WIDTH),
+            height: height.ceil() as u32,
+        };
+        self.layout();
+    }
+
+    ///
+    /// Place the message and content from the top, and the buttons at the bottom right.
+    ///
+    fn layout(&mut self) {
+        let x = self.position.x + PADDING;
+        let inner_width = (self.size.width as f64 - 2.0 * PADDING).max(0.0) as usize;
+        let mut y = self.position.y + Self::title_height() + PADDING;
+        if let Some(message) = self.message.as_mut() {
+            message.set_position(x, y);
+            y += message.get_size().height as f64 + PADDING;
+        }
+        if let Some(content) = self.content.as_mut() {
+            let height = content.get_size().height;
+            content.set_size(inner_width, height as usize);
+            content.set_position(x, y);
+        }
+        let row = self.button_row_size();
+        let bottom = self.position.y + self.size.height as f64 - PADDING;
+        let mut button_x = self.position.x + self.size.width as f64 - PADDING - row.width as f64;
+        for (button, _) in self.buttons.iter_mut() {
+            let size = button.get_size();
+            button.set_position(button_x, bottom - size.height as f64);
+            button_x += size.width as f64 + BUTTON_SPACING;
+        }
+    }
+
+    fn default_button(&self) -> Option<usize> {
+        self.button_with_role(ButtonRole::Accept)
+    }
+
+    fn button_with_role(&self, role: ButtonRole) -> Option<usize> {
+        self.buttons.iter().position(|(_, r)| *r == role)
+    }
+
+    fn focus_button(&mut self, index: usize) {
+        if index < self.buttons.len() {
+            self.focused_button = Some(index);
+            focus::set_focus(self.focus_id);
+        }
+    }
+
+    ///
+    /// Returns the focused button, if the focus is on the buttons rather than the content.
+    ///
+    fn focused_button(&self) -> Option<usize> {
+        self.focused_button
+            .filter(|_| focus::is_focused(self.focus_id))
+    }
+
+    ///
+    /// Move the focus to the next or previous stop: each stop of the content, then each
+    /// button. From a part of the content that is not a stop, `Tab` goes to the first stop.
+    ///
+    fn move_focus(&mut self, forward: bool) {
+        let content_stops = self.content_focus.len();
+        let stops = content_stops + self.buttons.len();
+        if stops == 0 {
+            return;
+        }
+        let focused = focus::focused();
+        let current = match self.focused_button() {
+            Some(index) => Some(content_stops + index),
+            None => self
+                .content_focus
+                .iter()
+                .position(|&id| Some(id) == focused),
+        };
+        let next = match current {
+            Some(current) if forward => (current + 1) % stops,
+            Some(current) => (current + stops - 1) % stops,
+            None if forward => 0,
+            None => stops - 1,
+        };
+        if next < content_stops {
+            self.focused_button = None;
+            focus::set_focus(self.content_focus[next]);
+        } else {
+            self.focus_button(next - content_stops);
+        }
+    }
+
+    ///
+    /// Close the dialog, with the button at `index` chosen, or `role` when there is none,
+    /// unless the `validate` handler keeps it open.
+    ///
+    fn close(&mut self, index: Option<usize>, role: ButtonRole) {
+        let role = index.map_or(role, |index| self.buttons[index].1);
+        let result = DialogResult {
+            button: index,
+            role,
+        };
+        if let Some(validate) = self.validate.as_mut()
+            && !validate(result)
+        {
+            return;
+        }
+        self.core.close(result);
+    }
+
+    fn choose(&mut self, index: usize) {
+        self.close(Some(index), ButtonRole::Other);
+    }
+
+    fn cancel(&mut self) {
+        let index = self.button_with_role(ButtonRole::Reject);
+        self.close(index, ButtonRole::Reject);
+    }
+}
+
+impl Component for DialogPanel {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if let Some(message) = self.message.as_mut() {
+            message.handle_mouse_event(input);
+        }
+        if let Some(content) = self.content.as_mut() {
+            content.handle_mouse_event(input);
+        }
+        for (button, _) in self.buttons.iter_mut() {
+            Component::handle_mouse_event(button, input);
+        }
+        if let Some(index) = self.clicked.take() {
+            self.choose(index);
+        }
+    }
+
+    fn handle_mouse_wheel_event(&mut self, delta: &MouseScrollDelta, phase: &TouchPhase) {
+        if let Some(content) = self.content.as_mut() {
+            content.handle_mouse_wheel_event(delta, phase);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &KeyEvent) {
+        let pressed = event.state == ElementState::Pressed;
+        let focused_button = self.focused_button();
+        match &event.logical_key {
+            Key::Named(NamedKey::Tab) => {
+                if pressed {
+                    self.move_focus(!event::modifiers().shift_key());
+                }
+            }
+            Key::Named(NamedKey::Enter) => {
+                if pressed && let Some(index) = focused_button.or(self.default_button()) {
+                    self.choose(index);
+                }
+            }
+            Key::Named(NamedKey::Escape) => {
+                if pressed {
+                    self.cancel();
+                }
+            }
+            Key::Named(NamedKey::Space) if focused_button.is_some() => {
+                if pressed && let Some(index) = focused_button {
+                    self.choose(index);
+                }
+            }
+            _ => {
+                if let Some(content) = self.content.as_mut() {
+                    content.handle_keyboard_event(event);
+                }
+            }
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        if let Some(content) = self.content.as_mut() {
+            content.animate(now);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let title_style = context.theme.style_for(&ComponentState::Hovered);
+        let focus_color = context
+            .theme
+            .style_for(&ComponentState::Focused)
+            .border_color
+            .value;
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width, self.size.height);
+        context.fill_rect(x, y, width, height, style.back_color.value);
+
+        let title_height = Self::title_height();
+        let back = title_style.back_color.value;
+        context.fill_rect(x, y, width, title_height as u32, back);
+        let bounds = Rectangle::new(x, y, width, title_height as u32);
+        context.with_clip(&bounds, |context| {
+            let color = title_style.fore_color.value;
+            context.draw_text(&self.title, x + PADDING, y + TITLE_PADDING, color);
+        });
+        let border_color = style.border_color.value;
+        let title_bottom = y + title_height - 1.0;
+        context.draw_line(
+            x,
+            title_bottom,
+            x + width as f64,
+            title_bottom,
+            border_color,
+        );
+        let border_width = style.border_width.max(1);
+        context.draw_border(x, y, width, height, border_width, border_color);
+
+        if let Some(message) = self.message.as_ref() {
+            message.draw(context);
+        }
+        if let Some(content) = self.content.as_ref() {
+            content.draw(context);
+        }
+        let outlined = self.focused_button().or(self.default_button());
+        for (index, (button, _)) in self.buttons.iter().enumerate() {
+            button.draw(context);
+            if outlined == Some(index) {
+                let position = button.get_position();
+                let size = button.get_size();
+                let gap = FOCUS_WIDTH as f64 + 1.0;
+                context.draw_border(
+                    position.x - gap,
+                    position.y - gap,
+                    size.width + 2 * (FOCUS_WIDTH as u32 + 1),
+                    size.height + 2 * (FOCUS_WIDTH as u32 + 1),
+                    FOCUS_WIDTH,
+                    focus_color,
+                );
+            }
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
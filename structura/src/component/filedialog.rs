@@ -0,0 +1,1137 @@
+//!
+//! # Structura Component: FileDialog.
+//!
+//! A file chooser to open or save files, built from Structura components in a modal `Dialog`,
+//! without depending on the file dialogs of the platform.
+//!
+//! ```ignore
+//! FileDialog::open()
+//!     .filter(FileFilter::new("Images", &["png", "jpg"]))
+//!     .filter(FileFilter::all())
+//!     .on_result(|paths| paths.iter().for_each(|path| load(path)))
+//!     .show();
+//!
+//! // Or, from async code:
+//! let paths = FileDialog::save().file_name("untitled.txt").show().await;
+//! ```
+//!
+//! - The path bar above the list shows each folder down to the current one, and a click goes
+//!   to that folder. `Backspace` in the list goes up to the parent folder.
+//! - The list shows the folders, then the files that match the chosen filter, with their size
+//!   and the time they were modified. A click on a column header sorts by it, keeping the
+//!   folders first.
+//! - A double-click or `Enter` on a folder opens it, and on a file chooses it. A folder typed
+//!   in the name field opens too.
+//! - With `multiple(true)`, several names can be typed in quotes, e.g. `"a.txt" "b.txt"`.
+//! - Names starting with `.` are hidden unless "Show hidden files" is checked.
+//! - "New Folder" creates a folder in the current one. Saving over an existing file asks for
+//!   confirmation first.
+//! - The result is the chosen paths, or an empty `Vec` when the dialog is cancelled.
+//!
+
+use crate::component::button::Button;
+use crate::component::checkbox::Checkbox;
+use crate::component::combobox::ComboBox;
+use crate::component::dialog::{
+    ButtonRole, Dialog, DialogFuture, DialogHandle, DialogResult, confirm, message_box, prompt,
+};
+use crate::component::label::Label;
+use crate::component::table::{SortOrder, Table, TableChange, TableModel, TableNotifier};
+use crate::component::textfield::TextField;
+use crate::component::{Component, ComponentHandle, ComponentState, metrics};
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use winit::event::{ElementState, KeyEvent, MouseScrollDelta, TouchPhase};
+use winit::keyboard::{Key, NamedKey};
+
+/// Space between the rows and controls of the browser.
+const SPACING: f64 = 8.0;
+
+/// Space left and right of each folder in the path bar.
+const CRUMB_PADDING: f64 = 6.0;
+
+/// Drawn between the folders of the path bar.
+const CRUMB_SEPARATOR: &str = ">";
+
+/// Drawn in place of the folders that do not fit in the path bar.
+const CRUMB_ELLIPSIS: &str = "...";
+
+const DEFAULT_WIDTH: u32 = 720;
+const DEFAULT_HEIGHT: u32 = 440;
+const FILTER_WIDTH: u32 = 240;
+
+const NAME_COLUMN: usize = 0;
+const SIZE_COLUMN: usize = 1;
+const MODIFIED_COLUMN: usize = 2;
+
+/// The result of choosing the Open or Save button, the first of the dialog.
+const ACCEPTED: DialogResult = DialogResult {
+    button: Some(0),
+    role: ButtonRole::Accept,
+};
+
+///
+/// Whether a `FileDialog` chooses files to open, or where to save one.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FileDialogMode {
+    Open,
+    Save,
+}
+
+///
+/// A named set of file extensions that a `FileDialog` can show, e.g. "Images".
+///
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FileFilter {
+    name: String,
+
+    /// Lowercase, without a leading `.`. Empty to match every file.
+    extensions: Vec<String>,
+}
+
+impl FileFilter {
+    ///
+    /// Constructor. The `extensions` may be written as `"png"`, `".png"` or `"*.png"`.
+    ///
+    pub fn new(name: &str, extensions: &[&str]) -> Self {
+        let extensions = extensions
+            .iter()
+            .map(|extension| extension.trim_start_matches('*').trim_start_matches('.'))
+            .filter(|extension| !extension.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        Self {
+            name: name.to_string(),
+            extensions,
+        }
+    }
+
+    ///
+    /// A filter matching every file.
+    ///
+    pub fn all() -> Self {
+        Self::new("All files", &[])
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    ///
+    /// Returns `true` if the extension of `path` is one of the filter, ignoring case.
+    ///
+    pub fn matches(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+        path.extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .is_some_and(|extension| self.extensions.contains(&extension))
+    }
+
+    ///
+    /// Returns the text shown in the filter list, e.g. "Images (*.png, *.jpg)".
+    ///
+    fn label(&self) -> String {
+        if self.extensions.is_empty() {
+            return self.name.clone();
+        }
+        let patterns: Vec<String> = self
+            .extensions
+            .iter()
+            .map(|extension| format!("*.{extension}"))
+            .collect();
+        format!("{} ({})", self.name, patterns.join(", "))
+    }
+}
+
+///
+/// A dialog to choose files to open, or where to save one.
+///
+pub struct FileDialog {
+    mode: FileDialogMode,
+    title: Option<String>,
+    directory: Option<PathBuf>,
+    file_name: String,
+    filters: Vec<FileFilter>,
+    show_hidden: bool,
+    multiple: bool,
+    on_result: Option<Box<dyn FnOnce(Vec<PathBuf>)>>,
+}
+
+impl FileDialog {
+    ///
+    /// Constructor, for a dialog that chooses an existing file to open.
+    ///
+    pub fn open() -> Self {
+        Self::new(FileDialogMode::Open)
+    }
+
+    ///
+    /// Constructor, for a dialog that chooses where to save a file.
+    ///
+    pub fn save() -> Self {
+        Self::new(FileDialogMode::Save)
+    }
+
+    fn new(mode: FileDialogMode) -> Self {
+        Self {
+            mode,
+            title: None,
+            directory: None,
+            file_name: String::new(),
+            filters: vec![],
+            show_hidden: false,
+            multiple: false,
+            on_result: None,
+        }
+    }
+
+    ///
+    /// The title of the dialog. "Open" or "Save As" by default.
+    ///
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    ///
+    /// The folder shown first. The current directory by default. When it cannot be read, the
+    /// nearest folder above it that can is shown instead.
+    ///
+    pub fn directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    ///
+    /// The name initially in the name field, e.g. to suggest a name to save as.
+    ///
+    pub fn file_name(mut self, file_name: &str) -> Self {
+        self.file_name = file_name.to_string();
+        self
+    }
+
+    ///
+    /// Add a filter to choose from. The first filter is chosen initially, and without any
+    /// filter, every file is shown.
+    ///
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    ///
+    /// Show the files and folders whose names start with `.` initially.
+    ///
+    pub fn show_hidden(mut self, show_hidden: bool) -> Self {
+        self.show_hidden = show_hidden;
+        self
+    }
+
+    ///
+    /// Let the user choose several files to open.
+    ///
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    ///
+    /// Add event handler, called with the chosen paths when the dialog closes. The paths are
+    /// empty when the dialog was cancelled.
+    ///
+    pub fn on_result<F: FnOnce(Vec<PathBuf>) + 'static>(mut self, f: F) -> Self {
+        self.on_result = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Open the dialog above all other layers. Returns a future for the chosen paths.
+    ///
+    pub fn show(self) -> DialogFuture<Vec<PathBuf>> {
+        let (title, accept) = match self.mode {
+            FileDialogMode::Open => ("Open", "Open"),
+            FileDialogMode::Save => ("Save As", "Save"),
+        };
+        let mut dialog = Dialog::new(self.title.as_deref().unwrap_or(title))
+            .button(accept, ButtonRole::Accept)
+            .button("Cancel", ButtonRole::Reject);
+        let selection = Rc::new(RefCell::new(Vec::new()));
+        let handle = dialog.handle();
+        let browser = Rc::new_cyclic(|this| {
+            RefCell::new(FileBrowser::new(
+                &self,
+                selection.clone(),
+                handle,
+                this.clone(),
+            ))
+        });
+        for focus_id in browser.borrow().focus_order() {
+            dialog = dialog.focus(focus_id);
+        }
+        let validate = {
+            let browser = browser.clone();
+            move |result: DialogResult| !result.is_accepted() || browser.borrow_mut().accept()
+        };
+        let mut dialog = dialog
+            .content(Box::new(ComponentHandle::new(browser)))
+            .validate(validate)
+            .map(move |result| {
+                if result.is_accepted() {
+                    selection.take()
+                } else {
+                    Vec::new()
+                }
+            });
+        if let Some(on_result) = self.on_result {
+            dialog = dialog.on_result(on_result);
+        }
+        dialog.show()
+    }
+}
+
+///
+/// A file or folder in the list.
+///
+struct FileEntry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+///
+/// The entries of the current folder, shown by the `Table` of the browser.
+///
+struct FileModel {
+    entries: Vec<FileEntry>,
+    notifier: Option<TableNotifier>,
+}
+
+impl FileModel {
+    fn set_entries(&mut self, entries: Vec<FileEntry>) {
+        self.entries = entries;
+        if let Some(notifier) = self.notifier.as_ref() {
+            notifier.notify(TableChange::Reset);
+        }
+    }
+}
+
+impl TableModel for FileModel {
+    fn row_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn column_count(&self) -> usize {
+        3
+    }
+
+    fn column_title(&self, column: usize) -> String {
+        match column {
+            NAME_COLUMN => "Name",
+            SIZE_COLUMN => "Size",
+            _ => "Modified (UTC)",
+        }
+        .to_string()
+    }
+
+    fn cell_text(&self, row: usize, column: usize) -> String {
+        let entry = &self.entries[row];
+        match column {
+            NAME_COLUMN if entry.is_dir => format!("{}{}", entry.name, std::path::MAIN_SEPARATOR),
+            NAME_COLUMN => entry.name.clone(),
+            SIZE_COLUMN if entry.is_dir => String::new(),
+            SIZE_COLUMN => format_size(entry.size),
+            _ => entry.modified.map(format_time).unwrap_or_default(),
+        }
+    }
+
+    ///
+    /// Names compare ignoring case, and sizes and times by value.
+    ///
+    fn compare(&self, column: usize, a: usize, b: usize) -> Ordering {
+        let (a, b) = (&self.entries[a], &self.entries[b]);
+        match column {
+            SIZE_COLUMN => a.size.cmp(&b.size),
+            MODIFIED_COLUMN => a.modified.cmp(&b.modified),
+            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        }
+    }
+
+    ///
+    /// Folders sort before files, in either sort order.
+    ///
+    fn compare_group(&self, a: usize, b: usize) -> Ordering {
+        self.entries[b].is_dir.cmp(&self.entries[a].is_dir)
+    }
+
+    fn set_notifier(&mut self, notifier: TableNotifier) {
+        self.notifier = Some(notifier);
+    }
+}
+
+///
+/// Returns the size as text, e.g. "512 bytes" or "1.5 MB".
+///
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["bytes", "KB", "MB", "GB", "TB"];
+    if size < 1024 {
+        return format!("{size} {}", UNITS[0]);
+    }
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+///
+/// Returns the time as text in UTC, e.g. "2024-05-17 09:30".
+///
+fn format_time(time: SystemTime) -> String {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(error) => -(error.duration().as_secs() as i64),
+    };
+    let (days, seconds) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+
+    //
+    // Convert days since 1970-01-01 to a civil date, in eras of 400 years.
+    //
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+///
+/// Returns the entries of `directory`: its folders, and the files matching `filter`.
+///
+fn read_entries(
+    directory: &Path,
+    show_hidden: bool,
+    filter: Option<&FileFilter>,
+) -> io::Result<Vec<FileEntry>> {
+    let mut entries = vec![];
+    for entry in fs::read_dir(directory)? {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !show_hidden && name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+
+        //
+        // Follow links to show the folder or file they point to.
+        //
+        let Ok(metadata) = fs::metadata(&path).or_else(|_| entry.metadata()) else {
+            continue;
+        };
+        let is_dir = metadata.is_dir();
+        if !is_dir && filter.is_some_and(|filter| !filter.matches(&path)) {
+            continue;
+        }
+        entries.push(FileEntry {
+            name,
+            path,
+            is_dir,
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+        });
+    }
+    Ok(entries)
+}
+
+///
+/// Split the text of the name field into names: quoted names, or else the whole text.
+///
+fn parse_names(text: &str, multiple: bool) -> Vec<String> {
+    let text = text.trim();
+    if !multiple || !text.starts_with('"') {
+        return if text.is_empty() {
+            vec![]
+        } else {
+            vec![text.to_string()]
+        };
+    }
+    text.split('"')
+        .skip(1)
+        .step_by(2)
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+///
+/// A folder in the path bar.
+///
+struct Crumb {
+    path: PathBuf,
+    text: String,
+
+    /// `None` when it does not fit in the path bar.
+    bounds: Option<Rectangle>,
+}
+
+///
+/// The content of a `FileDialog`: the path bar, the list of files, the name field and the
+/// filters.
+///
+struct FileBrowser {
+    position: Point,
+    size: Size,
+    mode: FileDialogMode,
+    multiple: bool,
+    directory: PathBuf,
+    show_hidden: bool,
+    filters: Vec<FileFilter>,
+    crumbs: Vec<Crumb>,
+    hovered_crumb: Option<usize>,
+    table: Table<FileModel>,
+    name_label: Label,
+    name_field: TextField,
+    filter_box: ComboBox<FileFilter>,
+    filter_index: Option<usize>,
+    hidden_box: Checkbox,
+    new_folder: Button,
+
+    /// Set by the handlers of the `Table` and the "New Folder" button.
+    selected_row: Rc<Cell<Option<usize>>>,
+    activated_row: Rc<Cell<Option<usize>>>,
+    new_folder_clicked: Rc<Cell<bool>>,
+
+    /// The chosen paths, taken as the result of the dialog.
+    selection: Rc<RefCell<Vec<PathBuf>>>,
+    handle: DialogHandle,
+
+    /// The browser itself, for the handlers of the dialogs it opens.
+    this: Weak<RefCell<FileBrowser>>,
+}
+
+impl FileBrowser {
+    fn new(
+        dialog: &FileDialog,
+        selection: Rc<RefCell<Vec<PathBuf>>>,
+        handle: DialogHandle,
+        this: Weak<RefCell<FileBrowser>>,
+    ) -> Self {
+        let selected_row = Rc::new(Cell::new(None));
+        let activated_row = Rc::new(Cell::new(None));
+        let new_folder_clicked = Rc::new(Cell::new(false));
+        let model = FileModel {
+            entries: vec![],
+            notifier: None,
+        };
+        let mut table = Table::new(model)
+            .column_width(NAME_COLUMN, 340.0)
+            .column_width(SIZE_COLUMN, 100.0)
+            .column_width(MODIFIED_COLUMN, 180.0)
+            .on_selection_changed({
+                let selected_row = selected_row.clone();
+                move |row| selected_row.set(Some(row))
+            })
+            .on_row_activated({
+                let activated_row = activated_row.clone();
+                move |row| activated_row.set(Some(row))
+            });
+        table.sort_by(Some((NAME_COLUMN, SortOrder::Ascending)));
+
+        let mut name_field = TextField::new();
+        name_field.set_text(&dialog.file_name);
+        name_field.select_all();
+
+        let filters = if dialog.filters.is_empty() {
+            vec![FileFilter::all()]
+        } else {
+            dialog.filters.clone()
+        };
+        let filter_box = ComboBox::new(filters.clone(), FileFilter::label).selected(0);
+        let mut new_folder = Button::default()
+            .set_text("New Folder".to_string())
+            .on_click({
+                let clicked = new_folder_clicked.clone();
+                move || clicked.set(true)
+            });
+        let size = new_folder.preferred_size();
+        new_folder.set_size(size.width as usize, size.height as usize);
+
+        let mut browser = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: DEFAULT_WIDTH,
+                height: DEFAULT_HEIGHT,
+            },
+            mode: dialog.mode,
+            multiple: dialog.multiple && dialog.mode == FileDialogMode::Open,
+            directory: PathBuf::new(),
+            show_hidden: dialog.show_hidden,
+            filters,
+            crumbs: vec![],
+            hovered_crumb: None,
+            table,
+            name_label: Label::new("Name:"),
+            name_field,
+            filter_box,
+            filter_index: Some(0),
+            hidden_box: Checkbox::new("Show hidden files").checked(dialog.show_hidden),
+            new_folder,
+            selected_row,
+            activated_row,
+            new_folder_clicked,
+            selection,
+            handle,
+            this,
+        };
+        let start = dialog
+            .directory
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("/"));
+        let start = fs::canonicalize(&start).unwrap_or(start);
+
+        //
+        // The dialog is not open yet, so an error shown now would be hidden below it. A start
+        // folder that cannot be read falls back to the nearest folder above it that can.
+        //
+        let opened = start
+            .ancestors()
+            .any(|directory| browser.open_directory(directory).is_ok());
+        if !opened {
+            browser.directory = start;
+            browser.update_crumbs();
+        }
+        browser.layout();
+        browser
+    }
+
+    ///
+    /// Returns the controls in `Tab` order, starting with the list when opening and the name
+    /// when saving.
+    ///
+    fn focus_order(&self) -> Vec<FocusId> {
+        let mut order = vec![
+            self.table.focus_id(),
+            self.name_field.focus_id(),
+            self.filter_box.focus_id(),
+            self.hidden_box.focus_id(),
+            self.new_folder.focus_id(),
+        ];
+        if self.mode == FileDialogMode::Save {
+            order.rotate_left(1);
+        }
+        order
+    }
+
+    fn filter(&self) -> Option<&FileFilter> {
+        self.filter_index.and_then(|index| self.filters.get(index))
+    }
+
+    fn row_height(&self) -> f64 {
+        self.name_field.get_size().height as f64
+    }
+
+    ///
+    /// Show the entries of `directory`. Returns `false`, and tells the user, when it cannot
+    /// be read.
+    ///
+    fn navigate(&mut self, directory: &Path) -> bool {
+        match self.open_directory(directory) {
+            Ok(()) => true,
+            Err(error) => {
+                let message = format!("{} cannot be opened.\n{error}", directory.display());
+                message_box("Error", &message).show();
+                false
+            }
+        }
+    }
+
+    ///
+    /// Show the entries of `directory`, if it can be read.
+    ///
+    fn open_directory(&mut self, directory: &Path) -> io::Result<()> {
+        let entries = read_entries(directory, self.show_hidden, self.filter())?;
+        self.directory = directory.to_path_buf();
+        self.table.model_mut().set_entries(entries);
+        self.table.set_selected_row(None);
+        self.update_crumbs();
+        Ok(())
+    }
+
+    ///
+    /// Read the current folder again, e.g. after changing the filter.
+    ///
+    fn reload(&mut self) {
+        let directory = self.directory.clone();
+        self.navigate(&directory);
+    }
+
+    fn go_up(&mut self) {
+        if let Some(parent) = self.directory.parent().map(Path::to_path_buf) {
+            self.navigate(&parent);
+        }
+    }
+
+    fn selected_entry(&self) -> Option<&FileEntry> {
+        let row = self.table.selected_row()?;
+        self.table.model().entries.get(row)
+    }
+
+    ///
+    /// Open a folder, or choose a file, in the list.
+    ///
+    fn activate(&mut self, row: usize) {
+        let Some(entry) = self.table.model().entries.get(row) else {
+            return;
+        };
+        if entry.is_dir {
+            let path = entry.path.clone();
+            self.navigate(&path);
+        } else {
+            self.name_field.set_text(&entry.name.clone());
+            if self.accept() {
+                self.handle.close(ACCEPTED);
+            }
+        }
+    }
+
+    ///
+    /// Choose the paths in the name field, or open the folder it names. Returns `false` when
+    /// the dialog should stay open.
+    ///
+    fn accept(&mut self) -> bool {
+        if focus::is_focused(self.table.focus_id())
+            && let Some(entry) = self.selected_entry()
+            && entry.is_dir
+        {
+            let path = entry.path.clone();
+            self.navigate(&path);
+            return false;
+        }
+        let names = parse_names(self.name_field.text(), self.multiple);
+        let mut paths: Vec<PathBuf> = names.iter().map(|name| self.directory.join(name)).collect();
+        if paths.is_empty() {
+            return false;
+        }
+        if paths.len() == 1 && paths[0].is_dir() {
+            let path = paths.remove(0);
+            if self.navigate(&path) {
+                self.name_field.set_text("");
+            }
+            return false;
+        }
+        match self.mode {
+            FileDialogMode::Open => {
+                if let Some(missing) = paths.iter().find(|path| !path.is_file()) {
+                    let message = format!("{} was not found.", missing.display());
+                    message_box("Open", &message).show();
+                    return false;
+                }
+            }
+            FileDialogMode::Save => {
+                let mut path = paths.remove(0);
+                if path.extension().is_none()
+                    && let Some(extension) = self.filter().and_then(|f| f.extensions.first())
+                {
+                    path.set_extension(extension);
+                }
+                if let Some(parent) = path.parent()
+                    && !parent.is_dir()
+                {
+                    let message = format!("The folder {} does not exist.", parent.display());
+                    message_box("Save As", &message).show();
+                    return false;
+                }
+                if path.exists() {
+                    self.confirm_overwrite(path);
+                    return false;
+                }
+                paths = vec![path];
+            }
+        }
+        *self.selection.borrow_mut() = paths;
+        true
+    }
+
+    ///
+    /// Ask whether to replace the file at `path`, and close the dialog if so.
+    ///
+    fn confirm_overwrite(&self, path: PathBuf) {
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+        let message = format!("{name} already exists.\nDo you want to replace it?");
+        let selection = self.selection.clone();
+        let handle = self.handle.clone();
+        confirm("Confirm Save As", &message)
+            .on_result(move |replace| {
+                if replace {
+                    *selection.borrow_mut() = vec![path];
+                    handle.close(ACCEPTED);
+                }
+            })
+            .show();
+    }
+
+    ///
+    /// Ask for a name, and create a folder with it in the current folder.
+    ///
+    fn ask_new_folder(&self) {
+        let this = self.this.clone();
+        prompt("New Folder", "Folder name:", "New Folder")
+            .on_result(move |name| {
+                let name = name.map(|name| name.trim().to_string());
+                if let (Some(name), Some(browser)) = (name, this.upgrade())
+                    && !name.is_empty()
+                {
+                    browser.borrow_mut().create_folder(&name);
+                }
+            })
+            .show();
+    }
+
+    fn create_folder(&mut self, name: &str) {
+        let path = self.directory.join(name);
+        if let Err(error) = fs::create_dir(&path) {
+            let message = format!("{name} cannot be created.\n{error}");
+            message_box("New Folder", &message).show();
+            return;
+        }
+        self.reload();
+        let row = self
+            .table
+            .model()
+            .entries
+            .iter()
+            .position(|entry| entry.path == path);
+        self.table.set_selected_row(row);
+    }
+
+    ///
+    /// Apply the changes made by the user to the controls, after passing them an event.
+    ///
+    fn update(&mut self) {
+        if let Some(row) = self.selected_row.take()
+            && let Some(entry) = self.table.model().entries.get(row)
+            && !entry.is_dir
+        {
+            let name = entry.name.clone();
+            self.name_field.set_text(&name);
+        }
+        if let Some(row) = self.activated_row.take() {
+            self.activate(row);
+        }
+        if self.hidden_box.is_checked() != self.show_hidden {
+            self.show_hidden = self.hidden_box.is_checked();
+            self.reload();
+        }
+        if self.filter_box.selected_index() != self.filter_index {
+            self.filter_index = self.filter_box.selected_index();
+            self.apply_filter_extension();
+            self.reload();
+        }
+        if self.new_folder_clicked.take() {
+            self.ask_new_folder();
+        }
+    }
+
+    ///
+    /// When saving, change the extension of the name to the one of the chosen filter.
+    ///
+    fn apply_filter_extension(&mut self) {
+        if self.mode != FileDialogMode::Save {
+            return;
+        }
+        let Some(extension) = self.filter().and_then(|f| f.extensions.first()).cloned() else {
+            return;
+        };
+        let mut name = PathBuf::from(self.name_field.text());
+        if name.extension().is_some() {
+            name.set_extension(extension);
+            self.name_field.set_text(&name.to_string_lossy());
+        }
+    }
+
+    ///
+    /// List the folders from the root down to the current one, and place the ones that fit
+    /// in the path bar, dropping the top ones first.
+    ///
+    fn update_crumbs(&mut self) {
+        let mut paths: Vec<&Path> = self.directory.ancestors().collect();
+        paths.reverse();
+        self.crumbs = paths
+            .into_iter()
+            .map(|path| Crumb {
+                path: path.to_path_buf(),
+                text: path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                ),
+                bounds: None,
+            })
+            .collect();
+        self.hovered_crumb = None;
+        self.layout_crumbs();
+    }
+
+    fn layout_crumbs(&mut self) {
+        let height = self.row_height();
+        let available = self.crumb_bar_width();
+        let separator = metrics::text_width(CRUMB_SEPARATOR) as f64 + 2.0 * CRUMB_PADDING;
+        let ellipsis = metrics::text_width(CRUMB_ELLIPSIS) as f64 + 2.0 * CRUMB_PADDING;
+        let widths: Vec<f64> = self
+            .crumbs
+            .iter()
+            .map(|crumb| (metrics::text_width(&crumb.text) as f64 + 2.0 * CRUMB_PADDING).ceil())
+            .collect();
+
+        //
+        // Keep the most folders from the current one up that fit.
+        //
+        let mut first = widths.len().saturating_sub(1);
+        let mut total = widths.last().copied().unwrap_or(0.0);
+        while first > 0 {
+            let more = total + separator + widths[first - 1];
+            let needed = if first > 1 {
+                more + separator + ellipsis
+            } else {
+                more
+            };
+            if needed > available {
+                break;
+            }
+            total = more;
+            first -= 1;
+        }
+        let mut x = self.position.x;
+        if first > 0 {
+            x += ellipsis + separator;
+        }
+        let y = self.position.y;
+        for (index, crumb) in self.crumbs.iter_mut().enumerate() {
+            crumb.bounds = (index >= first).then(|| {
+                let bounds = Rectangle::new(x, y, widths[index] as u32, height as u32);
+                x += widths[index] + separator;
+                bounds
+            });
+        }
+    }
+
+    ///
+    /// Returns the width of the path bar, left of the hidden-files toggle and "New Folder".
+    ///
+    fn crumb_bar_width(&self) -> f64 {
+        let controls = self.hidden_box.get_size().width as f64
+            + self.new_folder.get_size().width as f64
+            + 2.0 * SPACING;
+        (self.size.width as f64 - controls).max(0.0)
+    }
+
+    ///
+    /// Place the path bar and its controls at the top, the name field and filters at the
+    /// bottom, and the list in between.
+    ///
+    fn layout(&mut self) {
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width as f64, self.size.height as f64);
+        let row_height = self.row_height();
+
+        let button = self.new_folder.get_size();
+        let button_x = x + width - button.width as f64;
+        let button_y = y + (row_height - button.height as f64) / 2.0;
+        self.new_folder.set_position(button_x, button_y);
+        let toggle = self.hidden_box.get_size();
+        let toggle_x = button_x - SPACING - toggle.width as f64;
+        let toggle_y = y + (row_height - toggle.height as f64) / 2.0;
+        self.hidden_box.set_position(toggle_x, toggle_y);
+        self.layout_crumbs();
+
+        let bottom = y + height - row_height;
+        let table_y = y + row_height + SPACING;
+        let table_height = (bottom - SPACING - table_y).max(0.0);
+        self.table.set_position(x, table_y);
+        self.table.set_size(width as usize, table_height as usize);
+
+        let label = self.name_label.get_size();
+        let label_y = bottom + (row_height - label.height as f64) / 2.0;
+        self.name_label.set_position(x, label_y);
+        let filter_x = x + width - FILTER_WIDTH as f64;
+        self.filter_box.set_position(filter_x, bottom);
+        self.filter_box
+            .set_size(FILTER_WIDTH as usize, row_height as usize);
+        let field_x = x + label.width as f64 + SPACING;
+        let field_width = (filter_x - SPACING - field_x).max(0.0);
+        self.name_field.set_position(field_x, bottom);
+        self.name_field
+            .set_size(field_width as usize, row_height as usize);
+    }
+
+    fn draw_crumbs(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let hovered = context.theme.style_for(&ComponentState::Hovered);
+        let height = self.row_height();
+        let text_y = self.position.y + (height - context.line_height() as f64) / 2.0;
+        let color = style.fore_color.value;
+        let mut x = self.position.x;
+        if self
+            .crumbs
+            .first()
+            .is_some_and(|crumb| crumb.bounds.is_none())
+        {
+            context.draw_text(CRUMB_ELLIPSIS, x + CRUMB_PADDING, text_y, color);
+            x += context.text_width(CRUMB_ELLIPSIS) as f64 + 2.0 * CRUMB_PADDING;
+            context.draw_text(CRUMB_SEPARATOR, x + CRUMB_PADDING, text_y, color);
+        }
+        let visible = self
+            .crumbs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, crumb)| crumb.bounds.as_ref().map(|b| (index, crumb, b)));
+        for (index, crumb, bounds) in visible {
+            let mut color = style.fore_color.value;
+            if self.hovered_crumb == Some(index) {
+                let (bx, by) = (bounds.point.x, bounds.point.y);
+                let (width, height) = (bounds.size.width, bounds.size.height);
+                context.fill_rect(bx, by, width, height, hovered.back_color.value);
+                color = hovered.fore_color.value;
+            }
+            context.draw_text(&crumb.text, bounds.point.x + CRUMB_PADDING, text_y, color);
+            if index + 1 < self.crumbs.len() {
+                let separator_x = bounds.point.x + bounds.size.width as f64 + CRUMB_PADDING;
+                let color = style.fore_color.value;
+                context.draw_text(CRUMB_SEPARATOR, separator_x, text_y, color);
+            }
+        }
+    }
+
+    fn crumb_at(&self, point: Point) -> Option<usize> {
+        self.crumbs.iter().position(|crumb| {
+            crumb
+                .bounds
+                .as_ref()
+                .is_some_and(|bounds| bounds.contains(point.x, point.y))
+        })
+    }
+}
+
+impl Component for FileBrowser {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        self.hovered_crumb = self.crumb_at(input.position);
+        self.table.handle_mouse_event(input);
+        self.name_label.handle_mouse_event(input);
+        self.name_field.handle_mouse_event(input);
+        self.filter_box.handle_mouse_event(input);
+        self.hidden_box.handle_mouse_event(input);
+        Component::handle_mouse_event(&mut self.new_folder, input);
+        self.update();
+        if input.pressed
+            && let Some(index) = self.hovered_crumb
+        {
+            let path = self.crumbs[index].path.clone();
+            if path != self.directory {
+                self.navigate(&path);
+            }
+        }
+    }
+
+    fn handle_mouse_wheel_event(&mut self, delta: &MouseScrollDelta, phase: &TouchPhase) {
+        self.table.handle_mouse_wheel_event(delta, phase);
+        self.filter_box.handle_mouse_wheel_event(delta, phase);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &KeyEvent) {
+        let table_focused = focus::is_focused(self.table.focus_id());
+        if table_focused
+            && event.state == ElementState::Pressed
+            && event.logical_key == Key::Named(NamedKey::Backspace)
+        {
+            self.go_up();
+            return;
+        }
+        self.table.handle_keyboard_event(event);
+        self.name_field.handle_keyboard_event(event);
+        self.filter_box.handle_keyboard_event(event);
+        self.hidden_box.handle_keyboard_event(event);
+        self.new_folder.handle_keyboard_event(event);
+        self.update();
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.table.animate(now);
+        self.name_field.animate(now);
+        self.filter_box.animate(now);
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let bar = Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.crumb_bar_width() as u32,
+            self.row_height() as u32,
+        );
+        context.with_clip(&bar, |context| self.draw_crumbs(context));
+        self.hidden_box.draw(context);
+        self.new_folder.draw(context);
+        self.table.draw(context);
+        self.name_label.draw(context);
+        self.name_field.draw(context);
+        self.filter_box.draw(context);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
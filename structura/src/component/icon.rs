@@ -0,0 +1,166 @@
+//!
+//! # Structura Component: Icon.
+//!
+//! A small picture shown next to text, e.g. by the nodes of a `TreeView`.
+//!
+//! An `Icon` is either an image of ARGB pixels, drawn with its alpha, or drawn by a closure in
+//! the bounds of the icon. A few stock icons are drawn with the drawing primitives:
+//!
+//! ```ignore
+//! let folder = Icon::folder();
+//! let logo = Icon::from_pixels(16, 16, pixels);
+//! let dot = Icon::from_fn(8, 8, |context, bounds, color| {
+//!     context.fill_circle(bounds.point.x + 4.0, bounds.point.y + 4.0, 4.0, color);
+//! });
+//! ```
+//!
+//! Icons are cheap to clone: clones share the pixels or closure.
+//!
+
+use crate::geometry::Rectangle;
+use crate::view::BufferContext;
+use std::fmt;
+use std::rc::Rc;
+
+/// Size of the stock icons.
+const STOCK_SIZE: u32 = 16;
+
+/// Color of the stock folder icons.
+const FOLDER_COLOR: u32 = 0xFFE8B84A;
+
+/// Draws an icon within its bounds, with the foreground color of the component.
+type DrawIcon = Rc<dyn Fn(&mut BufferContext, &Rectangle, u32)>;
+
+#[derive(Clone)]
+enum Picture {
+    /// ARGB pixels, row by row.
+    Pixels(Rc<[u32]>),
+    Drawn(DrawIcon),
+}
+
+///
+/// A small image, or drawing, shown by a `Component`.
+///
+#[derive(Clone)]
+pub struct Icon {
+    width: u32,
+    height: u32,
+    picture: Picture,
+}
+
+impl fmt::Debug for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Icon")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Icon {
+    ///
+    /// Constructor, for an image of `width` by `height` ARGB pixels, row by row. Missing pixels
+    /// are transparent.
+    ///
+    pub fn from_pixels(width: u32, height: u32, mut pixels: Vec<u32>) -> Self {
+        pixels.resize((width * height) as usize, 0);
+        Self {
+            width,
+            height,
+            picture: Picture::Pixels(pixels.into()),
+        }
+    }
+
+    ///
+    /// Constructor, for an icon drawn by `draw` within its bounds, with the foreground color of
+    /// the `Component` showing it.
+    ///
+    pub fn from_fn<F>(width: u32, height: u32, draw: F) -> Self
+    where
+        F: Fn(&mut BufferContext, &Rectangle, u32) + 'static,
+    {
+        Self {
+            width,
+            height,
+            picture: Picture::Drawn(Rc::new(draw)),
+        }
+    }
+
+    ///
+    /// Stock icon of a closed folder.
+    ///
+    pub fn folder() -> Self {
+        Self::from_fn(STOCK_SIZE, STOCK_SIZE, |context, bounds, _color| {
+            let (x, y) = (bounds.point.x, bounds.point.y);
+            context.fill_rect(x + 1.0, y + 2.0, 6, 2, FOLDER_COLOR);
+            context.fill_rect(x + 1.0, y + 4.0, 14, 10, FOLDER_COLOR);
+        })
+    }
+
+    ///
+    /// Stock icon of an open folder.
+    ///
+    pub fn folder_open() -> Self {
+        Self::from_fn(STOCK_SIZE, STOCK_SIZE, |context, bounds, _color| {
+            let (x, y) = (bounds.point.x, bounds.point.y);
+            context.fill_rect(x + 1.0, y + 2.0, 6, 2, FOLDER_COLOR);
+            context.draw_border(x + 1.0, y + 4.0, 14, 10, 1, FOLDER_COLOR);
+            //
+            // The front flap, slanting out of the folder.
+            //
+            for row in 0..6 {
+                let (slant, y) = (row as f64 / 2.0, y + 8.0 + row as f64);
+                context.draw_line(x + 4.0 - slant, y, x + 16.0 - slant, y, FOLDER_COLOR);
+            }
+        })
+    }
+
+    ///
+    /// Stock icon of a document, with a folded corner.
+    ///
+    pub fn file() -> Self {
+        Self::from_fn(STOCK_SIZE, STOCK_SIZE, |context, bounds, color| {
+            let (x, y) = (bounds.point.x, bounds.point.y);
+            let (left, top, right, bottom) = (x + 3.0, y + 1.0, x + 13.0, y + 15.0);
+            context.draw_line(left, top, right - 4.0, top, color);
+            context.draw_line(right - 4.0, top, right, top + 4.0, color);
+            context.draw_line(right - 4.0, top, right - 4.0, top + 4.0, color);
+            context.draw_line(right - 4.0, top + 4.0, right, top + 4.0, color);
+            context.draw_line(right, top + 4.0, right, bottom, color);
+            context.draw_line(left, bottom, right, bottom, color);
+            context.draw_line(left, top, left, bottom, color);
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    ///
+    /// Draw the icon with its top-left corner at `(x, y)`. `color` is the foreground color of
+    /// the `Component`, used by drawn icons.
+    ///
+    pub fn draw(&self, context: &mut BufferContext, x: f64, y: f64, color: u32) {
+        let bounds = Rectangle::new(x, y, self.width, self.height);
+        match &self.picture {
+            Picture::Pixels(pixels) => {
+                let (x, y) = (x.round() as i64, y.round() as i64);
+                for (i, &pixel) in pixels.iter().enumerate() {
+                    let alpha = (pixel >> 24) as f32 / 255.0;
+                    if alpha > 0.0 {
+                        let (column, row) = (i as u32 % self.width, i as u32 / self.width);
+                        let (px, py) = (x + column as i64, y + row as i64);
+                        context.blend_pixel(px, py, pixel, alpha);
+                    }
+                }
+            }
+            Picture::Drawn(draw) => context.with_clip(&bounds, |context| {
+                draw(context, &bounds, color);
+            }),
+        }
+    }
+}
@@ -1,5 +1,586 @@
 //!
+//! # Structura Component: Label.
 //!
+//! Non-editable text, sized from the measured text.
 //!
+//! - Horizontal and vertical alignment within the `Label` bounds.
+//! - Optional word wrapping at the `Label` width, and a maximum number of lines. Text that
+//!   does not fit ends with an ellipsis (`…`).
+//! - Optionally selectable with the mouse, with `Ctrl+C` to copy and `Ctrl+A` to select all.
+//! - A mnemonic, marked with `&` in the text (`&&` for a literal `&`). `Alt` + the mnemonic
+//!   moves keyboard focus to the `Component` set with `Label::label_for()`.
+//!
+//! ```ignore
+//! let notes = TextArea::new();
+//! let label = Label::new("&Notes").label_for(notes.focus_id());
+//! ```
+//!
+
+use crate::clipboard;
+use crate::component::Component;
+use crate::component::metrics;
+use crate::event::focus::{self, FocusId};
+use crate::event::{self, MouseInput};
+use crate::geometry::{HorizontalAlignment, Point, Rectangle, Size, VerticalAlignment};
+use crate::view::BufferContext;
+use winit::event::ElementState;
+use winit::keyboard::Key;
+
+/// Default text color.
+const TEXT_COLOR: u32 = 0xFFFFFFFF;
+
+/// Color of the highlight behind selected text.
+const SELECTION_COLOR: u32 = 0xFF3399FF;
+
+/// Opacity of the selection highlight, from `0.0` to `1.0`.
+const SELECTION_ALPHA: f32 = 0.5;
+
+/// Space between the bounds and the text, on each side.
+const PADDING: f64 = 4.0;
+
+/// Drawn at the end of text that does not fit.
+const ELLIPSIS: &str = "…";
+
+///
+/// A line of laid out text.
+///
+struct Line {
+    /// Index of the first character of the line, in characters from the start of the text.
+    start: usize,
+
+    /// The visible characters of the line.
+    text: String,
+
+    /// True if the line was cut short, and ends with an ellipsis.
+    ellipsis: bool,
+}
+
+impl Line {
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    ///
+    /// Returns the width of the line as it is drawn, ignoring trailing whitespace left at a
+    /// wrap point.
+    ///
+    fn width(&self) -> f64 {
+        let mut width = metrics::text_width(self.text.trim_end()) as f64;
+        if self.ellipsis {
+            width += metrics::text_width(ELLIPSIS) as f64;
+        }
+        width
+    }
+
+    ///
+    /// Returns the x offset of the character at `index` (relative to the line), from the
+    /// start of the line.
+    ///
+    fn offset_of(&self, index: usize) -> f64 {
+        let prefix: String = self.text.chars().take(index).collect();
+        metrics::text_width(&prefix) as f64
+    }
+}
+
+///
+/// A non-editable text `Component`.
+///
+pub struct Label {
+    position: Point,
+    size: Size,
+    text: String,
+    mnemonic: Option<usize>,
+    horizontal_alignment: HorizontalAlignment,
+    vertical_alignment: VerticalAlignment,
+    wrap: bool,
+    max_lines: Option<usize>,
+    selectable: bool,
+    selection: Option<(usize, usize)>,
+    selecting: bool,
+    focus_id: FocusId,
+    target: Option<FocusId>,
+    color: u32,
+}
+
+impl Default for Label {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl Label {
+    ///
+    /// Constructor. An `&` in `text` marks the next character as the mnemonic.
+    ///
+    pub fn new(text: &str) -> Self {
+        let (text, mnemonic) = parse_mnemonic(text);
+        let mut label = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            text,
+            mnemonic,
+            horizontal_alignment: HorizontalAlignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
+            wrap: false,
+            max_lines: None,
+            selectable: false,
+            selection: None,
+            selecting: false,
+            focus_id: FocusId::new(),
+            target: None,
+            color: TEXT_COLOR,
+        };
+        label.fit_to_text();
+        label
+    }
+
+    pub fn horizontal_alignment(mut self, alignment: HorizontalAlignment) -> Self {
+        self.horizontal_alignment = alignment;
+        self
+    }
+
+    pub fn vertical_alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.vertical_alignment = alignment;
+        self
+    }
+
+    ///
+    /// Wrap lines at word boundaries to fit the `Label` width. The height is fitted to the
+    /// wrapped text, and refitted whenever the width changes. Until a width is set, the
+    /// `Label` is as wide as its widest line.
+    ///
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self.fit_to_text();
+        self
+    }
+
+    ///
+    /// Show at most `max_lines` lines, ending the last one with an ellipsis if text is cut.
+    ///
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines.max(1));
+        self.fit_to_text();
+        self
+    }
+
+    ///
+    /// Allow the text to be selected with the mouse and copied.
+    ///
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        if !selectable {
+            self.selection = None;
+        }
+        self
+    }
+
+    pub fn color(mut self, color: u32) -> Self {
+        self.color = color;
+        self
+    }
+
+    ///
+    /// Move keyboard focus to `target` when `Alt` + the mnemonic is pressed.
+    ///
+    pub fn label_for(mut self, target: FocusId) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    ///
+    /// Returns the text, without the mnemonic marker.
+    ///
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    ///
+    /// Replace the text, and fit the size to it. An `&` marks the mnemonic, as in
+    /// `Label::new()`.
+    ///
+    pub fn set_text(&mut self, text: &str) {
+        let (text, mnemonic) = parse_mnemonic(text);
+        self.text = text;
+        self.mnemonic = mnemonic;
+        self.selection = None;
+        self.fit_to_text();
+    }
+
+    ///
+    /// Returns the mnemonic character, if any.
+    ///
+    pub fn mnemonic(&self) -> Option<char> {
+        self.text.chars().nth(self.mnemonic?)
+    }
+
+    ///
+    /// Returns the selected text, or an empty string.
+    ///
+    pub fn selected_text(&self) -> String {
+        match self.selection_range() {
+            Some((start, end)) => self.text.chars().skip(start).take(end - start).collect(),
+            None => String::new(),
+        }
+    }
+
+    ///
+    /// Returns the height needed to show the text at `width`.
+    ///
+    pub fn height_for_width(&self, width: u32) -> u32 {
+        let content_width = (width as f64 - PADDING * 2.0).max(0.0);
+        let lines = self.layout(content_width).len();
+        (lines as f64 * metrics::line_height() as f64 + PADDING * 2.0).ceil() as u32
+    }
+
+    ///
+    /// Size the `Label` to its text: the widest line, unless wrapping at a width already set,
+    /// and the height of the visible lines.
+    ///
+    fn fit_to_text(&mut self) {
+        if !self.wrap || self.size.width == 0 {
+            let widest = self
+                .text
+                .split('\n')
+                .map(|line| metrics::text_width(line) as f64)
+                .fold(0.0, f64::max);
+            self.size.width = (widest + PADDING * 2.0).ceil() as u32;
+        }
+        self.size.height = self.height_for_width(self.size.width);
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    fn content_width(&self) -> f64 {
+        (self.size.width as f64 - PADDING * 2.0).max(0.0)
+    }
+
+    ///
+    /// Split the text into lines: at newlines, at word boundaries when wrapping, and up to
+    /// `max_lines`. Lines that are cut short get an ellipsis.
+    ///
+    fn layout(&self, width: f64) -> Vec<Line> {
+        let mut lines = vec![];
+        let mut start = 0;
+        for paragraph in self.text.split('\n') {
+            let chars: Vec<char> = paragraph.chars().collect();
+            if self.wrap {
+                wrap_paragraph(&chars, start, width, &mut lines);
+            } else {
+                lines.push(Line {
+                    start,
+                    text: paragraph.to_string(),
+                    ellipsis: false,
+                });
+            }
+            start += chars.len() + 1;
+        }
+
+        if let Some(max_lines) = self.max_lines
+            && lines.len() > max_lines
+        {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                last.ellipsis = true;
+            }
+        }
+        for line in lines.iter_mut() {
+            if line.ellipsis || line.width() > width {
+                fit_with_ellipsis(line, width);
+            }
+        }
+        lines
+    }
+
+    ///
+    /// Returns the top of the first line, following the vertical alignment.
+    ///
+    fn text_top(&self, line_count: usize) -> f64 {
+        let text_height = line_count as f64 * metrics::line_height() as f64;
+        let free = (self.size.height as f64 - PADDING * 2.0 - text_height).max(0.0);
+        let offset = match self.vertical_alignment {
+            VerticalAlignment::Top => 0.0,
+            VerticalAlignment::Center => free / 2.0,
+            VerticalAlignment::Bottom => free,
+        };
+        self.position.y + PADDING + offset
+    }
+
+    ///
+    /// Returns the left of `line`, following the horizontal alignment.
+    ///
+    fn line_left(&self, line: &Line) -> f64 {
+        let free = (self.content_width() - line.width()).max(0.0);
+        let offset = match self.horizontal_alignment {
+            HorizontalAlignment::Left => 0.0,
+            HorizontalAlignment::Center => free / 2.0,
+            HorizontalAlignment::Right => free,
+        };
+        self.position.x + PADDING + offset
+    }
+
+    ///
+    /// Returns the index of the character boundary closest to `x`, `y`.
+    ///
+    fn index_at(&self, x: f64, y: f64) -> usize {
+        let lines = self.layout(self.content_width());
+        let Some(last) = lines.len().checked_sub(1) else {
+            return 0;
+        };
+        let line_height = metrics::line_height() as f64;
+        let row = ((y - self.text_top(lines.len())) / line_height).floor();
+        let line = &lines[(row.max(0.0) as usize).min(last)];
+        let left = self.line_left(line);
+        let mut offset = 0.0;
+        for (i, c) in line.text.chars().enumerate() {
+            let width = metrics::text_width(&c.to_string()) as f64;
+            if x < left + offset + width / 2.0 {
+                return line.start + i;
+            }
+            offset += width;
+        }
+        line.start + line.len()
+    }
+
+    ///
+    /// Returns the selection as an ordered `(start, end)` range, if it is not empty.
+    ///
+    fn selection_range(&self) -> Option<(usize, usize)> {
+        let (anchor, caret) = self.selection?;
+        let range = (anchor.min(caret), anchor.max(caret));
+        (range.0 < range.1).then_some(range)
+    }
+
+    fn has_focus(&self) -> bool {
+        focus::is_focused(self.focus_id)
+    }
+}
+
+impl Component for Label {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if !self.selectable {
+            return;
+        }
+        let (x, y) = (input.position.x, input.position.y);
+        if input.pressed && !self.selecting {
+            if self.bounds().contains(x, y) {
+                let index = self.index_at(x, y);
+                self.selection = Some((index, index));
+                self.selecting = true;
+                focus::set_focus(self.focus_id);
+            } else {
+                self.selection = None;
+                focus::clear_focus(self.focus_id);
+            }
+        } else if self.selecting {
+            let index = self.index_at(x, y);
+            if let Some((_, caret)) = self.selection.as_mut() {
+                *caret = index;
+            }
+        }
+        if input.just_released {
+            self.selecting = false;
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        let Key::Character(key) = &event.logical_key else {
+            return;
+        };
+        let key = key.to_lowercase();
+        let modifiers = event::modifiers();
+
+        if modifiers.alt_key()
+            && let (Some(mnemonic), Some(target)) = (self.mnemonic(), self.target)
+            && mnemonic.to_lowercase().eq(key.chars())
+        {
+            focus::set_focus(target);
+            return;
+        }
+
+        if self.selectable && self.has_focus() && modifiers.control_key() {
+            match key.as_str() {
+                "c" => {
+                    let selected = self.selected_text();
+                    if !selected.is_empty() {
+                        clipboard::set_text(&selected);
+                    }
+                }
+                "a" => self.selection = Some((0, self.text.chars().count())),
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let lines = self.layout(self.content_width());
+        let line_height = metrics::line_height() as f64;
+        let top = self.text_top(lines.len());
+        let selection = self.selection_range();
+
+        context.with_clip(&self.bounds(), |context| {
+            for (row, line) in lines.iter().enumerate() {
+                let x = self.line_left(line);
+                let y = top + row as f64 * line_height;
+                let line_end = line.start + line.len();
+
+                if let Some((start, end)) = selection
+                    && start < line_end
+                    && end > line.start
+                {
+                    let from = line.offset_of(start.saturating_sub(line.start));
+                    let to = line.offset_of(end.min(line_end) - line.start);
+                    context.blend_rect(
+                        x + from,
+                        y,
+                        (to - from).ceil() as u32,
+                        line_height as u32,
+                        SELECTION_COLOR,
+                        SELECTION_ALPHA,
+                    );
+                }
+
+                context.draw_text(&line.text, x, y, self.color);
+                if line.ellipsis {
+                    let width = metrics::text_width(&line.text) as f64;
+                    context.draw_text(ELLIPSIS, x + width, y, self.color);
+                }
+
+                if let Some(mnemonic) = self.mnemonic
+                    && mnemonic >= line.start
+                    && mnemonic < line_end
+                {
+                    let index = mnemonic - line.start;
+                    let from = x + line.offset_of(index);
+                    let to = x + line.offset_of(index + 1);
+                    let underline_y = y + line_height - 4.0;
+                    context.draw_line(from, underline_y, to, underline_y, self.color);
+                }
+            }
+        });
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        let height = if self.wrap {
+            self.height_for_width(width as u32)
+        } else {
+            height as u32
+        };
+        self.size = Size {
+            width: width as u32,
+            height,
+        };
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
+
+///
+/// Remove the `&` mnemonic marker from `text`, returning the text and the character index of
+/// the mnemonic. `&&` is a literal `&`.
+///
+pub(crate) fn parse_mnemonic(text: &str) -> (String, Option<usize>) {
+    let mut result = String::new();
+    let mut mnemonic = None;
+    let mut count = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            match chars.next() {
+                Some('&') => result.push('&'),
+                Some(next) => {
+                    mnemonic.get_or_insert(count);
+                    result.push(next);
+                }
+                None => break,
+            }
+        } else {
+            result.push(c);
+        }
+        count += 1;
+    }
+    (result, mnemonic)
+}
+
+///
+/// Wrap `chars`, a paragraph starting at character index `start`, into lines no wider than
+/// `width`, breaking after whitespace where possible.
+///
+fn wrap_paragraph(chars: &[char], start: usize, width: f64, lines: &mut Vec<Line>) {
+    if chars.is_empty() {
+        lines.push(Line {
+            start,
+            text: String::new(),
+            ellipsis: false,
+        });
+        return;
+    }
+    let mut line_start = 0;
+    while line_start < chars.len() {
+        let mut end = line_start;
+        let mut line_width = 0.0;
+        let mut last_break = None;
+        while end < chars.len() {
+            let char_width = metrics::text_width(&chars[end].to_string()) as f64;
+            if line_width + char_width > width && end > line_start && !chars[end].is_whitespace() {
+                break;
+            }
+            line_width += char_width;
+            if chars[end].is_whitespace() {
+                last_break = Some(end);
+            }
+            end += 1;
+        }
+        if end < chars.len()
+            && let Some(last_break) = last_break
+        {
+            end = last_break + 1;
+        }
+        lines.push(Line {
+            start: start + line_start,
+            text: chars[line_start..end].iter().collect(),
+            ellipsis: false,
+        });
+        line_start = end;
+    }
+}
 
-// TODO: Future Label Component
+///
+/// Remove characters from the end of `line` until it fits in `width` with an ellipsis.
+///
+fn fit_with_ellipsis(line: &mut Line, width: f64) {
+    line.ellipsis = true;
+    while !line.text.is_empty() && line.width() > width {
+        line.text.pop();
+    }
+}
@@ -0,0 +1,758 @@
+//!
+//! # Structura Component: ListView.
+//!
+//! A scrolling list of rows, for any number of items.
+//!
+//! The items come from a `ListDataSource`, which only has to give the number of items and the
+//! item at an index. The `ListView` asks for the items of the visible rows when it draws, so
+//! the cost of layout, drawing and scrolling does not depend on the number of items.
+//!
+//! ```ignore
+//! let results = ListView::new(lines, |line: &String| line.clone())
+//!     .selection_mode(SelectionMode::Multiple)
+//!     .on_activate(|index| println!("open {index}"));
+//! ```
+//!
+//! - All rows have the same height.
+//! - Rows show the item's text, or are drawn by a custom row renderer, over the selection
+//!   and hover highlight.
+//! - Click selects a row. In `SelectionMode::Multiple`, `Ctrl+click` adds or removes a row,
+//!   and `Shift+click` selects the rows from the last clicked row.
+//! - With focus, `Up` / `Down`, `PageUp` / `PageDown` and `Home` / `End` move the current row,
+//!   with `Shift` to select as they go, or `Ctrl` to move without selecting. `Ctrl+Space`
+//!   toggles the current row, and `Ctrl+A` selects all rows. `Enter` or a double-click
+//!   activates the current row.
+//! - The mouse wheel, over the list, and the keyboard scroll smoothly. The scrollbar thumb can
+//!   be dragged.
+//! - Items added or removed through a shared data source, e.g. `Rc<RefCell<Vec<T>>>`, show on
+//!   the next redraw.
+//!
+
+use crate::component::scrollbar::{SCROLLBAR_WIDTH, ScrollBar};
+use crate::component::{Component, ComponentState, metrics};
+use crate::event::focus::{self, FocusId};
+use crate::event::{self, MouseInput};
+use crate::geometry::{Orientation, Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::event::{ElementState, MouseScrollDelta};
+use winit::keyboard::{Key, NamedKey};
+
+/// Space left of the text in each row.
+const PADDING: f64 = 8.0;
+
+/// Rows scrolled by one notch of the mouse wheel.
+const WHEEL_ROWS: f64 = 3.0;
+
+/// Longest time between the clicks of a double-click.
+pub(crate) const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// Color of the highlight behind selected rows.
+pub(crate) const SELECTION_COLOR: u32 = 0xFF3399FF;
+
+/// Opacity of the selection highlight, from `0.0` to `1.0`.
+pub(crate) const SELECTION_ALPHA: f32 = 0.5;
+
+/// Opacity of the highlight behind the row under the cursor.
+pub(crate) const HOVER_ALPHA: f32 = 0.15;
+
+///
+/// The items shown by a `ListView`: their number, and the item at each index.
+///
+/// `item()` is only called for the visible rows, so a data source can hold its items in any
+/// form, e.g. load them on demand.
+///
+pub trait ListDataSource {
+    type Item;
+
+    fn count(&self) -> usize;
+
+    fn item(&self, index: usize) -> Self::Item;
+}
+
+impl<T: Clone> ListDataSource for Vec<T> {
+    type Item = T;
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn item(&self, index: usize) -> T {
+        self[index].clone()
+    }
+}
+
+///
+/// A shared data source, so the items can change while the `ListView` shows them, e.g. as
+/// lines are added to a log.
+///
+impl<S: ListDataSource> ListDataSource for Rc<RefCell<S>> {
+    type Item = S::Item;
+
+    fn count(&self) -> usize {
+        self.borrow().count()
+    }
+
+    fn item(&self, index: usize) -> S::Item {
+        self.borrow().item(index)
+    }
+}
+
+///
+/// How many rows of a `ListView` can be selected.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SelectionMode {
+    None,
+    #[default]
+    Single,
+    Multiple,
+}
+
+///
+/// The selected rows of a `ListView`, held as ranges of indices so that selecting many rows
+/// stays cheap.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListSelection {
+    /// Sorted ranges, neither overlapping nor touching.
+    ranges: Vec<Range<usize>>,
+}
+
+impl ListSelection {
+    pub fn contains(&self, index: usize) -> bool {
+        let i = self.ranges.partition_point(|range| range.end <= index);
+        i < self.ranges.len() && self.ranges[i].start <= index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    ///
+    /// Returns the number of selected rows.
+    ///
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|range| range.len()).sum()
+    }
+
+    ///
+    /// Returns the selected ranges of indices, in order.
+    ///
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    ///
+    /// Returns the selected indices, in order.
+    ///
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ranges.iter().flat_map(|range| range.clone())
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    fn add(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        //
+        // Merge with every range that overlaps or touches `range`.
+        //
+        let start = self.ranges.partition_point(|r| r.end < range.start);
+        let end = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if start < end {
+            self.ranges[start].start.min(range.start)..self.ranges[end - 1].end.max(range.end)
+        } else {
+            range
+        };
+        self.ranges.splice(start..end, [merged]);
+    }
+
+    fn remove(&mut self, range: Range<usize>) {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        for r in self.ranges.drain(..) {
+            if r.end <= range.start || r.start >= range.end {
+                ranges.push(r);
+                continue;
+            }
+            if r.start < range.start {
+                ranges.push(r.start..range.start);
+            }
+            if r.end > range.end {
+                ranges.push(range.end..r.end);
+            }
+        }
+        self.ranges = ranges;
+    }
+
+    fn toggle(&mut self, index: usize) {
+        if self.contains(index) {
+            self.remove(index..index + 1);
+        } else {
+            self.add(index..index + 1);
+        }
+    }
+
+    ///
+    /// Drop the indices at and after `count`, e.g. after items were removed.
+    ///
+    fn truncate(&mut self, count: usize) {
+        self.remove(count..usize::MAX);
+    }
+}
+
+///
+/// How a row is shown, passed to the row renderer.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct RowState {
+    pub index: usize,
+    pub selected: bool,
+
+    /// The row moved by the keyboard.
+    pub current: bool,
+    pub hovered: bool,
+}
+
+/// Shows an item as text.
+type Display<T> = Box<dyn Fn(&T) -> String>;
+
+/// Called with the selection, when the user changes it.
+type SelectionHandler = Box<dyn FnMut(&ListSelection)>;
+
+/// Draws the content of a row, within its bounds.
+type RowRenderer<T> = Box<dyn Fn(&mut BufferContext, &T, &Rectangle, &RowState)>;
+
+///
+/// The state of a `ListView` kept by `Component::save_state()`.
+///
+struct ListViewState {
+    selection: ListSelection,
+    current: Option<usize>,
+    scroll: f64,
+}
+
+///
+/// ListView control showing a virtualized list of items from a `ListDataSource`.
+///
+pub struct ListView<S: ListDataSource> {
+    position: Point,
+    size: Size,
+    source: S,
+    display: Display<S::Item>,
+    renderer: Option<RowRenderer<S::Item>>,
+    row_height: f64,
+    selection_mode: SelectionMode,
+    selection: ListSelection,
+
+    /// The row moved by the keyboard, and the end of a `Shift` selection.
+    current: Option<usize>,
+
+    /// The start of a `Shift` selection.
+    anchor: Option<usize>,
+    hovered: Option<usize>,
+
+    /// True while the cursor is over the `ListView`, so the wheel scrolls it.
+    cursor_over: bool,
+
+    /// Holds the scroll offset, in pixels from the top of the first row. Behind a `RefCell` so
+    /// that `draw` can update it for items added or removed through a shared data source.
+    scroll: RefCell<ScrollBar>,
+    selecting: bool,
+    last_click: Option<(usize, Instant)>,
+    focus_id: FocusId,
+    on_selection_changed: Option<SelectionHandler>,
+    on_activate: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl<S: ListDataSource> ListView<S> {
+    ///
+    /// Constructor, showing the items of `source` as text with `display`.
+    ///
+    pub fn new<F: Fn(&S::Item) -> String + 'static>(source: S, display: F) -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 300,
+                height: 400,
+            },
+            source,
+            display: Box::new(display),
+            renderer: None,
+            row_height: (metrics::line_height() as f64 + PADDING).ceil(),
+            selection_mode: SelectionMode::Single,
+            selection: ListSelection::default(),
+            current: None,
+            anchor: None,
+            hovered: None,
+            cursor_over: false,
+            scroll: RefCell::new(ScrollBar::new(Orientation::Vertical)),
+            selecting: false,
+            last_click: None,
+            focus_id: FocusId::new(),
+            on_selection_changed: None,
+            on_activate: None,
+        }
+    }
+
+    ///
+    /// Draw the content of each row with `renderer`, instead of the item's text. The row's
+    /// selection and hover highlight is drawn first.
+    ///
+    pub fn row_renderer<F>(mut self, renderer: F) -> Self
+    where
+        F: Fn(&mut BufferContext, &S::Item, &Rectangle, &RowState) + 'static,
+    {
+        self.renderer = Some(Box::new(renderer));
+        self
+    }
+
+    ///
+    /// Set the height of every row.
+    ///
+    pub fn row_height(mut self, row_height: f64) -> Self {
+        self.row_height = row_height.max(1.0);
+        self
+    }
+
+    pub fn selection_mode(mut self, selection_mode: SelectionMode) -> Self {
+        self.selection_mode = selection_mode;
+        self.selection.clear();
+        self
+    }
+
+    ///
+    /// Add event handler, called when the user changes the selection.
+    ///
+    pub fn on_selection_changed<F: FnMut(&ListSelection) + 'static>(mut self, f: F) -> Self {
+        self.on_selection_changed = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Add event handler, called with the index of a row when it is double-clicked, or when
+    /// `Enter` is pressed on the current row.
+    ///
+    pub fn on_activate<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_activate = Some(Box::new(f));
+        self
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    ///
+    /// Returns the data source, to change the items.
+    ///
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    pub fn selection(&self) -> &ListSelection {
+        &self.selection
+    }
+
+    ///
+    /// Returns the first selected index.
+    ///
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selection.first()
+    }
+
+    ///
+    /// Select only the row at `index`, or none, and scroll it into view. Does not call
+    /// `on_selection_changed`.
+    ///
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selection.clear();
+        let index = index.filter(|&index| index < self.source.count());
+        if let Some(index) = index
+            && self.selection_mode != SelectionMode::None
+        {
+            self.selection.add(index..index + 1);
+            self.scroll_into_view(index);
+        }
+        self.current = index;
+        self.anchor = index;
+    }
+
+    ///
+    /// Returns the index of the first visible row.
+    ///
+    pub fn first_visible(&self) -> usize {
+        (self.scroll.borrow().offset() / self.row_height) as usize
+    }
+
+    ///
+    /// Scroll as little as possible for the row at `index` to be fully visible.
+    ///
+    pub fn scroll_into_view(&mut self, index: usize) {
+        self.update_scrollbar();
+        let top = index as f64 * self.row_height;
+        self.scroll.get_mut().scroll_into_view(top, top + self.row_height);
+    }
+
+    ///
+    /// Returns the `FocusId` of the `ListView`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    ///
+    /// Update the scrollbar for the number of items and the size, which may have changed.
+    ///
+    fn update_scrollbar(&self) {
+        let content = self.source.count() as f64 * self.row_height;
+        let mut scroll = self.scroll.borrow_mut();
+        scroll.set_lengths(content, self.size.height as f64);
+        scroll.set_track(Rectangle::new(
+            self.position.x + self.size.width.saturating_sub(SCROLLBAR_WIDTH) as f64,
+            self.position.y,
+            SCROLLBAR_WIDTH.min(self.size.width),
+            self.size.height,
+        ));
+    }
+
+    ///
+    /// Returns the bounds of the rows, left of the scrollbar.
+    ///
+    fn rows_bounds(&self) -> Rectangle {
+        let scrollbar = if self.scroll.borrow().is_shown() {
+            SCROLLBAR_WIDTH
+        } else {
+            0
+        };
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width.saturating_sub(scrollbar),
+            self.size.height,
+        )
+    }
+
+    fn row_at(&self, y: f64) -> Option<usize> {
+        let offset = y - self.position.y + self.scroll.borrow().offset();
+        if offset < 0.0 {
+            return None;
+        }
+        let row = (offset / self.row_height) as usize;
+        (row < self.source.count()).then_some(row)
+    }
+
+    fn rows_per_page(&self) -> usize {
+        ((self.size.height as f64 / self.row_height) as usize).max(1)
+    }
+
+    fn selection_changed(&mut self) {
+        if let Some(handler) = self.on_selection_changed.as_mut() {
+            handler(&self.selection);
+        }
+    }
+
+    fn activate(&mut self, index: usize) {
+        if let Some(handler) = self.on_activate.as_mut() {
+            handler(index);
+        }
+    }
+
+    ///
+    /// Make `index` the current row, selecting as for a click or key press with the
+    /// modifiers held: `extend` selects from the anchor, `toggle` adds or removes the row, and
+    /// `select` selects only the row.
+    ///
+    fn select_row(&mut self, index: usize, extend: bool, toggle: bool, select: bool) {
+        let before = self.selection.clone();
+        let multiple = self.selection_mode == SelectionMode::Multiple;
+        match self.selection_mode {
+            SelectionMode::None => {}
+            _ if multiple && extend => {
+                let anchor = self.anchor.unwrap_or(index);
+                if !toggle {
+                    self.selection.clear();
+                }
+                self.selection.add(anchor.min(index)..anchor.max(index) + 1);
+            }
+            _ if multiple && toggle && select => {
+                self.selection.toggle(index);
+                self.anchor = Some(index);
+            }
+            _ if select => {
+                self.selection.clear();
+                self.selection.add(index..index + 1);
+                self.anchor = Some(index);
+            }
+            _ => {}
+        }
+        self.current = Some(index);
+        self.scroll_into_view(index);
+        if self.selection != before {
+            self.selection_changed();
+        }
+    }
+
+    ///
+    /// Move the current row for a key press: by a row or a page, or to the start or end.
+    ///
+    fn move_current(&mut self, key: &NamedKey) -> bool {
+        let count = self.source.count();
+        if count == 0 {
+            return false;
+        }
+        let current = self.current.map(|current| current.min(count - 1));
+        let page = self.rows_per_page();
+        let target = match (key, current) {
+            (NamedKey::ArrowDown, Some(current)) => (current + 1).min(count - 1),
+            (NamedKey::ArrowUp, Some(current)) => current.saturating_sub(1),
+            (NamedKey::PageDown, Some(current)) => (current + page).min(count - 1),
+            (NamedKey::PageUp, Some(current)) => current.saturating_sub(page),
+            (NamedKey::ArrowDown | NamedKey::PageDown | NamedKey::Home, _) => 0,
+            (NamedKey::ArrowUp | NamedKey::PageUp, None) => 0,
+            (NamedKey::End, _) => count - 1,
+            _ => return false,
+        };
+        let modifiers = event::modifiers();
+        let (shift, ctrl) = (modifiers.shift_key(), modifiers.control_key());
+        self.select_row(target, shift, ctrl, !ctrl);
+        true
+    }
+}
+
+impl<S: ListDataSource> Component for ListView<S> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let (x, y) = (input.position.x, input.position.y);
+        self.update_scrollbar();
+        let on_rows = self.rows_bounds().contains(x, y);
+        self.hovered = if on_rows { self.row_at(y) } else { None };
+        self.cursor_over = self.bounds().contains(x, y);
+
+        if input.pressed {
+            if self.bounds().contains(x, y) {
+                focus::set_focus(self.focus_id);
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+        }
+        if self.scroll.get_mut().handle_mouse_event(&input) {
+            return;
+        }
+        if input.pressed {
+            if let Some(row) = self.hovered {
+                let modifiers = event::modifiers();
+                let (shift, ctrl) = (modifiers.shift_key(), modifiers.control_key());
+                let double_click = self
+                    .last_click
+                    .is_some_and(|(last, at)| last == row && at.elapsed() <= DOUBLE_CLICK_TIME);
+                self.select_row(row, shift, ctrl, true);
+                self.selecting = !ctrl;
+                if double_click {
+                    self.last_click = None;
+                    self.activate(row);
+                } else {
+                    self.last_click = Some((row, Instant::now()));
+                }
+            }
+        } else if self.selecting
+            && !input.is_elsewhere()
+            && self.selection_mode == SelectionMode::Multiple
+        {
+            //
+            // Dragging over the rows selects from the pressed row.
+            //
+            let count = self.source.count();
+            let row = self
+                .row_at(y.max(self.position.y))
+                .unwrap_or(count.saturating_sub(1));
+            if count > 0 && Some(row) != self.current {
+                self.select_row(row, true, false, true);
+            }
+        }
+        if input.just_released {
+            self.selecting = false;
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+        if !self.cursor_over {
+            return;
+        }
+        let dy = match delta {
+            MouseScrollDelta::LineDelta(_, y) => -*y as f64 * WHEEL_ROWS * self.row_height,
+            MouseScrollDelta::PixelDelta(p) => -p.y,
+        };
+        self.update_scrollbar();
+        self.scroll.get_mut().scroll_by(dy);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if !focus::is_focused(self.focus_id) || event.state != ElementState::Pressed {
+            return;
+        }
+        self.update_scrollbar();
+        let count = self.source.count();
+        let ctrl = event::modifiers().control_key();
+        match &event.logical_key {
+            Key::Named(key) if self.move_current(key) => {}
+            Key::Named(NamedKey::Space) if ctrl => {
+                if let Some(current) = self.current.filter(|&current| current < count) {
+                    self.select_row(current, false, true, true);
+                }
+            }
+            Key::Named(NamedKey::Enter) => {
+                if let Some(current) = self.current.filter(|&current| current < count) {
+                    self.activate(current);
+                }
+            }
+            Key::Character(key)
+                if ctrl
+                    && key.eq_ignore_ascii_case("a")
+                    && self.selection_mode == SelectionMode::Multiple =>
+            {
+                self.selection.clear();
+                self.selection.add(0..count);
+                self.selection_changed();
+            }
+            _ => {}
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.update_scrollbar();
+        self.scroll.get_mut().animate(now);
+    }
+
+    ///
+    /// Keeps the selection, current row and scroll position.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(ListViewState {
+            selection: self.selection.clone(),
+            current: self.current,
+            scroll: self.scroll.borrow().target(),
+        }))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<ListViewState>() {
+            let count = self.source.count();
+            self.selection = state.selection;
+            self.selection.truncate(count);
+            self.current = state.current.filter(|&current| current < count);
+            self.anchor = self.current;
+            self.update_scrollbar();
+            self.scroll.get_mut().set_offset(state.scroll);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = focus::is_focused(self.focus_id);
+        let style = context.theme.style_for(&ComponentState::Active);
+        let (x, y, w, h) = (
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        );
+        context.fill_rect(x, y, w, h, style.edit_back_color.value);
+        self.update_scrollbar();
+
+        //
+        // Only the rows from the first visible one to the bottom edge are drawn.
+        //
+        let count = self.source.count();
+        let scroll = self.scroll.borrow().offset();
+        let first = (scroll / self.row_height) as usize;
+        let visible = (h as f64 / self.row_height).ceil() as usize + 1;
+        let rows = self.rows_bounds();
+        let line_height = context.line_height() as f64;
+        context.with_clip(&rows, |context| {
+            for index in first..(first + visible).min(count) {
+                let row_y = y + index as f64 * self.row_height - scroll;
+                let bounds = Rectangle::new(x, row_y, rows.size.width, self.row_height as u32);
+                let state = RowState {
+                    index,
+                    selected: self.selection.contains(index),
+                    current: self.current == Some(index),
+                    hovered: self.hovered == Some(index),
+                };
+                let (bw, bh) = (bounds.size.width, bounds.size.height);
+                if state.selected {
+                    context.blend_rect(x, row_y, bw, bh, SELECTION_COLOR, SELECTION_ALPHA);
+                } else if state.hovered {
+                    context.blend_rect(x, row_y, bw, bh, style.fore_color.value, HOVER_ALPHA);
+                }
+                let item = self.source.item(index);
+                match self.renderer.as_ref() {
+                    Some(renderer) => {
+                        context.with_clip(&bounds, |context| {
+                            renderer(context, &item, &bounds, &state);
+                        });
+                    }
+                    None => {
+                        let text = (self.display)(&item);
+                        let text_y = row_y + (self.row_height - line_height) / 2.0;
+                        context.draw_text(&text, x + PADDING, text_y, style.edit_text_color.value);
+                    }
+                }
+                if state.current && focused {
+                    context.draw_border(x, row_y, bw, bh, 1, style.cursor_color.value);
+                }
+            }
+        });
+
+        self.scroll.borrow().draw(context);
+
+        let border = if focused {
+            style.cursor_color.value
+        } else {
+            style.border_color.value
+        };
+        context.draw_border(x, y, w, h, 1, border);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.update_scrollbar();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.update_scrollbar();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,806 @@
+//!
+//! # Structura Component: Menu.
+//!
+//! Pop-up menus of `Command`s, shown from a `MenuBar` or as context menus.
+//!
+//! ```ignore
+//! let menu = Menu::new()
+//!     .item("&Open...", &open)
+//!     .item("&Save", &save)
+//!     .separator()
+//!     .submenu("&Recent", recent)
+//!     .item("&Word Wrap", &word_wrap);
+//! let editor = WithContextMenu::new(TextField::new(), menu);
+//! ```
+//!
+//! - An `&` in the text of an item marks its mnemonic, which is underlined. Typing it while
+//!   the menu is open chooses the item.
+//! - Items trigger their `Command`, the same as its keyboard shortcut, which is shown at the
+//!   right of the item. Disabled commands are greyed out, and checkable and radio commands
+//!   show their checked state.
+//! - Each open menu is a popup in its own layer, next to the item of its parent menu. All
+//!   of them close when an item is chosen, or on a click outside of them.
+//! - `Up` / `Down`, `Home` / `End` move through the items, skipping separators and disabled
+//!   items. `Enter` or `Space` choose the item, `Right` opens a submenu, and `Left` or
+//!   `Escape` close it.
+//! - A `WithContextMenu` opens its menu on a right click, or on the `Menu` key or
+//!   `Shift+F10` after it was clicked.
+//!
+
+use crate::command::Command;
+use crate::component::label::parse_mnemonic;
+use crate::component::{Component, ComponentState, metrics};
+use crate::container::Container;
+use crate::event::{self, MouseInput};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::layer::{self, Layer, LayerId, Placement};
+use crate::view::{BufferContext, blend_color};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Instant;
+use winit::event::ElementState;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+/// Space above and below the text of each item.
+const ITEM_PADDING: f64 = 4.0;
+
+/// Space left of the text of each item, where check marks are drawn.
+const GUTTER_WIDTH: f64 = 28.0;
+
+/// Space right of each item, where submenu arrows are drawn.
+const ARROW_WIDTH: f64 = 24.0;
+
+/// Smallest space between the text of an item and its accelerator.
+const ACCELERATOR_GAP: f64 = 32.0;
+
+const SEPARATOR_HEIGHT: f64 = 9.0;
+
+const MIN_WIDTH: f64 = 160.0;
+
+/// Opacity of the text of disabled items, blended over the background.
+pub(crate) const DISABLED_ALPHA: f32 = 0.4;
+
+#[derive(Clone, Debug)]
+enum MenuEntry {
+    Item {
+        text: String,
+        mnemonic: Option<usize>,
+        command: Command,
+    },
+    Submenu {
+        text: String,
+        mnemonic: Option<usize>,
+        menu: Menu,
+    },
+    Separator,
+}
+
+impl MenuEntry {
+    ///
+    /// Returns the text, and the character index of the mnemonic.
+    ///
+    fn label(&self) -> Option<(&str, Option<usize>)> {
+        match self {
+            MenuEntry::Item { text, mnemonic, .. } | MenuEntry::Submenu { text, mnemonic, .. } => {
+                Some((text, *mnemonic))
+            }
+            MenuEntry::Separator => None,
+        }
+    }
+
+    fn mnemonic(&self) -> Option<char> {
+        let (text, mnemonic) = self.label()?;
+        text.chars().nth(mnemonic?)
+    }
+
+    ///
+    /// Returns true if the entry can be highlighted and chosen.
+    ///
+    fn is_enabled(&self) -> bool {
+        match self {
+            MenuEntry::Item { command, .. } => command.is_enabled(),
+            MenuEntry::Submenu { menu, .. } => !menu.is_empty(),
+            MenuEntry::Separator => false,
+        }
+    }
+}
+
+///
+/// A list of menu items, separators and submenus.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Menu {
+    entries: Vec<MenuEntry>,
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Add an item that triggers `command`. An `&` in `text` marks the next character as the
+    /// mnemonic (`&&` for a literal `&`).
+    ///
+    pub fn item(mut self, text: &str, command: &Command) -> Self {
+        let (text, mnemonic) = parse_mnemonic(text);
+        self.entries.push(MenuEntry::Item {
+            text,
+            mnemonic,
+            command: command.clone(),
+        });
+        self
+    }
+
+    ///
+    /// Add a line between groups of items.
+    ///
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    ///
+    /// Add an item that opens `menu` as a submenu.
+    ///
+    pub fn submenu(mut self, text: &str, menu: Menu) -> Self {
+        let (text, mnemonic) = parse_mnemonic(text);
+        self.entries.push(MenuEntry::Submenu {
+            text,
+            mnemonic,
+            menu,
+        });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+///
+/// The open popups of a menu, shared by the popups and the `MenuBar` or `WithContextMenu`
+/// that opened them.
+///
+#[derive(Default)]
+pub(crate) struct MenuSession {
+    /// The layer of each open popup, from the top-level menu to the deepest submenu.
+    levels: RefCell<Vec<LayerId>>,
+
+    /// For a `MenuBar`, its menus and the bounds of their titles. A button that opens a menu,
+    /// such as the overflow chevron of a `Toolbar`, is a bar of one menu.
+    pub(crate) bar: RefCell<Vec<(Menu, Rectangle)>>,
+
+    /// For a `MenuBar`, the menu that is open.
+    pub(crate) bar_open: Cell<Option<usize>>,
+
+    /// Set to the menu of the `MenuBar` that a click outside closed, so that a click on its
+    /// title closes the menu rather than opening it again.
+    pub(crate) dismissed: Cell<Option<usize>>,
+}
+
+impl MenuSession {
+    pub(crate) fn is_open(&self) -> bool {
+        !self.levels.borrow().is_empty()
+    }
+
+    fn depth(&self) -> usize {
+        self.levels.borrow().len()
+    }
+
+    ///
+    /// Open `menu` at `level`, next to `anchor`, closing the popups at that level and deeper.
+    /// Only the top-level popup closes on a click outside, so that clicking a parent menu
+    /// keeps its submenu open.
+    ///
+    pub(crate) fn open(
+        self: &Rc<Self>,
+        menu: Menu,
+        anchor: Rectangle,
+        placement: Placement,
+        level: usize,
+        highlight_first: bool,
+    ) {
+        self.close_from(level);
+        let popup = MenuPopup::new(menu, self.clone(), level, highlight_first);
+        let mut layer = Layer::new(Box::new(popup)).anchored(anchor, placement);
+        if level == 0 {
+            let session = self.clone();
+            layer = layer.light_dismiss(true).on_dismiss(move || {
+                session.dismissed.set(session.bar_open.get());
+                session.close_all();
+            });
+        }
+        self.levels.borrow_mut().push(layer::open(layer));
+    }
+
+    ///
+    /// Open the menu of the `MenuBar` at `index`, below its title.
+    ///
+    pub(crate) fn open_bar(self: &Rc<Self>, index: usize, highlight_first: bool) {
+        let Some((menu, anchor)) = self.bar.borrow().get(index).cloned() else {
+            return;
+        };
+        self.open(menu, anchor, Placement::Below, 0, highlight_first);
+        self.bar_open.set(Some(index));
+    }
+
+    ///
+    /// Open the menu of the `MenuBar` `offset` titles away from the open one, wrapping
+    /// around. Returns false if the menu was not opened from a `MenuBar`.
+    ///
+    fn switch_bar(self: &Rc<Self>, offset: isize) -> bool {
+        let count = self.bar.borrow().len() as isize;
+        let Some(open) = self.bar_open.get() else {
+            return false;
+        };
+        self.open_bar((open as isize + offset).rem_euclid(count) as usize, true);
+        true
+    }
+
+    ///
+    /// Close the popups at `level` and deeper.
+    ///
+    pub(crate) fn close_from(&self, level: usize) {
+        let closed = {
+            let mut levels = self.levels.borrow_mut();
+            let level = level.min(levels.len());
+            levels.split_off(level)
+        };
+        for id in closed {
+            layer::close(id);
+        }
+        if level == 0 {
+            self.bar_open.set(None);
+        }
+    }
+
+    pub(crate) fn close_all(&self) {
+        self.close_from(0);
+    }
+}
+
+///
+/// Draw the underline of the mnemonic at character `index` of `text`, drawn at `(x, y)`.
+///
+pub(crate) fn draw_mnemonic(
+    context: &mut BufferContext,
+    text: &str,
+    index: usize,
+    x: f64,
+    y: f64,
+    color: u32,
+) {
+    let prefix: String = text.chars().take(index).collect();
+    let Some(mnemonic) = text.chars().nth(index) else {
+        return;
+    };
+    let from = x + context.text_width(&prefix) as f64;
+    let to = from + context.text_width(&mnemonic.to_string()) as f64;
+    let underline_y = y + context.line_height() as f64 - 4.0;
+    context.draw_line(from, underline_y, to, underline_y, color);
+}
+
+///
+/// Open `menu` as a context menu, with its top-left corner at `position` when there is room.
+///
+pub fn show_context_menu(menu: &Menu, position: Point) {
+    let session = Rc::new(MenuSession::default());
+    let anchor = Rectangle::new(position.x, position.y, 0, 0);
+    session.open(menu.clone(), anchor, Placement::Below, 0, false);
+}
+
+///
+/// The items of a `Menu`, shown in a layer while the menu is open.
+///
+struct MenuPopup {
+    menu: Menu,
+    session: Rc<MenuSession>,
+    level: usize,
+    position: Point,
+    size: Size,
+
+    /// The top of each entry, from the top of the popup, followed by the bottom of the last.
+    row_tops: Vec<f64>,
+
+    /// The accelerator text of each entry, from the shortcut of its `Command`.
+    accelerators: Vec<Option<String>>,
+    highlighted: Option<usize>,
+
+    /// The entry whose submenu was last opened.
+    submenu: Option<usize>,
+}
+
+impl MenuPopup {
+    fn new(menu: Menu, session: Rc<MenuSession>, level: usize, highlight_first: bool) -> Self {
+        let item_height = metrics::line_height() as f64 + 2.0 * ITEM_PADDING;
+        let mut row_tops = vec![1.0];
+        let mut accelerators = vec![];
+        let (mut text_width, mut accelerator_width) = (0.0f64, 0.0f64);
+        for entry in menu.entries.iter() {
+            let accelerator = match entry {
+                MenuEntry::Item { command, .. } => {
+                    command.get_shortcut().map(|shortcut| shortcut.to_string())
+                }
+                _ => None,
+            };
+            if let Some(accelerator) = accelerator.as_ref() {
+                accelerator_width = accelerator_width.max(metrics::text_width(accelerator) as f64);
+            }
+            let height = match entry.label() {
+                Some((text, _)) => {
+                    text_width = text_width.max(metrics::text_width(text) as f64);
+                    item_height
+                }
+                None => SEPARATOR_HEIGHT,
+            };
+            accelerators.push(accelerator);
+            row_tops.push(row_tops[row_tops.len() - 1] + height);
+        }
+        if accelerator_width > 0.0 {
+            accelerator_width += ACCELERATOR_GAP;
+        }
+        let width = (GUTTER_WIDTH + text_width + accelerator_width + ARROW_WIDTH).max(MIN_WIDTH);
+        let height = row_tops[row_tops.len() - 1] + 1.0;
+        let mut popup = Self {
+            menu,
+            session,
+            level,
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: width.ceil() as u32,
+                height: height.ceil() as u32,
+            },
+            row_tops,
+            accelerators,
+            highlighted: None,
+            submenu: None,
+        };
+        if highlight_first {
+            popup.highlighted = popup.step(None, true);
+        }
+        popup
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    fn row_bounds(&self, index: usize) -> Rectangle {
+        let (top, bottom) = (self.row_tops[index], self.row_tops[index + 1]);
+        Rectangle::new(
+            self.position.x + 1.0,
+            self.position.y + top,
+            self.size.width.saturating_sub(2),
+            (bottom - top) as u32,
+        )
+    }
+
+    fn entry_at(&self, point: Point) -> Option<usize> {
+        if !self.bounds().contains(point.x, point.y) {
+            return None;
+        }
+        let y = point.y - self.position.y;
+        self.row_tops
+            .windows(2)
+            .position(|row| (row[0]..row[1]).contains(&y))
+    }
+
+    ///
+    /// Returns the next enabled entry after `from`, or before it when not `forward`,
+    /// wrapping around.
+    ///
+    fn step(&self, from: Option<usize>, forward: bool) -> Option<usize> {
+        let count = self.menu.entries.len();
+        let mut index = from;
+        for _ in 0..count {
+            let next = match (index, forward) {
+                (None, true) => 0,
+                (None, false) => count - 1,
+                (Some(i), true) => (i + 1) % count,
+                (Some(i), false) => (i + count - 1) % count,
+            };
+            if self.menu.entries[next].is_enabled() {
+                return Some(next);
+            }
+            index = Some(next);
+        }
+        from
+    }
+
+    fn is_submenu_open(&self, index: usize) -> bool {
+        self.submenu == Some(index) && self.session.depth() > self.level + 1
+    }
+
+    fn open_submenu(&mut self, index: usize, highlight_first: bool) {
+        if let MenuEntry::Submenu { menu, .. } = &self.menu.entries[index] {
+            let anchor = self.row_bounds(index);
+            let level = self.level + 1;
+            self.session.open(
+                menu.clone(),
+                anchor,
+                Placement::Right,
+                level,
+                highlight_first,
+            );
+            self.submenu = Some(index);
+        }
+    }
+
+    fn close_submenu(&mut self) {
+        self.session.close_from(self.level + 1);
+        self.submenu = None;
+    }
+
+    ///
+    /// Choose the entry at `index`: trigger the `Command` of an item, closing all menus, or
+    /// open a submenu.
+    ///
+    fn activate(&mut self, index: usize, from_keyboard: bool) {
+        let entry = &self.menu.entries[index];
+        if !entry.is_enabled() {
+            return;
+        }
+        match entry {
+            MenuEntry::Item { command, .. } => {
+                let command = command.clone();
+                self.session.close_all();
+                command.trigger();
+            }
+            MenuEntry::Submenu { .. } if from_keyboard || !self.is_submenu_open(index) => {
+                self.open_submenu(index, from_keyboard);
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_check(context: &mut BufferContext, radio: bool, x: f64, y: f64, color: u32) {
+        if radio {
+            context.fill_circle(x, y, 4.0, color);
+            return;
+        }
+        for offset in [0.0, 1.0] {
+            context.draw_line(x - 5.0, y + offset, x - 2.0, y + 3.0 + offset, color);
+            context.draw_line(x - 2.0, y + 3.0 + offset, x + 5.0, y - 4.0 + offset, color);
+        }
+    }
+
+    fn draw_arrow(context: &mut BufferContext, x: f64, y: f64, color: u32) {
+        context.draw_line(x - 2.0, y - 4.0, x + 2.0, y, color);
+        context.draw_line(x + 2.0, y, x - 2.0, y + 4.0, color);
+    }
+}
+
+impl Component for MenuPopup {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        //
+        // Over a submenu, keep the item that opened it highlighted.
+        //
+        if input.is_elsewhere() {
+            return;
+        }
+        let index = self
+            .entry_at(input.position)
+            .filter(|&index| self.menu.entries[index].is_enabled());
+        let Some(index) = index else {
+            if self.session.depth() <= self.level + 1 {
+                self.highlighted = None;
+            }
+            return;
+        };
+        self.highlighted = Some(index);
+        match self.menu.entries[index] {
+            MenuEntry::Submenu { .. } if !self.is_submenu_open(index) => {
+                self.open_submenu(index, false);
+            }
+            MenuEntry::Item { .. } if self.submenu.is_some() => self.close_submenu(),
+            _ => {}
+        }
+        if input.pressed || input.secondary_pressed {
+            self.activate(index, false);
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        match &event.logical_key {
+            Key::Named(NamedKey::ArrowDown) => self.highlighted = self.step(self.highlighted, true),
+            Key::Named(NamedKey::ArrowUp) => self.highlighted = self.step(self.highlighted, false),
+            Key::Named(NamedKey::Home) => self.highlighted = self.step(None, true),
+            Key::Named(NamedKey::End) => self.highlighted = self.step(None, false),
+            Key::Named(NamedKey::Enter | NamedKey::Space) => {
+                if let Some(index) = self.highlighted {
+                    self.activate(index, true);
+                }
+            }
+            Key::Named(NamedKey::ArrowRight) => match self.highlighted {
+                Some(index) if matches!(self.menu.entries[index], MenuEntry::Submenu { .. }) => {
+                    self.activate(index, true);
+                }
+                _ => {
+                    self.session.switch_bar(1);
+                }
+            },
+            Key::Named(NamedKey::ArrowLeft) => {
+                if self.level > 0 {
+                    self.session.close_from(self.level);
+                } else {
+                    self.session.switch_bar(-1);
+                }
+            }
+            Key::Named(NamedKey::Escape) => self.session.close_from(self.level),
+            Key::Character(text) if !event::modifiers().control_key() => {
+                let text = text.to_lowercase();
+                let index = self.menu.entries.iter().position(|entry| {
+                    entry.is_enabled()
+                        && entry
+                            .mnemonic()
+                            .is_some_and(|mnemonic| mnemonic.to_lowercase().eq(text.chars()))
+                });
+                if let Some(index) = index {
+                    self.highlighted = Some(index);
+                    self.activate(index, true);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let hovered = context.theme.style_for(&ComponentState::Hovered);
+        let back_color = style.edit_back_color.value;
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width, self.size.height);
+        context.fill_rect(x, y, width, height, back_color);
+        for (index, entry) in self.menu.entries.iter().enumerate() {
+            let row = self.row_bounds(index);
+            let center_y = row.point.y + (row.size.height / 2) as f64;
+            let Some((text, mnemonic)) = entry.label() else {
+                let right = x + width as f64 - ITEM_PADDING;
+                let color = style.border_color.value;
+                context.draw_line(x + ITEM_PADDING, center_y, right, center_y, color);
+                continue;
+            };
+            let mut color = style.edit_text_color.value;
+            if !entry.is_enabled() {
+                color = blend_color(back_color, color, DISABLED_ALPHA);
+            } else if self.highlighted == Some(index) {
+                let (row_width, row_height) = (row.size.width, row.size.height);
+                let back = hovered.back_color.value;
+                context.fill_rect(row.point.x, row.point.y, row_width, row_height, back);
+                color = hovered.fore_color.value;
+            }
+            let text_x = x + GUTTER_WIDTH;
+            let text_y = row.point.y + ITEM_PADDING;
+            context.draw_text(text, text_x, text_y, color);
+            if let Some(mnemonic) = mnemonic {
+                draw_mnemonic(context, text, mnemonic, text_x, text_y, color);
+            }
+            match entry {
+                MenuEntry::Item { command, .. } => {
+                    if command.is_checked() {
+                        let check_x = x + GUTTER_WIDTH / 2.0;
+                        Self::draw_check(context, command.is_radio(), check_x, center_y, color);
+                    }
+                    if let Some(accelerator) = self.accelerators[index].as_ref() {
+                        let accelerator_width = context.text_width(accelerator) as f64;
+                        let accelerator_x = x + width as f64 - ARROW_WIDTH - accelerator_width;
+                        context.draw_text(accelerator, accelerator_x, text_y, color);
+                    }
+                }
+                MenuEntry::Submenu { .. } => {
+                    let arrow_x = x + width as f64 - ARROW_WIDTH / 2.0;
+                    Self::draw_arrow(context, arrow_x, center_y, color);
+                }
+                MenuEntry::Separator => {}
+            }
+        }
+        context.draw_border(x, y, width, height, 1, style.border_color.value);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    ///
+    /// The popup sizes itself to its items.
+    ///
+    fn set_size(&mut self, _width: usize, _height: usize) {}
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
+
+///
+/// Wraps a `Component` or `ContainerComponent`, opening a context menu on a right click.
+/// Everything else is passed straight through.
+///
+pub struct WithContextMenu<T> {
+    inner: T,
+    menu: Menu,
+    session: Rc<MenuSession>,
+
+    /// Set when the last click was on the `Component`, so the `Menu` key opens its menu.
+    active: bool,
+}
+
+impl<T: Component> WithContextMenu<T> {
+    ///
+    /// Constructor.
+    ///
+    pub fn new(inner: T, menu: Menu) -> Self {
+        Self {
+            inner,
+            menu,
+            session: Rc::new(MenuSession::default()),
+            active: false,
+        }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    ///
+    /// Replace the menu. Takes effect the next time the menu opens.
+    ///
+    pub fn set_menu(&mut self, menu: Menu) {
+        self.menu = menu;
+    }
+
+    ///
+    /// Open the context menu at `position`, closing it first if it is open.
+    ///
+    pub fn show_menu(&mut self, position: Point) {
+        self.open(position, false);
+    }
+
+    pub fn is_menu_open(&self) -> bool {
+        self.session.is_open()
+    }
+
+    fn open(&mut self, position: Point, highlight_first: bool) {
+        if self.menu.is_empty() {
+            return;
+        }
+        let anchor = Rectangle::new(position.x, position.y, 0, 0);
+        self.session.open(
+            self.menu.clone(),
+            anchor,
+            Placement::Below,
+            0,
+            highlight_first,
+        );
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let position = self.inner.get_position();
+        let size = self.inner.get_size();
+        Rectangle::new(position.x, position.y, size.width, size.height)
+    }
+}
+
+impl<T> Drop for WithContextMenu<T> {
+    fn drop(&mut self) {
+        self.session.close_all();
+    }
+}
+
+impl<T: Component> Component for WithContextMenu<T> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        self.inner.handle_mouse_event(input);
+
+        let inside = self.bounds().contains(input.position.x, input.position.y);
+        if input.pressed {
+            self.active = inside;
+        }
+        if input.secondary_pressed && inside {
+            self.active = true;
+            self.open(input.position, false);
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        self.inner.handle_mouse_wheel_event(delta, phase);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        self.inner.handle_keyboard_event(event);
+        if !self.active || event.state != ElementState::Pressed {
+            return;
+        }
+        let shift_f10 = event.logical_key == Key::Named(NamedKey::F10)
+            && event::modifiers() == ModifiersState::SHIFT;
+        if shift_f10 || event.logical_key == Key::Named(NamedKey::ContextMenu) {
+            let position = self.inner.get_position();
+            self.open(position, true);
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.inner.animate(now);
+    }
+
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        self.inner.save_state()
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        self.inner.restore_state(state);
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        self.inner.draw(context);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.inner.set_position(x, y);
+    }
+
+    fn get_position(&self) -> Point {
+        self.inner.get_position()
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.inner.set_size(width, height);
+    }
+
+    fn get_size(&self) -> Size {
+        self.inner.get_size()
+    }
+}
+
+impl<T: Container + Component> Container for WithContextMenu<T> {
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.inner.push(component);
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        self.inner.resize(width, height);
+    }
+
+    fn update_layout(&mut self) {
+        self.inner.update_layout();
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        self.inner.fills_parent_container()
+    }
+}
@@ -0,0 +1,313 @@
+//!
+//! # Structura Component: MenuBar.
+//!
+//! A row of menu titles, usually in the North of a `BorderLayout`, each opening a `Menu`.
+//!
+//! ```ignore
+//! let menu_bar = MenuBar::new()
+//!     .menu("&File", Menu::new().item("&Open...", &open).item("&Save", &save))
+//!     .menu("&Edit", Menu::new().item("&Undo", &undo));
+//! ```
+//!
+//! - A click on a title opens its menu below it, and a second click closes it. While a menu
+//!   is open, moving the cursor over another title opens that one instead.
+//! - `Alt` + the mnemonic of a title opens its menu. `F10` moves keyboard focus to the bar,
+//!   where `Left` / `Right` choose a title, and `Down`, `Enter` or `Space` open it.
+//! - In an open menu, `Left` / `Right` move to the neighbouring menu of the bar.
+//!
+
+use crate::component::label::parse_mnemonic;
+use crate::component::menu::{Menu, MenuSession, draw_mnemonic};
+use crate::component::{Component, ComponentState, metrics};
+use crate::event::focus::{self, FocusId};
+use crate::event::{self, MouseInput};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::rc::Rc;
+use winit::event::ElementState;
+use winit::keyboard::{Key, NamedKey};
+
+/// Space left and right of each title.
+const TITLE_PADDING: f64 = 12.0;
+
+/// Space above and below the titles.
+const BAR_PADDING: f64 = 6.0;
+
+///
+/// A bar of menu titles.
+///
+pub struct MenuBar {
+    position: Point,
+    size: Size,
+
+    /// The text of each title, and the character index of its mnemonic.
+    titles: Vec<(String, Option<usize>)>,
+
+    /// The popups of the open menu. Also holds the menus, and the bounds of their titles.
+    session: Rc<MenuSession>,
+    hovered: Option<usize>,
+
+    /// The title chosen with the keyboard, while the bar has focus after `F10`.
+    keyboard_title: usize,
+
+    /// The focus before `F10`, given back when the bar loses focus.
+    previous_focus: Option<FocusId>,
+    focus_id: FocusId,
+}
+
+impl Default for MenuBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MenuBar {
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: (metrics::line_height() as f64 + 2.0 * BAR_PADDING).ceil() as u32,
+            },
+            titles: vec![],
+            session: Rc::new(MenuSession::default()),
+            hovered: None,
+            keyboard_title: 0,
+            previous_focus: None,
+            focus_id: FocusId::new(),
+        }
+    }
+
+    ///
+    /// Add a menu, with its `title`. An `&` in `title` marks the next character as the
+    /// mnemonic.
+    ///
+    pub fn menu(mut self, title: &str, menu: Menu) -> Self {
+        self.titles.push(parse_mnemonic(title));
+        let anchor = Rectangle::new(0.0, 0.0, 0, 0);
+        self.session.bar.borrow_mut().push((menu, anchor));
+        self.layout();
+        self
+    }
+
+    ///
+    /// Replace the menu at `index`. Takes effect the next time the menu opens.
+    ///
+    pub fn set_menu(&mut self, index: usize, menu: Menu) {
+        if let Some(entry) = self.session.bar.borrow_mut().get_mut(index) {
+            entry.0 = menu;
+        }
+    }
+
+    ///
+    /// Returns the index of the open menu.
+    ///
+    pub fn open_menu(&self) -> Option<usize> {
+        self.session.bar_open.get()
+    }
+
+    ///
+    /// Open the menu at `index`, highlighting its first item.
+    ///
+    pub fn open(&mut self, index: usize) {
+        self.leave_keyboard_mode();
+        self.session.open_bar(index, true);
+    }
+
+    pub fn close(&mut self) {
+        self.session.close_all();
+    }
+
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    ///
+    /// Place the titles from the left of the bar, and record their bounds for the popups.
+    ///
+    fn layout(&mut self) {
+        let mut x = self.position.x;
+        let mut bar = self.session.bar.borrow_mut();
+        for ((text, _), (_, bounds)) in self.titles.iter().zip(bar.iter_mut()) {
+            let width = metrics::text_width(text) as f64 + 2.0 * TITLE_PADDING;
+            *bounds = Rectangle::new(x, self.position.y, width.ceil() as u32, self.size.height);
+            x += width.ceil();
+        }
+    }
+
+    fn title_at(&self, point: Point) -> Option<usize> {
+        self.session
+            .bar
+            .borrow()
+            .iter()
+            .position(|(_, bounds)| bounds.contains(point.x, point.y))
+    }
+
+    fn title_with_mnemonic(&self, key: &str) -> Option<usize> {
+        let key = key.to_lowercase();
+        self.titles.iter().position(|(text, mnemonic)| {
+            mnemonic
+                .and_then(|index| text.chars().nth(index))
+                .is_some_and(|mnemonic| mnemonic.to_lowercase().eq(key.chars()))
+        })
+    }
+
+    fn is_keyboard_mode(&self) -> bool {
+        focus::is_focused(self.focus_id)
+    }
+
+    fn enter_keyboard_mode(&mut self) {
+        if self.titles.is_empty() {
+            return;
+        }
+        self.previous_focus = focus::focused();
+        self.keyboard_title = 0;
+        focus::set_focus(self.focus_id);
+    }
+
+    fn leave_keyboard_mode(&mut self) {
+        if !self.is_keyboard_mode() {
+            return;
+        }
+        match self.previous_focus.take() {
+            Some(previous) => focus::set_focus(previous),
+            None => focus::clear_focus(self.focus_id),
+        }
+    }
+}
+
+impl Drop for MenuBar {
+    fn drop(&mut self) {
+        self.session.close_all();
+    }
+}
+
+impl Component for MenuBar {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let dismissed = self.session.dismissed.take();
+        let title = self.title_at(input.position);
+        self.hovered = title;
+        if let (Some(open), Some(title)) = (self.session.bar_open.get(), title)
+            && open != title
+        {
+            self.session.open_bar(title, false);
+        }
+        if input.pressed || input.secondary_pressed {
+            self.leave_keyboard_mode();
+        }
+        if input.pressed
+            && let Some(title) = title
+            && dismissed != Some(title)
+            && self.session.bar_open.get() != Some(title)
+        {
+            self.session.open_bar(title, false);
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        let modifiers = event::modifiers();
+        match &event.logical_key {
+            Key::Named(NamedKey::F10) if modifiers.is_empty() => {
+                if self.is_keyboard_mode() {
+                    self.leave_keyboard_mode();
+                } else {
+                    self.enter_keyboard_mode();
+                }
+            }
+            Key::Character(key) if modifiers.alt_key() && !modifiers.control_key() => {
+                if let Some(index) = self.title_with_mnemonic(key) {
+                    self.open(index);
+                }
+            }
+            _ if !self.is_keyboard_mode() => {}
+            Key::Named(NamedKey::ArrowLeft) => {
+                let count = self.titles.len();
+                self.keyboard_title = (self.keyboard_title + count - 1) % count;
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                self.keyboard_title = (self.keyboard_title + 1) % self.titles.len();
+            }
+            Key::Named(NamedKey::ArrowDown | NamedKey::Enter | NamedKey::Space) => {
+                self.open(self.keyboard_title);
+            }
+            Key::Named(NamedKey::Escape) => self.leave_keyboard_mode(),
+            Key::Character(key) if modifiers.is_empty() => {
+                if let Some(index) = self.title_with_mnemonic(key) {
+                    self.open(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let hovered = context.theme.style_for(&ComponentState::Hovered);
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width, self.size.height);
+        context.fill_rect(x, y, width, height, style.back_color.value);
+        let bottom = y + height as f64 - 1.0;
+        let border_color = style.border_color.value;
+        context.draw_line(x, bottom, x + width as f64, bottom, border_color);
+
+        let highlighted = self
+            .session
+            .bar_open
+            .get()
+            .or(self.hovered)
+            .or(self.is_keyboard_mode().then_some(self.keyboard_title));
+        let text_y = y + (height as f64 - context.line_height() as f64) / 2.0;
+        let bar = self.session.bar.borrow();
+        let bounds = Rectangle::new(x, y, width, height);
+        context.with_clip(&bounds, |context| {
+            for (index, ((text, mnemonic), (_, title))) in
+                self.titles.iter().zip(bar.iter()).enumerate()
+            {
+                let mut color = style.fore_color.value;
+                if highlighted == Some(index) {
+                    let (title_x, title_y) = (title.point.x, title.point.y);
+                    let (title_width, title_height) = (title.size.width, title.size.height);
+                    let back = hovered.back_color.value;
+                    context.fill_rect(title_x, title_y, title_width, title_height, back);
+                    color = hovered.fore_color.value;
+                }
+                let text_x = title.point.x + TITLE_PADDING;
+                context.draw_text(text, text_x, text_y, color);
+                if let Some(mnemonic) = *mnemonic {
+                    draw_mnemonic(context, text, mnemonic, text_x, text_y, color);
+                }
+            }
+        });
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,51 @@
+//!
+//! Structura: Text measurement.
+//!
+//! `Component`s that size themselves from their text, such as a `Label`, measure it here
+//! before they are drawn. `BufferContext::text_width()` and `BufferContext::line_height()`
+//! give the same results while drawing.
+//!
+
+use crate::component::load_font;
+use rusttype::{Font, Scale};
+
+///
+/// The font size used by the `Application` to draw text.
+///
+pub const FONT_SIZE: f32 = 32.0;
+
+thread_local! {
+    static FONT: Font<'static> = load_font();
+}
+
+///
+/// Returns the width in pixels of a single line of `text`, in `font` at `font_size`.
+///
+pub fn text_width_in(font: &Font, font_size: f32, text: &str) -> f32 {
+    let scale = Scale::uniform(font_size);
+    text.chars()
+        .map(|c| font.glyph(c).scaled(scale).h_metrics().advance_width)
+        .sum()
+}
+
+///
+/// Returns the height in pixels of a single line of text, in `font` at `font_size`.
+///
+pub fn line_height_in(font: &Font, font_size: f32) -> f32 {
+    let v_metrics = font.v_metrics(Scale::uniform(font_size));
+    (v_metrics.ascent - v_metrics.descent + v_metrics.line_gap).ceil()
+}
+
+///
+/// Returns the width in pixels of a single line of `text`, in the default font.
+///
+pub fn text_width(text: &str) -> f32 {
+    FONT.with(|font| text_width_in(font, FONT_SIZE, text))
+}
+
+///
+/// Returns the height in pixels of a single line of text, in the default font.
+///
+pub fn line_height() -> f32 {
+    FONT.with(|font| line_height_in(font, FONT_SIZE))
+}
@@ -3,19 +3,39 @@
 //!
 
 pub mod button;
+pub mod checkbox;
+pub mod combobox;
+pub mod dialog;
+pub mod filedialog;
+pub mod icon;
 pub mod label;
+pub mod list;
+pub mod menu;
+pub mod menubar;
+pub mod metrics;
+pub mod progress;
+pub mod radio;
+pub(crate) mod scrollbar;
+pub mod slider;
 pub mod style;
+pub mod styled;
+pub mod table;
 pub mod text;
 pub mod textarea;
-mod tooltip;
+pub mod textfield;
+pub mod tooltip;
+pub mod tree;
 
+use crate::container::Container;
 use crate::event::MouseInput;
 use crate::geometry::Point;
 use crate::geometry::Size;
 use crate::view::BufferContext;
 use rusttype::Font;
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Instant;
 use winit::event::MouseScrollDelta;
 use winit::event::{KeyEvent, TouchPhase};
 
@@ -59,6 +79,29 @@ pub trait Component {
 
     fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent);
 
+    ///
+    /// Called before each redraw while an animation frame is pending, with the frame time.
+    ///
+    /// A `Component` that animates advances its animation here, and calls
+    /// `crate::animation::request_frame()` to get another frame. Containers pass the call on
+    /// to their children.
+    ///
+    fn animate(&mut self, _now: Instant) {}
+
+    ///
+    /// Returns the state that the user has built up in the `Component`, such as edited text or
+    /// a scroll position, so that it can be carried over when the UI is rebuilt, e.g. by
+    /// `crate::reload`. Returns `None` when there is nothing worth keeping.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        None
+    }
+
+    ///
+    /// Restore state returned by `save_state()` on an earlier instance of the same `Component`.
+    ///
+    fn restore_state(&mut self, _state: Box<dyn Any>) {}
+
     ///
     /// Called whenever the application updates it's state, or a redraw is requested in response to an event.
     ///
@@ -85,6 +128,56 @@ pub trait Component {
     fn get_size(&self) -> Size;
 }
 
+///
+/// A boxed `Component` is a `Component`, so that wrappers such as `Styled` and
+/// `ComponentHandle` can hold trait objects.
+///
+impl<T: Component + ?Sized> Component for Box<T> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        (**self).handle_mouse_event(input);
+    }
+
+    fn handle_mouse_wheel_event(&mut self, event: &MouseScrollDelta, phase: &TouchPhase) {
+        (**self).handle_mouse_wheel_event(event, phase);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &KeyEvent) {
+        (**self).handle_keyboard_event(event);
+    }
+
+    fn animate(&mut self, now: Instant) {
+        (**self).animate(now);
+    }
+
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        (**self).save_state()
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        (**self).restore_state(state);
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        (**self).draw(context);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        (**self).set_position(x, y);
+    }
+
+    fn get_position(&self) -> Point {
+        (**self).get_position()
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        (**self).set_size(width, height);
+    }
+
+    fn get_size(&self) -> Size {
+        (**self).get_size()
+    }
+}
+
 ///
 /// `Rc<RefCell<T>>` wrapper for structs that implement the `Component` trait.
 ///
@@ -117,6 +210,18 @@ impl<T: Component> Component for ComponentHandle<T> {
         self.inner.borrow_mut().handle_keyboard_event(event);
     }
 
+    fn animate(&mut self, now: Instant) {
+        self.inner.borrow_mut().animate(now);
+    }
+
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        self.inner.borrow().save_state()
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        self.inner.borrow_mut().restore_state(state);
+    }
+
     fn draw(&self, context: &mut BufferContext) {
         self.inner.borrow().draw(context);
     }
@@ -137,3 +242,21 @@ impl<T: Component> Component for ComponentHandle<T> {
         self.inner.borrow().get_size()
     }
 }
+
+impl<T: Component + Container> Container for ComponentHandle<T> {
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.inner.borrow_mut().push(component);
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        self.inner.borrow_mut().resize(width, height);
+    }
+
+    fn update_layout(&mut self) {
+        self.inner.borrow_mut().update_layout();
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        self.inner.borrow().fills_parent_container()
+    }
+}
@@ -0,0 +1,350 @@
+//!
+//! # Structura Component: ProgressBar.
+//!
+//! A bar that fills as a long operation progresses, or that animates to show that something
+//! is happening when the amount of work is unknown.
+//!
+//! Background work updates the bar through a `ProgressHandle`, which can be sent to other
+//! threads:
+//!
+//! ```ignore
+//! let progress = ProgressBar::new().max(files.len() as f64).show_text(true);
+//! let handle = progress.handle();
+//! std::thread::spawn(move || {
+//!     for (i, file) in files.iter().enumerate() {
+//!         copy(file);
+//!         handle.set_value(i as f64 + 1.0);
+//!     }
+//! });
+//! ```
+//!
+//! The handle stores each update in the state it shares with the bar, so it can be read back
+//! at once, and asks the UI loop to redraw the bar with `crate::dispatch::request_redraw()`.
+//! In indeterminate mode, the bar requests animation frames from the `Application` while it
+//! animates, rather than redrawing on every event.
+//!
+
+use crate::animation;
+use crate::component::{Component, ComponentState};
+use crate::dispatch;
+use crate::event::MouseInput;
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time for the indeterminate block to sweep across the bar.
+const SWEEP_DURATION: Duration = Duration::from_millis(1500);
+
+/// Width of the indeterminate block, as a fraction of the bar.
+const BLOCK_FRACTION: f64 = 0.3;
+
+/// Formats the text shown over the bar, from the value and maximum.
+type TextFormat = Box<dyn Fn(f64, f64) -> String>;
+
+///
+/// The progress shared between a `ProgressBar` and its `ProgressHandle`s.
+///
+#[derive(Debug, Clone, Copy)]
+struct Progress {
+    value: f64,
+    max: f64,
+    indeterminate: bool,
+}
+
+impl Progress {
+    ///
+    /// Returns how complete the work is, from `0.0` to `1.0`.
+    ///
+    fn fraction(&self) -> f64 {
+        if self.max > 0.0 {
+            (self.value / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+///
+/// A handle to update a `ProgressBar` from any thread. Cloning a `ProgressHandle` gives
+/// another handle to the same bar.
+///
+#[derive(Clone)]
+pub struct ProgressHandle {
+    progress: Arc<Mutex<Progress>>,
+}
+
+impl ProgressHandle {
+    ///
+    /// Set the value, from `0.0` to the maximum, and switch to determinate mode.
+    ///
+    pub fn set_value(&self, value: f64) {
+        self.update(move |progress| {
+            progress.value = value;
+            progress.indeterminate = false;
+        });
+    }
+
+    pub fn set_max(&self, max: f64) {
+        self.update(move |progress| progress.max = max);
+    }
+
+    ///
+    /// Switch between indeterminate and determinate mode.
+    ///
+    pub fn set_indeterminate(&self, indeterminate: bool) {
+        self.update(move |progress| progress.indeterminate = indeterminate);
+    }
+
+    ///
+    /// Returns the value, as last set through any handle or the `ProgressBar`.
+    ///
+    pub fn value(&self) -> f64 {
+        self.progress.lock().unwrap().value
+    }
+
+    ///
+    /// Apply `change` to the shared state, and ask the UI loop to redraw the bar, or to
+    /// animate it in indeterminate mode.
+    ///
+    fn update<F: FnOnce(&mut Progress)>(&self, change: F) {
+        let indeterminate = {
+            let mut progress = self.progress.lock().unwrap();
+            change(&mut progress);
+            progress.indeterminate
+        };
+        if indeterminate {
+            dispatch::post(animation::request_frame);
+        } else {
+            dispatch::request_redraw();
+        }
+    }
+}
+
+///
+/// ProgressBar control showing the progress of a long operation.
+///
+pub struct ProgressBar {
+    position: Point,
+    size: Size,
+    progress: Arc<Mutex<Progress>>,
+    show_text: bool,
+    text_format: TextFormat,
+
+    /// Position of the indeterminate block, from `0.0` to `1.0` of its sweep.
+    phase: f64,
+    started: Option<Instant>,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressBar {
+    ///
+    /// Constructor, for a determinate bar from `0` to `100`, at `0`.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 300,
+                height: 32,
+            },
+            progress: Arc::new(Mutex::new(Progress {
+                value: 0.0,
+                max: 100.0,
+                indeterminate: false,
+            })),
+            show_text: false,
+            text_format: Box::new(|value, max| {
+                let percent = if max > 0.0 { value / max * 100.0 } else { 0.0 };
+                format!("{:.0}%", percent.clamp(0.0, 100.0))
+            }),
+            phase: 0.0,
+            started: None,
+        }
+    }
+
+    ///
+    /// Set the value that means the work is complete.
+    ///
+    pub fn max(self, max: f64) -> Self {
+        self.set_max(max);
+        self
+    }
+
+    ///
+    /// Set the initial value.
+    ///
+    pub fn value(self, value: f64) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    ///
+    /// Start in indeterminate mode, animating until a value is set.
+    ///
+    pub fn indeterminate(self, indeterminate: bool) -> Self {
+        self.set_indeterminate(indeterminate);
+        self
+    }
+
+    ///
+    /// Show the progress as text over the bar, e.g. `"42%"`. Not shown in indeterminate mode.
+    ///
+    pub fn show_text(mut self, show_text: bool) -> Self {
+        self.show_text = show_text;
+        self
+    }
+
+    ///
+    /// Format the text from the value and maximum, e.g. `|value, max| format!("{value} of
+    /// {max}")`. Implies `show_text(true)`.
+    ///
+    pub fn text_format<F: Fn(f64, f64) -> String + 'static>(mut self, f: F) -> Self {
+        self.text_format = Box::new(f);
+        self.show_text = true;
+        self
+    }
+
+    ///
+    /// Returns a handle to update the bar, from any thread.
+    ///
+    pub fn handle(&self) -> ProgressHandle {
+        ProgressHandle {
+            progress: self.progress.clone(),
+        }
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.progress().value
+    }
+
+    pub fn get_max(&self) -> f64 {
+        self.progress().max
+    }
+
+    pub fn is_indeterminate(&self) -> bool {
+        self.progress().indeterminate
+    }
+
+    ///
+    /// Set the value, from `0.0` to the maximum, and switch to determinate mode.
+    ///
+    pub fn set_value(&self, value: f64) {
+        let mut progress = self.progress.lock().unwrap();
+        progress.value = value;
+        progress.indeterminate = false;
+    }
+
+    pub fn set_max(&self, max: f64) {
+        self.progress.lock().unwrap().max = max;
+    }
+
+    ///
+    /// Switch between indeterminate and determinate mode.
+    ///
+    pub fn set_indeterminate(&self, indeterminate: bool) {
+        self.progress.lock().unwrap().indeterminate = indeterminate;
+        if indeterminate {
+            animation::request_frame();
+        }
+    }
+
+    fn progress(&self) -> Progress {
+        *self.progress.lock().unwrap()
+    }
+}
+
+impl Component for ProgressBar {
+    fn handle_mouse_event(&mut self, _input: MouseInput) {}
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, _event: &winit::event::KeyEvent) {}
+
+    ///
+    /// Sweeps the indeterminate block, requesting frames for as long as the bar is
+    /// indeterminate.
+    ///
+    fn animate(&mut self, now: Instant) {
+        if !self.is_indeterminate() {
+            self.started = None;
+            return;
+        }
+        let started = *self.started.get_or_insert(now);
+        let elapsed = now.duration_since(started).as_secs_f64();
+        self.phase = (elapsed / SWEEP_DURATION.as_secs_f64()).fract();
+        animation::request_frame();
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let progress = self.progress();
+        let (x, y) = (self.position.x, self.position.y);
+        let (w, h) = (self.size.width, self.size.height);
+        let bw = style.border_width as u32;
+        context.fill_rect(x, y, w, h, style.edit_back_color.value);
+
+        let inner = Rectangle::new(
+            x + bw as f64,
+            y + bw as f64,
+            w.saturating_sub(bw * 2),
+            h.saturating_sub(bw * 2),
+        );
+        let inner_width = inner.size.width as f64;
+        context.with_clip(&inner, |context| {
+            let (from, width) = if progress.indeterminate {
+                //
+                // The block enters at the left edge and leaves at the right edge.
+                //
+                let block = inner_width * BLOCK_FRACTION;
+                (self.phase * (inner_width + block) - block, block)
+            } else {
+                (0.0, inner_width * progress.fraction())
+            };
+            context.fill_rect(
+                inner.point.x + from,
+                inner.point.y,
+                width.round() as u32,
+                inner.size.height,
+                style.cursor_color.value,
+            );
+
+            if self.show_text && !progress.indeterminate {
+                let text = (self.text_format)(progress.value, progress.max);
+                let text_x = x + (w as f64 - context.text_width(&text) as f64) / 2.0;
+                let text_y = y + (h as f64 - context.line_height() as f64) / 2.0;
+                context.draw_text(&text, text_x, text_y, style.edit_text_color.value);
+            }
+        });
+        context.draw_border(x, y, w, h, style.border_width, style.border_color.value);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,408 @@
+//!
+//! # Structura Component: RadioButton.
+//!
+//! A round button with a label, one of a `RadioGroup` in which at most one is selected.
+//!
+//! ```ignore
+//! let size = RadioGroup::new().on_change(|index| println!("size {index}"));
+//! let small = RadioButton::new("Small", &size);
+//! let large = RadioButton::new("Large", &size).selected(true);
+//! ```
+//!
+//! - Clicking the button or its label selects it, as does `Space` while it has focus.
+//! - The arrow keys select the previous or next enabled button in the group, and move the
+//!   focus with the selection.
+//! - It is drawn with the theme's style for its state: hovered, pressed, focused or disabled.
+//!
+
+use crate::component::checkbox::{display_state, draw_label, fit_size, indicator_bounds};
+use crate::component::{Component, ComponentState};
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use winit::event::ElementState;
+use winit::keyboard::{Key, NamedKey};
+
+type Handler<T> = Rc<RefCell<dyn FnMut(T)>>;
+
+///
+/// A `RadioButton` in a group: how to reach it, and whom to tell when it changes.
+///
+struct Member {
+    focus_id: FocusId,
+    enabled: bool,
+    on_change: Option<Handler<bool>>,
+}
+
+struct GroupState {
+    members: RefCell<Vec<Member>>,
+    selected: Cell<Option<usize>>,
+    on_change: RefCell<Option<Handler<usize>>>,
+}
+
+///
+/// A set of `RadioButton`s in which at most one is selected. Cloning a `RadioGroup` gives
+/// another handle to the same group.
+///
+#[derive(Clone)]
+pub struct RadioGroup {
+    state: Rc<GroupState>,
+}
+
+impl Default for RadioGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RadioGroup {
+    ///
+    /// Constructor, for an empty group with nothing selected.
+    ///
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(GroupState {
+                members: RefCell::new(Vec::new()),
+                selected: Cell::new(None),
+                on_change: RefCell::new(None),
+            }),
+        }
+    }
+
+    ///
+    /// Add event handler, called with the index of the newly selected `RadioButton` when the
+    /// user changes the selection.
+    ///
+    pub fn on_change<F: FnMut(usize) + 'static>(self, f: F) -> Self {
+        *self.state.on_change.borrow_mut() = Some(Rc::new(RefCell::new(f)));
+        self
+    }
+
+    ///
+    /// Returns the index of the selected `RadioButton`, in the order they joined the group.
+    ///
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected.get()
+    }
+
+    ///
+    /// Select the `RadioButton` at `index`, or none. Does not call the `on_change` handlers.
+    ///
+    pub fn set_selected(&self, index: Option<usize>) {
+        let count = self.state.members.borrow().len();
+        self.state
+            .selected
+            .set(index.filter(|&index| index < count));
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.members.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn join(&self, focus_id: FocusId) -> usize {
+        let mut members = self.state.members.borrow_mut();
+        members.push(Member {
+            focus_id,
+            enabled: true,
+            on_change: None,
+        });
+        members.len() - 1
+    }
+
+    ///
+    /// Select the `RadioButton` at `index` on behalf of the user, calling the `on_change`
+    /// handlers of the buttons that changed and of the group.
+    ///
+    fn select(&self, index: usize) {
+        let previous = self.state.selected.replace(Some(index));
+        if previous == Some(index) {
+            return;
+        }
+        //
+        // Collect the handlers first, so that they are free to use the group.
+        //
+        let (deselected, selected) = {
+            let members = self.state.members.borrow();
+            let handler = |index: usize| members.get(index).and_then(|m| m.on_change.clone());
+            (previous.and_then(handler), handler(index))
+        };
+        if let Some(handler) = deselected {
+            (handler.borrow_mut())(false);
+        }
+        if let Some(handler) = selected {
+            (handler.borrow_mut())(true);
+        }
+        let handler = self.state.on_change.borrow().clone();
+        if let Some(handler) = handler {
+            (handler.borrow_mut())(index);
+        }
+    }
+
+    ///
+    /// Select the next enabled `RadioButton` after `index` in the direction of `step`,
+    /// wrapping around, and give it focus.
+    ///
+    fn select_adjacent(&self, index: usize, step: isize) {
+        let target = {
+            let members = self.state.members.borrow();
+            let count = members.len() as isize;
+            (1..count)
+                .map(|offset| (index as isize + step * offset).rem_euclid(count) as usize)
+                .find(|&i| members[i].enabled)
+                .map(|i| (i, members[i].focus_id))
+        };
+        if let Some((target, focus_id)) = target {
+            focus::set_focus(focus_id);
+            self.select(target);
+        }
+    }
+}
+
+///
+/// RadioButton control with a label.
+///
+pub struct RadioButton {
+    position: Point,
+    size: Size,
+    text: String,
+    group: RadioGroup,
+    index: usize,
+    enabled: bool,
+    pressed: bool,
+    focus_id: FocusId,
+    component_state: ComponentState,
+}
+
+impl RadioButton {
+    ///
+    /// Constructor, sized to fit the label `text`. The button joins `group` after the
+    /// buttons already in it.
+    ///
+    pub fn new(text: &str, group: &RadioGroup) -> Self {
+        let focus_id = FocusId::new();
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: fit_size(text),
+            text: text.to_string(),
+            group: group.clone(),
+            index: group.join(focus_id),
+            enabled: true,
+            pressed: false,
+            focus_id,
+            component_state: ComponentState::Active,
+        }
+    }
+
+    ///
+    /// Select this button initially, deselecting the others in the group.
+    ///
+    pub fn selected(self, selected: bool) -> Self {
+        if selected {
+            self.group.set_selected(Some(self.index));
+        } else if self.is_selected() {
+            self.group.set_selected(None);
+        }
+        self
+    }
+
+    ///
+    /// Enable or disable the `RadioButton`. A disabled `RadioButton` ignores input, and is
+    /// skipped by the arrow keys.
+    ///
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    ///
+    /// Add event handler, called with true when the user selects this button, and with false
+    /// when the user selects another button in the group.
+    ///
+    pub fn on_change<F: FnMut(bool) + 'static>(self, f: F) -> Self {
+        self.group.state.members.borrow_mut()[self.index].on_change =
+            Some(Rc::new(RefCell::new(f)));
+        self
+    }
+
+    pub fn is_selected(&self) -> bool {
+        self.group.selected() == Some(self.index)
+    }
+
+    ///
+    /// Returns the index of this button in its `RadioGroup`.
+    ///
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn group(&self) -> &RadioGroup {
+        &self.group
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.pressed = false;
+        self.group.state.members.borrow_mut()[self.index].enabled = enabled;
+        self.component_state = if enabled {
+            ComponentState::Active
+        } else {
+            focus::clear_focus(self.focus_id);
+            ComponentState::Disabled
+        };
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    ///
+    /// Returns the `FocusId` of the `RadioButton`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+}
+
+impl Component for RadioButton {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if !self.enabled {
+            return;
+        }
+        let inside = self.bounds().contains(input.position.x, input.position.y);
+        if input.pressed {
+            if inside {
+                focus::set_focus(self.focus_id);
+                self.pressed = true;
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+        }
+        if input.just_released {
+            if self.pressed && inside {
+                self.group.select(self.index);
+            }
+            self.pressed = false;
+        }
+        self.component_state = match (inside, self.pressed) {
+            (true, true) => ComponentState::Pressed,
+            (true, false) => ComponentState::Hovered,
+            _ => ComponentState::Active,
+        };
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if !self.enabled
+            || !focus::is_focused(self.focus_id)
+            || event.state != ElementState::Pressed
+        {
+            return;
+        }
+        match event.logical_key {
+            Key::Named(NamedKey::Space) if !event.repeat => self.group.select(self.index),
+            Key::Named(NamedKey::ArrowUp | NamedKey::ArrowLeft) => {
+                self.group.select_adjacent(self.index, -1)
+            }
+            Key::Named(NamedKey::ArrowDown | NamedKey::ArrowRight) => {
+                self.group.select_adjacent(self.index, 1)
+            }
+            _ => {}
+        }
+    }
+
+    ///
+    /// Keeps whether this button is selected.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        self.is_selected().then(|| Box::new(true) as Box<dyn Any>)
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if state.downcast::<bool>().is_ok_and(|selected| *selected) {
+            self.group.set_selected(Some(self.index));
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = focus::is_focused(self.focus_id);
+        let style = context
+            .theme
+            .style_for(&display_state(&self.component_state, focused));
+        let indicator = indicator_bounds(self.position, &self.size);
+        let radius = indicator.size.width as f64 / 2.0;
+        let center = Point {
+            x: indicator.point.x + radius,
+            y: indicator.point.y + radius,
+        };
+        let border = style.border_width.max(1) as f64;
+        context.fill_circle(center.x, center.y, radius, style.border_color.value);
+        context.fill_circle(
+            center.x,
+            center.y,
+            radius - border,
+            style.edit_back_color.value,
+        );
+        if self.is_selected() {
+            context.fill_circle(
+                center.x,
+                center.y,
+                radius * 0.45,
+                style.edit_text_color.value,
+            );
+        }
+        draw_label(
+            context,
+            &self.text,
+            self.position,
+            &self.size,
+            &style,
+            focused,
+        );
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,273 @@
+//!
+//! # Structura: ScrollBar.
+//!
+//! The scrollbar of the virtualized components, e.g. `ListView` and `TreeView`.
+//!
+//! These components draw only their visible part, so rather than being wrapped in a
+//! `ScrollView`, they keep a `ScrollBar` for each scrolling direction. The `ScrollBar` holds
+//! the smooth scroll offset, and handles the mouse on its track and thumb. The owner sets the
+//! lengths of its content and viewport, and the bounds of the track, whenever they change.
+//!
+//! `thumb_extent()` and `offset_for_thumb()` hold the thumb geometry, which `ScrollView` uses
+//! for its own scrollbars too.
+//!
+
+use crate::animation::Tween;
+use crate::component::ComponentState;
+use crate::component::style::ColorFactor;
+use crate::event::MouseInput;
+use crate::geometry::{Orientation, Rectangle};
+use crate::view::BufferContext;
+use std::time::{Duration, Instant};
+
+/// Width of a vertical scrollbar, and height of a horizontal one.
+pub(crate) const SCROLLBAR_WIDTH: u32 = 12;
+
+/// The smallest length of the thumb, so that it stays easy to grab.
+const MIN_THUMB_LENGTH: f64 = 20.0;
+
+/// Time taken by a smooth scroll.
+const SCROLL_DURATION: Duration = Duration::from_millis(120);
+
+pub(crate) struct ScrollBar {
+    orientation: Orientation,
+
+    /// Scroll offset, in pixels from the start of the content.
+    offset: Tween,
+    content: f64,
+    viewport: f64,
+    track: Rectangle,
+
+    /// While dragging the thumb, the distance from its start to the cursor.
+    drag: Option<f64>,
+}
+
+impl ScrollBar {
+    pub(crate) fn new(orientation: Orientation) -> Self {
+        Self {
+            orientation,
+            offset: Tween::new(0.0, SCROLL_DURATION),
+            content: 0.0,
+            viewport: 0.0,
+            track: Rectangle::new(0.0, 0.0, 0, 0),
+            drag: None,
+        }
+    }
+
+    ///
+    /// Set the length of the content, and of the part of it that is visible. Keeps the offset
+    /// within the content.
+    ///
+    pub(crate) fn set_lengths(&mut self, content: f64, viewport: f64) {
+        self.content = content;
+        self.viewport = viewport;
+        if self.offset.target() > self.max_offset() {
+            self.offset.set(self.max_offset());
+        }
+    }
+
+    pub(crate) fn set_track(&mut self, track: Rectangle) {
+        self.track = track;
+    }
+
+    ///
+    /// Returns true if the content is longer than the viewport, so the scrollbar is needed.
+    ///
+    pub(crate) fn is_shown(&self) -> bool {
+        self.content > self.viewport
+    }
+
+    pub(crate) fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    pub(crate) fn max_offset(&self) -> f64 {
+        (self.content - self.viewport).max(0.0)
+    }
+
+    ///
+    /// Returns the current offset, which moves towards `target()` while scrolling smoothly.
+    ///
+    pub(crate) fn offset(&self) -> f64 {
+        self.offset.value().clamp(0.0, self.max_offset())
+    }
+
+    pub(crate) fn target(&self) -> f64 {
+        self.offset.target()
+    }
+
+    ///
+    /// Scroll smoothly to `offset`.
+    ///
+    pub(crate) fn scroll_to(&mut self, offset: f64) {
+        self.offset.animate_to(offset.clamp(0.0, self.max_offset()));
+    }
+
+    pub(crate) fn scroll_by(&mut self, delta: f64) {
+        self.scroll_to(self.target() + delta);
+    }
+
+    ///
+    /// Jump straight to `offset`.
+    ///
+    pub(crate) fn set_offset(&mut self, offset: f64) {
+        self.offset.set(offset.clamp(0.0, self.max_offset()));
+    }
+
+    ///
+    /// Scroll as little as possible for the content from `start` to `end` to be visible.
+    ///
+    pub(crate) fn scroll_into_view(&mut self, start: f64, end: f64) {
+        let target = self.target();
+        if start < target {
+            self.scroll_to(start);
+        } else if end > target + self.viewport {
+            self.scroll_to(end - self.viewport);
+        }
+    }
+
+    ///
+    /// Advance a smooth scroll. Returns true if the offset changed.
+    ///
+    pub(crate) fn animate(&mut self, now: Instant) -> bool {
+        self.offset.update(now)
+    }
+
+    pub(crate) fn contains(&self, x: f64, y: f64) -> bool {
+        self.is_shown() && self.track.contains(x, y)
+    }
+
+    ///
+    /// Handle a press on the track or thumb, and dragging of the thumb. Returns true if the
+    /// event was for the `ScrollBar`.
+    ///
+    pub(crate) fn handle_mouse_event(&mut self, input: &MouseInput) -> bool {
+        let (x, y) = (input.position.x, input.position.y);
+        let position = self.along(x, y);
+        let mut consumed = false;
+        if input.pressed && self.contains(x, y) {
+            let (start, length) = self.thumb();
+            if (start..start + length).contains(&position) {
+                self.drag = Some(position - start);
+            } else if position < start {
+                self.scroll_by(-self.viewport);
+            } else {
+                self.scroll_by(self.viewport);
+            }
+            consumed = true;
+        } else if let Some(grab) = self.drag {
+            if !input.is_elsewhere() {
+                let (_, length) = self.thumb();
+                self.set_offset(offset_for_thumb(
+                    position - grab,
+                    length,
+                    self.track_start(),
+                    self.track_length(),
+                    self.max_offset(),
+                ));
+            }
+            consumed = true;
+        }
+        if input.just_released {
+            self.drag = None;
+        }
+        consumed
+    }
+
+    pub(crate) fn draw(&self, context: &mut BufferContext) {
+        if !self.is_shown() {
+            return;
+        }
+        let style = context.theme.style_for(&ComponentState::Active);
+        let track_color = style.back_color.lighten(ColorFactor::double()).value;
+        let thumb_state = if self.is_dragging() {
+            ComponentState::Pressed
+        } else {
+            ComponentState::Active
+        };
+        let thumb_color = context.theme.style_for(&thumb_state).back_color.value;
+        let track = &self.track;
+        let (x, y) = (track.point.x, track.point.y);
+        context.fill_rect(x, y, track.size.width, track.size.height, track_color);
+        let (start, length) = self.thumb();
+        match self.orientation {
+            Orientation::Vertical => {
+                context.fill_rect(x, start, track.size.width, length as u32, thumb_color)
+            }
+            Orientation::Horizontal => {
+                context.fill_rect(start, y, length as u32, track.size.height, thumb_color)
+            }
+        }
+    }
+
+    ///
+    /// Returns the position of `(x, y)` along the scrollbar.
+    ///
+    fn along(&self, x: f64, y: f64) -> f64 {
+        match self.orientation {
+            Orientation::Vertical => y,
+            Orientation::Horizontal => x,
+        }
+    }
+
+    fn track_start(&self) -> f64 {
+        self.along(self.track.point.x, self.track.point.y)
+    }
+
+    fn track_length(&self) -> f64 {
+        match self.orientation {
+            Orientation::Vertical => self.track.size.height as f64,
+            Orientation::Horizontal => self.track.size.width as f64,
+        }
+    }
+
+    ///
+    /// Returns the `(start, length)` of the thumb.
+    ///
+    fn thumb(&self) -> (f64, f64) {
+        thumb_extent(
+            self.track_start(),
+            self.track_length(),
+            self.content,
+            self.viewport,
+            self.offset(),
+        )
+    }
+}
+
+///
+/// Returns the `(start, length)` of the thumb on a track from `track_start`, `track_length`
+/// long, showing `viewport` of `content` scrolled to `offset`.
+///
+pub(crate) fn thumb_extent(
+    track_start: f64,
+    track_length: f64,
+    content: f64,
+    viewport: f64,
+    offset: f64,
+) -> (f64, f64) {
+    let length = (track_length * viewport / content.max(1.0))
+        .clamp(MIN_THUMB_LENGTH.min(track_length), track_length);
+    let max_offset = (content - viewport).max(0.0);
+    let ratio = if max_offset > 0.0 {
+        offset / max_offset
+    } else {
+        0.0
+    };
+    (track_start + ratio * (track_length - length), length)
+}
+
+///
+/// Returns the offset, up to `max_offset`, that puts a thumb `thumb_length` long at
+/// `thumb_start` on the track, e.g. while it is dragged.
+///
+pub(crate) fn offset_for_thumb(
+    thumb_start: f64,
+    thumb_length: f64,
+    track_start: f64,
+    track_length: f64,
+    max_offset: f64,
+) -> f64 {
+    let travel = (track_length - thumb_length).max(1.0);
+    (thumb_start - track_start) / travel * max_offset
+}
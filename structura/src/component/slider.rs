@@ -0,0 +1,943 @@
+//!
+//! # Structura Component: Slider.
+//!
+//! A thumb dragged along a track to pick a value, and `RangeSlider`, with two thumbs to pick
+//! a range.
+//!
+//! ```ignore
+//! let volume = Slider::new()
+//!     .range(0.0, 100.0)
+//!     .step(5.0)
+//!     .ticks(25.0)
+//!     .tick_labels(true)
+//!     .on_change(|value| println!("volume {value}"));
+//! ```
+//!
+//! - Horizontal sliders increase to the right, vertical sliders increase upwards.
+//! - Pressing the track moves the nearest thumb there. Dragging keeps going when the cursor
+//!   leaves the `Component`, until the button is released.
+//! - With focus, the arrow keys move by one step, `PageUp` / `PageDown` by a page, and
+//!   `Home` / `End` go to the ends. In a `RangeSlider`, the keys move the thumb last pressed.
+//! - The mouse wheel moves by one step while the cursor is over the `Component`.
+//! - Values are snapped to the steps from `min`. A step of `0.0` allows any value.
+//!
+
+use crate::component::checkbox::display_state;
+use crate::component::metrics;
+use crate::component::style::ComponentStyle;
+use crate::component::{Component, ComponentState};
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Orientation, Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use winit::event::{ElementState, MouseScrollDelta};
+use winit::keyboard::{Key, NamedKey};
+
+/// Radius of a thumb.
+const THUMB_RADIUS: f64 = 10.0;
+
+/// Thickness of the track.
+const TRACK_THICKNESS: u32 = 4;
+
+/// Space around the thumbs, ticks and labels.
+const PADDING: f64 = 4.0;
+
+/// Length of a tick mark.
+const TICK_LENGTH: f64 = 6.0;
+
+/// Default length of a slider along its track.
+const DEFAULT_LENGTH: u32 = 300;
+
+/// Number of keyboard or wheel moves from `min` to `max` when there is no step.
+const CONTINUOUS_MOVES: f64 = 100.0;
+
+/// Formats the tick labels.
+type LabelFormat = Box<dyn Fn(f64) -> String>;
+
+///
+/// The value range and geometry shared by `Slider` and `RangeSlider`.
+///
+struct Track {
+    position: Point,
+    size: Size,
+    orientation: Orientation,
+    min: f64,
+    max: f64,
+    step: f64,
+    page_step: Option<f64>,
+    tick_interval: Option<f64>,
+    tick_labels: bool,
+    label_format: LabelFormat,
+}
+
+impl Track {
+    fn new() -> Self {
+        let mut track = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            orientation: Orientation::Horizontal,
+            min: 0.0,
+            max: 100.0,
+            step: 1.0,
+            page_step: None,
+            tick_interval: None,
+            tick_labels: false,
+            label_format: Box::new(format_value),
+        };
+        track.fit();
+        track
+    }
+
+    ///
+    /// Size the slider for its orientation, ticks and labels, keeping its length.
+    ///
+    fn fit(&mut self) {
+        let mut depth = PADDING * 2.0 + THUMB_RADIUS * 2.0;
+        if self.tick_interval.is_some() {
+            depth += TICK_LENGTH + PADDING;
+        }
+        let length = match self.orientation {
+            Orientation::Horizontal => self.size.width,
+            Orientation::Vertical => self.size.height,
+        };
+        let length = if length == 0 { DEFAULT_LENGTH } else { length };
+        self.size = match self.orientation {
+            Orientation::Horizontal => {
+                if self.tick_labels {
+                    depth += metrics::line_height() as f64;
+                }
+                Size {
+                    width: length,
+                    height: depth.ceil() as u32,
+                }
+            }
+            Orientation::Vertical => {
+                if self.tick_labels {
+                    let widest = self
+                        .tick_values()
+                        .map(|value| metrics::text_width(&(self.label_format)(value)) as f64)
+                        .fold(0.0, f64::max);
+                    depth += widest + PADDING;
+                }
+                Size {
+                    width: depth.ceil() as u32,
+                    height: length,
+                }
+            }
+        };
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    fn set_range(&mut self, min: f64, max: f64) {
+        (self.min, self.max) = if min <= max { (min, max) } else { (max, min) };
+    }
+
+    ///
+    /// Clamp `value` to the range, and snap it to the nearest step.
+    ///
+    fn snap(&self, value: f64) -> f64 {
+        let value = if self.step > 0.0 {
+            self.min + ((value - self.min) / self.step).round() * self.step
+        } else {
+            value
+        };
+        value.clamp(self.min, self.max)
+    }
+
+    ///
+    /// Returns the amount moved by an arrow key or a wheel notch.
+    ///
+    fn small_step(&self) -> f64 {
+        if self.step > 0.0 {
+            self.step
+        } else {
+            (self.max - self.min) / CONTINUOUS_MOVES
+        }
+    }
+
+    fn large_step(&self) -> f64 {
+        self.page_step
+            .unwrap_or(((self.max - self.min) / 10.0).max(self.small_step()))
+    }
+
+    ///
+    /// Returns the coordinate of `point` along the track.
+    ///
+    fn along(&self, point: Point) -> f64 {
+        match self.orientation {
+            Orientation::Horizontal => point.x,
+            Orientation::Vertical => point.y,
+        }
+    }
+
+    ///
+    /// Returns the coordinates along the track of `min` and `max`.
+    ///
+    fn ends(&self) -> (f64, f64) {
+        let inset = PADDING + THUMB_RADIUS;
+        match self.orientation {
+            Orientation::Horizontal => (
+                self.position.x + inset,
+                self.position.x + self.size.width as f64 - inset,
+            ),
+            Orientation::Vertical => (
+                self.position.y + self.size.height as f64 - inset,
+                self.position.y + inset,
+            ),
+        }
+    }
+
+    ///
+    /// Returns the coordinate across the track of its center line.
+    ///
+    fn center_line(&self) -> f64 {
+        match self.orientation {
+            Orientation::Horizontal => self.position.y + PADDING + THUMB_RADIUS,
+            Orientation::Vertical => self.position.x + PADDING + THUMB_RADIUS,
+        }
+    }
+
+    fn offset_of(&self, value: f64) -> f64 {
+        let (start, end) = self.ends();
+        let span = self.max - self.min;
+        let t = if span > 0.0 {
+            (value - self.min) / span
+        } else {
+            0.0
+        };
+        start + t * (end - start)
+    }
+
+    ///
+    /// Returns the snapped value at the coordinate `along` the track.
+    ///
+    fn value_at(&self, along: f64) -> f64 {
+        let (start, end) = self.ends();
+        let t = if end != start {
+            ((along - start) / (end - start)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.snap(self.min + t * (self.max - self.min))
+    }
+
+    fn thumb_center(&self, value: f64) -> Point {
+        let (along, across) = (self.offset_of(value), self.center_line());
+        match self.orientation {
+            Orientation::Horizontal => Point {
+                x: along,
+                y: across,
+            },
+            Orientation::Vertical => Point {
+                x: across,
+                y: along,
+            },
+        }
+    }
+
+    ///
+    /// Returns the new value after pressing `key`, or `None` if the key does not move it.
+    ///
+    fn key_value(&self, value: f64, key: &Key) -> Option<f64> {
+        let value = match key {
+            Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => value - self.small_step(),
+            Key::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => value + self.small_step(),
+            Key::Named(NamedKey::PageDown) => value - self.large_step(),
+            Key::Named(NamedKey::PageUp) => value + self.large_step(),
+            Key::Named(NamedKey::Home) => self.min,
+            Key::Named(NamedKey::End) => self.max,
+            _ => return None,
+        };
+        Some(self.snap(value))
+    }
+
+    ///
+    /// Returns the new value after scrolling the mouse wheel: one step per notch, up or
+    /// right to increase.
+    ///
+    fn wheel_value(&self, value: f64, delta: &MouseScrollDelta) -> f64 {
+        let notches = match delta {
+            MouseScrollDelta::LineDelta(x, y) => {
+                if *y != 0.0 {
+                    *y as f64
+                } else {
+                    *x as f64
+                }
+            }
+            MouseScrollDelta::PixelDelta(p) => {
+                if p.y != 0.0 {
+                    p.y.signum()
+                } else {
+                    p.x.signum()
+                }
+            }
+        };
+        self.snap(value + notches * self.small_step())
+    }
+
+    fn tick_values(&self) -> impl Iterator<Item = f64> + '_ {
+        let interval = self.tick_interval.filter(|interval| *interval > 0.0);
+        let count = interval
+            .map(|interval| ((self.max - self.min) / interval + 1e-9).floor() as usize + 1)
+            .unwrap_or(0);
+        (0..count).map(move |i| self.min + i as f64 * interval.unwrap_or(0.0))
+    }
+
+    ///
+    /// Draw the track, highlighted between the values `from` and `to`, with its ticks and
+    /// labels.
+    ///
+    fn draw(&self, context: &mut BufferContext, style: &ComponentStyle, from: f64, to: f64) {
+        let (start, end) = self.ends();
+        let across = self.center_line() - TRACK_THICKNESS as f64 / 2.0;
+        let (lo, hi) = (self.offset_of(from), self.offset_of(to));
+        let length = (end - start).abs();
+        let filled = (hi - lo).abs();
+        match self.orientation {
+            Orientation::Horizontal => {
+                let (x, y) = (start, across);
+                context.fill_rect(
+                    x,
+                    y,
+                    length as u32,
+                    TRACK_THICKNESS,
+                    style.border_color.value,
+                );
+                let t = TRACK_THICKNESS;
+                context.fill_rect(lo, y, filled as u32, t, style.cursor_color.value);
+            }
+            Orientation::Vertical => {
+                let (x, y) = (across, end);
+                context.fill_rect(
+                    x,
+                    y,
+                    TRACK_THICKNESS,
+                    length as u32,
+                    style.border_color.value,
+                );
+                let t = TRACK_THICKNESS;
+                context.fill_rect(x, hi, t, filled as u32, style.cursor_color.value);
+            }
+        }
+
+        let tick_start = self.center_line() + THUMB_RADIUS + PADDING;
+        let line_height = context.line_height() as f64;
+        for value in self.tick_values() {
+            let along = self.offset_of(value).round();
+            let label = self.tick_labels.then(|| (self.label_format)(value));
+            match self.orientation {
+                Orientation::Horizontal => {
+                    let y = tick_start;
+                    context.draw_line(along, y, along, y + TICK_LENGTH, style.fore_color.value);
+                    if let Some(label) = label {
+                        let x = along - context.text_width(&label) as f64 / 2.0;
+                        let y = y + TICK_LENGTH + PADDING;
+                        context.draw_text(&label, x, y, style.fore_color.value);
+                    }
+                }
+                Orientation::Vertical => {
+                    let x = tick_start;
+                    context.draw_line(x, along, x + TICK_LENGTH, along, style.fore_color.value);
+                    if let Some(label) = label {
+                        let x = x + TICK_LENGTH + PADDING;
+                        let y = along - line_height / 2.0;
+                        context.draw_text(&label, x, y, style.fore_color.value);
+                    }
+                }
+            }
+        }
+    }
+
+    ///
+    /// Draw a thumb at `value`, with a focus ring when `focused`.
+    ///
+    fn draw_thumb(
+        &self,
+        context: &mut BufferContext,
+        style: &ComponentStyle,
+        value: f64,
+        focused: bool,
+    ) {
+        let center = self.thumb_center(value);
+        if focused {
+            let ring = THUMB_RADIUS + 3.0;
+            context.fill_circle(center.x, center.y, ring, style.cursor_color.value);
+        }
+        let border = style.border_width.max(1) as f64;
+        let (inner, outer) = (THUMB_RADIUS - border, THUMB_RADIUS);
+        context.fill_circle(center.x, center.y, outer, style.border_color.value);
+        context.fill_circle(center.x, center.y, inner, style.back_color.value);
+    }
+}
+
+///
+/// The builders that only change the `Track`, shared by `Slider` and `RangeSlider`.
+///
+macro_rules! track_builders {
+    () => {
+        ///
+        /// Lay the slider out along the x or y axis. Defaults to `Orientation::Horizontal`.
+        ///
+        pub fn orientation(mut self, orientation: Orientation) -> Self {
+            self.track.orientation = orientation;
+            self.track.fit();
+            self
+        }
+
+        ///
+        /// Set the amount moved by `PageUp` / `PageDown`. Defaults to a tenth of the range.
+        ///
+        pub fn page_step(mut self, page_step: f64) -> Self {
+            self.track.page_step = Some(page_step);
+            self
+        }
+
+        ///
+        /// Draw tick marks every `interval`, starting at `min`.
+        ///
+        pub fn ticks(mut self, interval: f64) -> Self {
+            self.track.tick_interval = Some(interval);
+            self.track.fit();
+            self
+        }
+
+        ///
+        /// Label the tick marks with their values.
+        ///
+        pub fn tick_labels(mut self, tick_labels: bool) -> Self {
+            self.track.tick_labels = tick_labels;
+            self.track.fit();
+            self
+        }
+
+        ///
+        /// Format the tick labels, e.g. `|value| format!("{value}%")`.
+        ///
+        pub fn label_format<F: Fn(f64) -> String + 'static>(mut self, f: F) -> Self {
+            self.track.label_format = Box::new(f);
+            self.track.fit();
+            self
+        }
+    };
+}
+
+///
+/// Returns `value` without trailing zeros, e.g. `"5"` or `"0.25"`.
+///
+fn format_value(value: f64) -> String {
+    let text = format!("{value:.2}");
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+///
+/// Returns the `ComponentState` for the mouse over a slider.
+///
+fn mouse_state(inside: bool, dragging: bool) -> ComponentState {
+    match (inside, dragging) {
+        (_, true) => ComponentState::Pressed,
+        (true, false) => ComponentState::Hovered,
+        _ => ComponentState::Active,
+    }
+}
+
+///
+/// Slider control for picking a value in a range.
+///
+pub struct Slider {
+    track: Track,
+    value: f64,
+    enabled: bool,
+    hovered: bool,
+
+    /// While dragging, the distance along the track from the thumb center to the cursor.
+    drag_offset: Option<f64>,
+    focus_id: FocusId,
+    component_state: ComponentState,
+    on_change: Option<Box<dyn FnMut(f64)>>,
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Slider {
+    ///
+    /// Constructor, for a horizontal slider from `0` to `100` in steps of `1`.
+    ///
+    pub fn new() -> Self {
+        Self {
+            track: Track::new(),
+            value: 0.0,
+            enabled: true,
+            hovered: false,
+            drag_offset: None,
+            focus_id: FocusId::new(),
+            component_state: ComponentState::Active,
+            on_change: None,
+        }
+    }
+
+    track_builders!();
+
+    ///
+    /// Set the `min` and `max` values.
+    ///
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.track.set_range(min, max);
+        self.value = self.track.snap(self.value);
+        self.track.fit();
+        self
+    }
+
+    ///
+    /// Set the step that values snap to. `0.0` allows any value.
+    ///
+    pub fn step(mut self, step: f64) -> Self {
+        self.track.step = step.max(0.0);
+        self.value = self.track.snap(self.value);
+        self
+    }
+
+    ///
+    /// Set the initial value.
+    ///
+    pub fn value(mut self, value: f64) -> Self {
+        self.value = self.track.snap(value);
+        self
+    }
+
+    ///
+    /// Enable or disable the `Slider`. A disabled `Slider` ignores input.
+    ///
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    ///
+    /// Add event handler, called with the new value when the user changes it.
+    ///
+    pub fn on_change<F: FnMut(f64) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    ///
+    /// Change the value, snapped to the range and step. Does not call `on_change`.
+    ///
+    pub fn set_value(&mut self, value: f64) {
+        self.value = self.track.snap(value);
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.drag_offset = None;
+        self.component_state = if enabled {
+            ComponentState::Active
+        } else {
+            focus::clear_focus(self.focus_id);
+            ComponentState::Disabled
+        };
+    }
+
+    ///
+    /// Returns the `FocusId` of the `Slider`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    ///
+    /// Change the value on behalf of the user, calling `on_change` if it changed.
+    ///
+    fn change_value(&mut self, value: f64) {
+        if value != self.value {
+            self.value = value;
+            if let Some(handler) = self.on_change.as_mut() {
+                handler(value);
+            }
+        }
+    }
+}
+
+impl Component for Slider {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if !self.enabled {
+            return;
+        }
+        let inside = self
+            .track
+            .bounds()
+            .contains(input.position.x, input.position.y);
+        let along = self.track.along(input.position);
+        if input.pressed {
+            if inside {
+                focus::set_focus(self.focus_id);
+                let thumb = self.track.offset_of(self.value);
+                if (along - thumb).abs() <= THUMB_RADIUS {
+                    self.drag_offset = Some(along - thumb);
+                } else {
+                    self.drag_offset = Some(0.0);
+                    self.change_value(self.track.value_at(along));
+                }
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+        } else if let Some(offset) = self.drag_offset
+            && !input.is_elsewhere()
+        {
+            self.change_value(self.track.value_at(along - offset));
+        }
+        if input.just_released {
+            self.drag_offset = None;
+        }
+        self.hovered = inside;
+        self.component_state = mouse_state(inside, self.drag_offset.is_some());
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+        if self.enabled && self.hovered {
+            self.change_value(self.track.wheel_value(self.value, delta));
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if self.enabled
+            && focus::is_focused(self.focus_id)
+            && event.state == ElementState::Pressed
+            && let Some(value) = self.track.key_value(self.value, &event.logical_key)
+        {
+            self.change_value(value);
+        }
+    }
+
+    ///
+    /// Keeps the value.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.value))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(value) = state.downcast::<f64>() {
+            self.set_value(*value);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = focus::is_focused(self.focus_id);
+        let style = context
+            .theme
+            .style_for(&display_state(&self.component_state, focused));
+        self.track.draw(context, &style, self.track.min, self.value);
+        self.track.draw_thumb(context, &style, self.value, focused);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.track.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.track.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.track.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+    }
+
+    fn get_size(&self) -> Size {
+        self.track.size.clone()
+    }
+}
+
+///
+/// Slider control with two thumbs, for picking a range between `min` and `max`.
+///
+pub struct RangeSlider {
+    track: Track,
+    values: [f64; 2],
+
+    /// The thumb moved by the keyboard: the one last pressed.
+    active: usize,
+    enabled: bool,
+    hovered: bool,
+
+    /// While dragging, the thumb, and the distance along the track from its center to the
+    /// cursor.
+    drag: Option<(usize, f64)>,
+    focus_id: FocusId,
+    component_state: ComponentState,
+    on_change: Option<Box<dyn FnMut(f64, f64)>>,
+}
+
+impl Default for RangeSlider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RangeSlider {
+    ///
+    /// Constructor, for a horizontal slider from `0` to `100` in steps of `1`, with the
+    /// whole range selected.
+    ///
+    pub fn new() -> Self {
+        let track = Track::new();
+        Self {
+            values: [track.min, track.max],
+            track,
+            active: 0,
+            enabled: true,
+            hovered: false,
+            drag: None,
+            focus_id: FocusId::new(),
+            component_state: ComponentState::Active,
+            on_change: None,
+        }
+    }
+
+    track_builders!();
+
+    ///
+    /// Set the `min` and `max` values, selecting the whole range.
+    ///
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.track.set_range(min, max);
+        self.values = [self.track.min, self.track.max];
+        self.track.fit();
+        self
+    }
+
+    ///
+    /// Set the step that values snap to. `0.0` allows any value.
+    ///
+    pub fn step(mut self, step: f64) -> Self {
+        self.track.step = step.max(0.0);
+        self.values = self.values.map(|value| self.track.snap(value));
+        self
+    }
+
+    ///
+    /// Set the initial range.
+    ///
+    pub fn values(mut self, low: f64, high: f64) -> Self {
+        self.set_values(low, high);
+        self
+    }
+
+    ///
+    /// Enable or disable the `RangeSlider`. A disabled `RangeSlider` ignores input.
+    ///
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    ///
+    /// Add event handler, called with the new low and high values when the user changes
+    /// either.
+    ///
+    pub fn on_change<F: FnMut(f64, f64) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Returns the low and high values.
+    ///
+    pub fn get_values(&self) -> (f64, f64) {
+        (self.values[0], self.values[1])
+    }
+
+    ///
+    /// Change the range, snapped to the track. Does not call `on_change`.
+    ///
+    pub fn set_values(&mut self, low: f64, high: f64) {
+        let (low, high) = (self.track.snap(low), self.track.snap(high));
+        self.values = [low.min(high), low.max(high)];
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.drag = None;
+        self.component_state = if enabled {
+            ComponentState::Active
+        } else {
+            focus::clear_focus(self.focus_id);
+            ComponentState::Disabled
+        };
+    }
+
+    ///
+    /// Returns the `FocusId` of the `RangeSlider`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    ///
+    /// Returns the thumb nearest to the coordinate `along` the track. When the thumbs are
+    /// together, the one on the side of `along`.
+    ///
+    fn nearest_thumb(&self, along: f64) -> usize {
+        let [low, high] = self.values.map(|value| self.track.offset_of(value));
+        let (to_low, to_high) = ((along - low).abs(), (along - high).abs());
+        if to_low < to_high {
+            0
+        } else if to_high < to_low {
+            1
+        } else {
+            let increasing = (self.track.offset_of(self.track.max) - low).signum();
+            usize::from((along - low) * increasing > 0.0)
+        }
+    }
+
+    ///
+    /// Move `thumb` to `value` on behalf of the user, without passing the other thumb, and
+    /// call `on_change` if it moved.
+    ///
+    fn change_value(&mut self, thumb: usize, value: f64) {
+        let value = match thumb {
+            0 => value.min(self.values[1]),
+            _ => value.max(self.values[0]),
+        };
+        if value != self.values[thumb] {
+            self.values[thumb] = value;
+            if let Some(handler) = self.on_change.as_mut() {
+                handler(self.values[0], self.values[1]);
+            }
+        }
+    }
+}
+
+impl Component for RangeSlider {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if !self.enabled {
+            return;
+        }
+        let inside = self
+            .track
+            .bounds()
+            .contains(input.position.x, input.position.y);
+        let along = self.track.along(input.position);
+        if input.pressed {
+            if inside {
+                focus::set_focus(self.focus_id);
+                let thumb = self.nearest_thumb(along);
+                let center = self.track.offset_of(self.values[thumb]);
+                self.active = thumb;
+                if (along - center).abs() <= THUMB_RADIUS {
+                    self.drag = Some((thumb, along - center));
+                } else {
+                    self.drag = Some((thumb, 0.0));
+                    self.change_value(thumb, self.track.value_at(along));
+                }
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+        } else if let Some((thumb, offset)) = self.drag
+            && !input.is_elsewhere()
+        {
+            self.change_value(thumb, self.track.value_at(along - offset));
+        }
+        if input.just_released {
+            self.drag = None;
+        }
+        self.hovered = inside;
+        self.component_state = mouse_state(inside, self.drag.is_some());
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+        if self.enabled && self.hovered {
+            let value = self.track.wheel_value(self.values[self.active], delta);
+            self.change_value(self.active, value);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if self.enabled
+            && focus::is_focused(self.focus_id)
+            && event.state == ElementState::Pressed
+            && let Some(value) = self
+                .track
+                .key_value(self.values[self.active], &event.logical_key)
+        {
+            self.change_value(self.active, value);
+        }
+    }
+
+    ///
+    /// Keeps the low and high values.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.values))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(values) = state.downcast::<[f64; 2]>() {
+            self.set_values(values[0], values[1]);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = focus::is_focused(self.focus_id);
+        let style = context
+            .theme
+            .style_for(&display_state(&self.component_state, focused));
+        let [low, high] = self.values;
+        self.track.draw(context, &style, low, high);
+        let inactive = 1 - self.active;
+        self.track
+            .draw_thumb(context, &style, self.values[inactive], false);
+        self.track
+            .draw_thumb(context, &style, self.values[self.active], focused);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.track.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.track.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.track.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+    }
+
+    fn get_size(&self) -> Size {
+        self.track.size.clone()
+    }
+}
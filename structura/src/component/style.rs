@@ -5,6 +5,7 @@
 //!
 
 use crate::component::ComponentState;
+use std::rc::Rc;
 
 ///
 /// The factor to be applied for `Color::lighten()` and `Color::darken()`.
@@ -267,3 +268,128 @@ impl Default for ComponentStyle {
         ComponentStyle::STYLE_ACTIVE
     }
 }
+
+///
+/// A named set of style overrides, such as a `class="danger"` in UI markup, applied on top of
+/// the current `ComponentTheme` by a `Styled` component.
+///
+/// Fields left as `None` keep the value from the current theme. Overridden colors are lightened
+/// for `ComponentState::Hovered` and darkened for `ComponentState::Pressed`, so that components
+/// still show feedback.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleClass {
+    pub name: String,
+    pub edit_text_color: Option<u32>,
+    pub edit_back_color: Option<u32>,
+    pub fore_color: Option<u32>,
+    pub back_color: Option<u32>,
+    pub cursor_color: Option<u32>,
+    pub border_color: Option<u32>,
+    pub border_width: Option<usize>,
+}
+
+impl StyleClass {
+    ///
+    /// Constructor, with no overrides.
+    ///
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    ///
+    /// Apply the overrides to `style`, the style of the current theme for `state`.
+    ///
+    pub fn apply(&self, style: ComponentStyle, state: &ComponentState) -> ComponentStyle {
+        self.override_with(style, |value| {
+            let color = Color::new(value);
+            match state {
+                ComponentState::Hovered => color.lighten(ColorFactor::default()),
+                ComponentState::Pressed => color.darken(ColorFactor::default()),
+                _ => color,
+            }
+        })
+    }
+
+    ///
+    /// Apply the overrides to `style` as they are, without adjusting colors for the state.
+    ///
+    pub fn apply_exact(&self, style: ComponentStyle) -> ComponentStyle {
+        self.override_with(style, Color::new)
+    }
+
+    fn override_with<F: Fn(u32) -> Color>(
+        &self,
+        mut style: ComponentStyle,
+        adjust: F,
+    ) -> ComponentStyle {
+        let fields = [
+            (&mut style.edit_text_color, self.edit_text_color),
+            (&mut style.edit_back_color, self.edit_back_color),
+            (&mut style.fore_color, self.fore_color),
+            (&mut style.back_color, self.back_color),
+            (&mut style.cursor_color, self.cursor_color),
+            (&mut style.border_color, self.border_color),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                *field = adjust(value);
+            }
+        }
+        if let Some(border_width) = self.border_width {
+            style.border_width = border_width;
+        }
+        style
+    }
+}
+
+///
+/// A `ComponentTheme` that applies one or more `StyleClass`es on top of a base theme.
+///
+pub struct ClassTheme {
+    pub base: Rc<dyn ComponentTheme>,
+    pub classes: Vec<Rc<StyleClass>>,
+}
+
+impl ComponentTheme for ClassTheme {
+    fn style_for(&self, state: &ComponentState) -> ComponentStyle {
+        self.classes
+            .iter()
+            .fold(self.base.style_for(state), |style, class| {
+                class.apply(style, state)
+            })
+    }
+}
+
+///
+/// A `ComponentTheme` that overrides a base theme with `StyleClass`es, each for a single
+/// `ComponentState` or for all states, such as a theme loaded from a file with
+/// `markup::load_theme_file()`.
+///
+/// Overrides for all states are applied first, with `StyleClass::apply()`, followed by the
+/// overrides for the current state, with `StyleClass::apply_exact()`.
+///
+pub struct StateTheme {
+    pub base: Rc<dyn ComponentTheme>,
+    pub styles: Vec<(Option<ComponentState>, StyleClass)>,
+}
+
+impl ComponentTheme for StateTheme {
+    fn style_for(&self, state: &ComponentState) -> ComponentStyle {
+        let mut style = self.base.style_for(state);
+        for (style_state, class) in self.styles.iter() {
+            if style_state.is_none() {
+                style = class.apply(style, state);
+            }
+        }
+        for (style_state, class) in self.styles.iter() {
+            if style_state.as_ref() == Some(state) {
+                style = class.apply_exact(style);
+            }
+        }
+        style
+    }
+}
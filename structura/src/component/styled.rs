@@ -0,0 +1,122 @@
+//!
+//! # Structura Component: Styled.
+//!
+//! Wraps a `Component` or `ContainerComponent`, drawing it with one or more `StyleClass`es
+//! applied on top of the current `ComponentTheme`. Everything else is passed straight through.
+//!
+
+use crate::component::Component;
+use crate::component::style::{ClassTheme, StyleClass};
+use crate::container::Container;
+use crate::event::MouseInput;
+use crate::geometry::{Point, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::rc::Rc;
+use std::time::Instant;
+
+///
+/// A `Component` drawn with style classes. Also a `Container` when the wrapped `Component` is.
+///
+pub struct Styled<T> {
+    inner: T,
+    classes: Vec<Rc<StyleClass>>,
+}
+
+impl<T> Styled<T> {
+    ///
+    /// Constructor.
+    ///
+    pub fn new(inner: T, classes: Vec<Rc<StyleClass>>) -> Self {
+        Self { inner, classes }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn classes(&self) -> &[Rc<StyleClass>] {
+        &self.classes
+    }
+}
+
+impl<T: Component> Component for Styled<T> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        self.inner.handle_mouse_event(input);
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        self.inner.handle_mouse_wheel_event(delta, phase);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        self.inner.handle_keyboard_event(event);
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.inner.animate(now);
+    }
+
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        self.inner.save_state()
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        self.inner.restore_state(state);
+    }
+
+    ///
+    /// Draw the wrapped `Component` with the style classes applied, then restore the theme.
+    ///
+    fn draw(&self, context: &mut BufferContext) {
+        let theme = Rc::new(ClassTheme {
+            base: context.theme.clone(),
+            classes: self.classes.clone(),
+        });
+        let previous = std::mem::replace(&mut context.theme, theme);
+        self.inner.draw(context);
+        context.theme = previous;
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.inner.set_position(x, y);
+    }
+
+    fn get_position(&self) -> Point {
+        self.inner.get_position()
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.inner.set_size(width, height);
+    }
+
+    fn get_size(&self) -> Size {
+        self.inner.get_size()
+    }
+}
+
+impl<T: Container> Container for Styled<T> {
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.inner.push(component);
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        self.inner.resize(width, height);
+    }
+
+    fn update_layout(&mut self) {
+        self.inner.update_layout();
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        self.inner.fills_parent_container()
+    }
+}
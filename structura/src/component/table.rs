@@ -0,0 +1,1381 @@
+//!
+//! # Structura Component: Table.
+//!
+//! A grid of cells under a header row, for any number of rows and columns.
+//!
+//! The cells come from a `TableModel`, which gives the number of rows and columns, the column
+//! titles and the text of each cell. Only the visible cells are drawn. A model that changes
+//! tells the `Table` through the `TableNotifier` it is given, from any thread, and the `Table`
+//! updates at its next frame:
+//!
+//! ```ignore
+//! impl TableModel for Orders {
+//!     fn row_count(&self) -> usize { self.orders.len() }
+//!     fn column_count(&self) -> usize { 3 }
+//!     fn column_title(&self, column: usize) -> String { TITLES[column].to_string() }
+//!     fn cell_text(&self, row: usize, column: usize) -> String { self.orders[row].field(column) }
+//!     fn set_notifier(&mut self, notifier: TableNotifier) { self.notifier = Some(notifier) }
+//! }
+//!
+//! let table = Table::new(orders).frozen_first_column(true).column_width(0, 200.0);
+//! ```
+//!
+//! - Dragging the edge of a header cell resizes its column. Dragging a header cell moves its
+//!   column, and a click sorts the rows by it, then reverses the order.
+//! - The header stays in place while the rows scroll, unless `frozen_header(false)`. With
+//!   `frozen_first_column(true)`, the first column also stays in place while the columns
+//!   scroll.
+//! - With focus, the arrow keys, `PageUp` / `PageDown` and `Home` / `End` move the current
+//!   cell. A double-click, `Enter` or `F2` edits an editable cell in an embedded `TextField`,
+//!   as does typing. `Enter` or a click elsewhere keeps the edit, and `Escape` cancels it.
+//!   On a cell that is not editable, a double-click or `Enter` activates the row instead.
+//!
+//! Rows are identified by their index in the model, e.g. in `selected_row()`, whatever the
+//! sort order.
+//!
+
+use crate::animation;
+use crate::component::list::{DOUBLE_CLICK_TIME, HOVER_ALPHA, SELECTION_ALPHA, SELECTION_COLOR};
+use crate::component::scrollbar::{SCROLLBAR_WIDTH, ScrollBar};
+use crate::component::textfield::TextField;
+use crate::component::{Component, ComponentState, metrics};
+use crate::dispatch;
+use crate::event::focus::{self, FocusId};
+use crate::event::{self, MouseInput};
+use crate::geometry::{Orientation, Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use winit::event::{ElementState, MouseScrollDelta};
+use winit::keyboard::{Key, NamedKey};
+
+/// Space left of the text in each cell.
+const PADDING: f64 = 6.0;
+
+/// Width of new columns.
+const DEFAULT_COLUMN_WIDTH: f64 = 120.0;
+
+/// The narrowest a column can be resized to.
+const MIN_COLUMN_WIDTH: f64 = 24.0;
+
+/// Distance from the edge of a header cell where a drag resizes the column.
+const RESIZE_MARGIN: f64 = 4.0;
+
+/// Distance the mouse must move on a header cell before it drags the column, rather than
+/// sorting by it.
+const DRAG_THRESHOLD: f64 = 4.0;
+
+/// Rows scrolled by one notch of the mouse wheel.
+const WHEEL_ROWS: f64 = 3.0;
+
+/// Opacity of the grid lines between cells.
+const GRID_ALPHA: f32 = 0.25;
+
+///
+/// The cells shown by a `Table`.
+///
+/// Rows and columns are numbered from `0`, in the order of the model. The `Table` sorts and
+/// moves them for display without changing the model.
+///
+pub trait TableModel {
+    fn row_count(&self) -> usize;
+
+    fn column_count(&self) -> usize;
+
+    fn column_title(&self, column: usize) -> String;
+
+    fn cell_text(&self, row: usize, column: usize) -> String;
+
+    ///
+    /// Returns true if the user can edit the cell.
+    ///
+    fn is_editable(&self, _row: usize, _column: usize) -> bool {
+        false
+    }
+
+    ///
+    /// Store the text the user entered in an editable cell.
+    ///
+    fn set_cell_text(&mut self, _row: usize, _column: usize, _text: &str) {}
+
+    ///
+    /// Compare two rows by a column, to sort by it. Compares the text of the cells by default,
+    /// e.g. override to compare numbers.
+    ///
+    fn compare(&self, column: usize, a: usize, b: usize) -> Ordering {
+        self.cell_text(a, column).cmp(&self.cell_text(b, column))
+    }
+
+    ///
+    /// Compare two rows by the group they belong to, e.g. folders before files. When sorting,
+    /// rows are ordered by group first, in this order whichever way the column is sorted. All
+    /// rows are in one group by default.
+    ///
+    fn compare_group(&self, _a: usize, _b: usize) -> Ordering {
+        Ordering::Equal
+    }
+
+    ///
+    /// Keep the notifier, to tell the `Table` when the model changes. Called by
+    /// `Table::new()`.
+    ///
+    fn set_notifier(&mut self, _notifier: TableNotifier) {}
+}
+
+///
+/// A change to a `TableModel`, given to `TableNotifier::notify()`.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableChange {
+    /// Rows were inserted at `start`, moving the rows after them down.
+    RowsInserted { start: usize, count: usize },
+
+    /// Rows were removed from `start`, moving the rows after them up.
+    RowsRemoved { start: usize, count: usize },
+
+    /// The cells of rows changed.
+    RowsChanged { start: usize, count: usize },
+
+    /// Columns were added or removed, or their titles changed.
+    ColumnsChanged,
+
+    /// Anything may have changed.
+    Reset,
+}
+
+///
+/// Tells a `Table` that its model changed. Can be cloned, and sent to other threads.
+///
+#[derive(Clone)]
+pub struct TableNotifier {
+    changes: Arc<Mutex<Vec<TableChange>>>,
+}
+
+impl TableNotifier {
+    ///
+    /// Tell the `Table` about `change`, which it shows at its next frame.
+    ///
+    pub fn notify(&self, change: TableChange) {
+        self.changes.lock().unwrap().push(change);
+        dispatch::post(animation::request_frame);
+    }
+
+    fn take(&self) -> Vec<TableChange> {
+        std::mem::take(&mut *self.changes.lock().unwrap())
+    }
+}
+
+///
+/// The order of the rows, when sorted by a column.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Column {
+    /// The column in the model.
+    index: usize,
+    width: f64,
+}
+
+///
+/// A drag that started on the header, on a column at a display position.
+///
+#[derive(Debug, Clone, Copy)]
+enum HeaderDrag {
+    Resize {
+        column: usize,
+        start_x: f64,
+        start_width: f64,
+    },
+
+    /// Pressed, but not moved far enough to drag the column yet.
+    Press {
+        column: usize,
+        start_x: f64,
+    },
+    Move {
+        column: usize,
+        x: f64,
+    },
+}
+
+/// Called with a row of the model, e.g. when it is selected.
+type RowHandler = Box<dyn FnMut(usize)>;
+
+///
+/// The state of a `Table` kept by `Component::save_state()`.
+///
+struct TableState {
+    columns: Vec<Column>,
+    sort: Option<(usize, SortOrder)>,
+    selected: Option<usize>,
+    scroll: (f64, f64),
+}
+
+///
+/// Table control showing the cells of a `TableModel`.
+///
+pub struct Table<M: TableModel> {
+    position: Point,
+    size: Size,
+    model: M,
+    notifier: TableNotifier,
+
+    /// The columns, in display order.
+    columns: Vec<Column>,
+
+    /// The row of the model shown at each row of the display, and the reverse.
+    order: Vec<usize>,
+    view_of: Vec<usize>,
+
+    /// The column of the model that the rows are sorted by.
+    sort: Option<(usize, SortOrder)>,
+    row_height: f64,
+    frozen_header: bool,
+    frozen_first_column: bool,
+
+    /// The header and rows, without the scrollbars.
+    viewport: Rectangle,
+    vertical: ScrollBar,
+    horizontal: ScrollBar,
+
+    /// The selected row of the model.
+    selected: Option<usize>,
+
+    /// The column of the model holding the current cell, in the selected row.
+    current_column: usize,
+
+    /// The row of the display under the mouse.
+    hovered: Option<usize>,
+
+    /// True while the cursor is over the `Table`, so the wheel scrolls it.
+    cursor_over: bool,
+    header_drag: Option<HeaderDrag>,
+    editor: TextField,
+
+    /// The row and column of the model of the cell being edited.
+    editing: Option<(usize, usize)>,
+    last_click: Option<((usize, usize), Instant)>,
+    focus_id: FocusId,
+    on_selection_changed: Option<RowHandler>,
+    on_row_activated: Option<RowHandler>,
+}
+
+impl<M: TableModel> Table<M> {
+    ///
+    /// Constructor, showing the cells of `model`, unsorted.
+    ///
+    pub fn new(mut model: M) -> Self {
+        let notifier = TableNotifier {
+            changes: Arc::new(Mutex::new(Vec::new())),
+        };
+        model.set_notifier(notifier.clone());
+        let columns = (0..model.column_count())
+            .map(|index| Column {
+                index,
+                width: DEFAULT_COLUMN_WIDTH,
+            })
+            .collect();
+        let mut table = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 400,
+                height: 300,
+            },
+            model,
+            notifier,
+            columns,
+            order: Vec::new(),
+            view_of: Vec::new(),
+            sort: None,
+            row_height: (metrics::line_height() as f64 + 8.0).ceil(),
+            frozen_header: true,
+            frozen_first_column: false,
+            viewport: Rectangle::new(0.0, 0.0, 0, 0),
+            vertical: ScrollBar::new(Orientation::Vertical),
+            horizontal: ScrollBar::new(Orientation::Horizontal),
+            selected: None,
+            current_column: 0,
+            hovered: None,
+            cursor_over: false,
+            header_drag: None,
+            editor: TextField::new(),
+            editing: None,
+            last_click: None,
+            focus_id: FocusId::new(),
+            on_selection_changed: None,
+            on_row_activated: None,
+        };
+        table.update_order();
+        table.update_layout();
+        table
+    }
+
+    ///
+    /// Set the height of the header and of every row.
+    ///
+    pub fn row_height(mut self, row_height: f64) -> Self {
+        self.row_height = row_height.max(1.0);
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Set the width of a column of the model.
+    ///
+    pub fn column_width(mut self, column: usize, width: f64) -> Self {
+        if let Some(column) = self.columns.iter_mut().find(|c| c.index == column) {
+            column.width = width.max(MIN_COLUMN_WIDTH);
+        }
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Keep the header in place while the rows scroll. On by default.
+    ///
+    pub fn frozen_header(mut self, frozen_header: bool) -> Self {
+        self.frozen_header = frozen_header;
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Keep the first column in place while the columns scroll.
+    ///
+    pub fn frozen_first_column(mut self, frozen_first_column: bool) -> Self {
+        self.frozen_first_column = frozen_first_column;
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Add event handler, called with the row of the model the user selects.
+    ///
+    pub fn on_selection_changed<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_selection_changed = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Add event handler, called with the row of the model the user double-clicks, or presses
+    /// `Enter` on, when the cell is not editable.
+    ///
+    pub fn on_row_activated<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_row_activated = Some(Box::new(f));
+        self
+    }
+
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    ///
+    /// Returns the model, to change the cells. Tell the `Table` with the `TableNotifier`.
+    ///
+    pub fn model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
+    ///
+    /// Returns the selected row of the model.
+    ///
+    pub fn selected_row(&self) -> Option<usize> {
+        self.selected
+    }
+
+    ///
+    /// Select a row of the model, or none, and scroll it into view. Does not call
+    /// `on_selection_changed`.
+    ///
+    pub fn set_selected_row(&mut self, row: Option<usize>) {
+        self.apply_changes();
+        self.selected = row.filter(|&row| row < self.order.len());
+        self.scroll_to_current();
+    }
+
+    ///
+    /// Sort the rows by a column of the model, or restore the order of the model.
+    ///
+    pub fn sort_by(&mut self, sort: Option<(usize, SortOrder)>) {
+        self.commit_edit();
+        self.sort = sort.filter(|&(column, _)| column < self.model.column_count());
+        self.update_order();
+        self.scroll_to_current();
+    }
+
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    ///
+    /// Returns the columns of the model, in display order.
+    ///
+    pub fn column_order(&self) -> Vec<usize> {
+        self.columns.iter().map(|column| column.index).collect()
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    ///
+    /// Returns the `FocusId` of the `Table`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    fn is_focused(&self) -> bool {
+        focus::is_focused(self.focus_id) || focus::is_focused(self.editor.focus_id())
+    }
+
+    ///
+    /// Apply the changes the model told about through the `TableNotifier`.
+    ///
+    fn apply_changes(&mut self) {
+        let changes = self.notifier.take();
+        if changes.is_empty() {
+            return;
+        }
+        for change in changes {
+            match change {
+                TableChange::RowsInserted { start, count } => {
+                    let shift = |row: usize| if row >= start { row + count } else { row };
+                    self.selected = self.selected.map(shift);
+                    self.editing = self.editing.map(|(row, column)| (shift(row), column));
+                }
+                TableChange::RowsRemoved { start, count } => {
+                    let removed = start..start + count;
+                    let shift = |row: usize| {
+                        if row >= removed.end {
+                            Some(row - count)
+                        } else {
+                            (!removed.contains(&row)).then_some(row)
+                        }
+                    };
+                    self.selected = self.selected.and_then(shift);
+                    if let Some((row, column)) = self.editing {
+                        match shift(row) {
+                            Some(row) => self.editing = Some((row, column)),
+                            None => self.cancel_edit(),
+                        }
+                    }
+                }
+                TableChange::RowsChanged { .. } => {}
+                TableChange::ColumnsChanged => self.update_columns(),
+                TableChange::Reset => {
+                    self.selected = None;
+                    self.cancel_edit();
+                    self.update_columns();
+                }
+            }
+        }
+        let count = self.model.row_count();
+        self.selected = self.selected.filter(|&row| row < count);
+        if self.editing.is_some_and(|(row, _)| row >= count) {
+            self.cancel_edit();
+        }
+        self.update_order();
+    }
+
+    ///
+    /// Keep the columns that are still in the model, in their display order and width, and
+    /// add the new ones at the end.
+    ///
+    fn update_columns(&mut self) {
+        let count = self.model.column_count();
+        self.columns.retain(|column| column.index < count);
+        for index in 0..count {
+            if !self.columns.iter().any(|column| column.index == index) {
+                self.columns.push(Column {
+                    index,
+                    width: DEFAULT_COLUMN_WIDTH,
+                });
+            }
+        }
+        self.sort = self.sort.filter(|&(column, _)| column < count);
+        if self.editing.is_some_and(|(_, column)| column >= count) {
+            self.cancel_edit();
+        }
+    }
+
+    ///
+    /// Sort the rows, after a change to the model or to the sort column.
+    ///
+    fn update_order(&mut self) {
+        let count = self.model.row_count();
+        let mut order: Vec<usize> = (0..count).collect();
+        if let Some((column, sort)) = self.sort {
+            let model = &self.model;
+            order.sort_by(|&a, &b| {
+                model.compare_group(a, b).then_with(|| {
+                    let ordering = model.compare(column, a, b);
+                    match sort {
+                        SortOrder::Ascending => ordering,
+                        SortOrder::Descending => ordering.reverse(),
+                    }
+                })
+            });
+        }
+        self.view_of = vec![0; count];
+        for (view, &row) in order.iter().enumerate() {
+            self.view_of[row] = view;
+        }
+        self.order = order;
+        self.hovered = self.hovered.filter(|&view| view < count);
+        self.update_layout();
+    }
+
+    fn header_height(&self) -> f64 {
+        self.row_height
+    }
+
+    ///
+    /// Returns the height of the header when it stays in place, or `0.0` when it scrolls with
+    /// the rows.
+    ///
+    fn fixed_header_height(&self) -> f64 {
+        if self.frozen_header {
+            self.header_height()
+        } else {
+            0.0
+        }
+    }
+
+    fn frozen_width(&self) -> f64 {
+        match self.columns.first() {
+            Some(column) if self.frozen_first_column => column.width,
+            _ => 0.0,
+        }
+    }
+
+    ///
+    /// Fit the viewport and scrollbars to the size, rows and columns, and move the editor with
+    /// its cell.
+    ///
+    fn update_layout(&mut self) {
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width as f64, self.size.height as f64);
+        let bar = SCROLLBAR_WIDTH as f64;
+        let fixed_header = self.fixed_header_height();
+        let frozen_width = self.frozen_width();
+        let total_width: f64 = self.columns.iter().map(|column| column.width).sum();
+        let content_height =
+            self.order.len() as f64 * self.row_height + self.header_height() - fixed_header;
+
+        //
+        // Each scrollbar takes space from the other direction, which may then need one too.
+        //
+        let mut vertical = content_height > height - fixed_header;
+        let horizontal = total_width > width - if vertical { bar } else { 0.0 };
+        vertical |= content_height > height - fixed_header - if horizontal { bar } else { 0.0 };
+        let horizontal = total_width > width - if vertical { bar } else { 0.0 };
+        let body_width = (width - if vertical { bar } else { 0.0 }).max(0.0);
+        let body_height = (height - if horizontal { bar } else { 0.0 }).max(0.0);
+
+        self.viewport = Rectangle::new(x, y, body_width as u32, body_height as u32);
+        let rows_height = (body_height - fixed_header).max(0.0);
+        self.vertical.set_lengths(content_height, rows_height);
+        self.vertical.set_track(Rectangle::new(
+            x + body_width,
+            y + fixed_header,
+            SCROLLBAR_WIDTH,
+            rows_height as u32,
+        ));
+        let columns_width = (body_width - frozen_width).max(0.0);
+        self.horizontal
+            .set_lengths(total_width - frozen_width, columns_width);
+        self.horizontal.set_track(Rectangle::new(
+            x + frozen_width,
+            y + body_height,
+            columns_width as u32,
+            SCROLLBAR_WIDTH,
+        ));
+        self.place_editor();
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    fn header_y(&self) -> f64 {
+        if self.frozen_header {
+            self.viewport.point.y
+        } else {
+            self.viewport.point.y - self.vertical.offset()
+        }
+    }
+
+    ///
+    /// Returns the top of a row of the display.
+    ///
+    fn row_y(&self, view: usize) -> f64 {
+        self.viewport.point.y + self.header_height() + view as f64 * self.row_height
+            - self.vertical.offset()
+    }
+
+    ///
+    /// Returns the left edge of a column, at a display position.
+    ///
+    fn column_x(&self, display: usize) -> f64 {
+        if display == 0 && self.frozen_first_column {
+            return self.viewport.point.x;
+        }
+        let before: f64 = self.columns[..display].iter().map(|c| c.width).sum();
+        self.viewport.point.x + before - self.horizontal.offset()
+    }
+
+    ///
+    /// Returns the area of the rows, below the header when it stays in place.
+    ///
+    fn rows_area(&self) -> Rectangle {
+        let fixed_header = self.fixed_header_height();
+        let viewport = &self.viewport;
+        Rectangle::new(
+            viewport.point.x,
+            viewport.point.y + fixed_header,
+            viewport.size.width,
+            (viewport.size.height as f64 - fixed_header).max(0.0) as u32,
+        )
+    }
+
+    ///
+    /// Returns the area of the columns that scroll, right of a frozen first column, from `top`
+    /// to `bottom`.
+    ///
+    fn scrolling_area(&self, top: f64, bottom: f64) -> Rectangle {
+        let frozen_width = self.frozen_width();
+        let viewport = &self.viewport;
+        Rectangle::new(
+            viewport.point.x + frozen_width,
+            top,
+            (viewport.size.width as f64 - frozen_width).max(0.0) as u32,
+            (bottom - top).max(0.0) as u32,
+        )
+    }
+
+    fn frozen_area(&self, top: f64, bottom: f64) -> Rectangle {
+        let frozen_width = self.frozen_width().min(self.viewport.size.width as f64);
+        Rectangle::new(
+            self.viewport.point.x,
+            top,
+            frozen_width as u32,
+            (bottom - top).max(0.0) as u32,
+        )
+    }
+
+    ///
+    /// Returns the display positions of the columns that scroll.
+    ///
+    fn scrolling_columns(&self) -> Range<usize> {
+        let first = usize::from(self.frozen_first_column && !self.columns.is_empty());
+        first..self.columns.len()
+    }
+
+    fn row_at(&self, y: f64) -> Option<usize> {
+        if !self.rows_area().contains(self.viewport.point.x, y) {
+            return None;
+        }
+        let offset = y - self.row_y(0);
+        if offset < 0.0 {
+            return None;
+        }
+        let view = (offset / self.row_height) as usize;
+        (view < self.order.len()).then_some(view)
+    }
+
+    ///
+    /// Returns the display position of the column at `x`.
+    ///
+    fn column_at(&self, x: f64) -> Option<usize> {
+        if self.frozen_first_column
+            && !self.columns.is_empty()
+            && x < self.viewport.point.x + self.frozen_width()
+        {
+            return Some(0);
+        }
+        self.scrolling_columns().find(|&display| {
+            let left = self.column_x(display);
+            (left..left + self.columns[display].width).contains(&x)
+        })
+    }
+
+    fn in_header(&self, x: f64, y: f64) -> bool {
+        let top = self.header_y();
+        self.viewport.contains(x, y) && (top..top + self.header_height()).contains(&y)
+    }
+
+    ///
+    /// Returns the display position of the column whose right edge is at `x`, to resize it.
+    ///
+    fn resize_handle_at(&self, x: f64) -> Option<usize> {
+        (0..self.columns.len()).find(|&display| {
+            let right = self.column_x(display) + self.columns[display].width;
+            (x - right).abs() <= RESIZE_MARGIN
+        })
+    }
+
+    fn display_of(&self, column: usize) -> Option<usize> {
+        self.columns.iter().position(|c| c.index == column)
+    }
+
+    fn current_view(&self) -> Option<usize> {
+        self.selected.map(|row| self.view_of[row])
+    }
+
+    ///
+    /// Scroll the current cell into view.
+    ///
+    fn scroll_to_current(&mut self) {
+        if let Some(view) = self.current_view() {
+            let top =
+                view as f64 * self.row_height + self.header_height() - self.fixed_header_height();
+            self.vertical.scroll_into_view(top, top + self.row_height);
+        }
+        if let Some(display) = self.display_of(self.current_column)
+            && !(display == 0 && self.frozen_first_column)
+        {
+            let before: f64 = self.columns[..display].iter().map(|c| c.width).sum();
+            let left = before - self.frozen_width();
+            let right = left + self.columns[display].width;
+            self.horizontal.scroll_into_view(left, right);
+        }
+    }
+
+    ///
+    /// Select a row of the model for the user, and scroll the current cell into view.
+    ///
+    fn select(&mut self, row: usize) {
+        let changed = self.selected != Some(row);
+        self.selected = Some(row);
+        self.scroll_to_current();
+        if changed && let Some(handler) = self.on_selection_changed.as_mut() {
+            handler(row);
+        }
+    }
+
+    ///
+    /// Start editing the current cell, if it is editable, with the text of the cell or with
+    /// `text` typed by the user. Returns `false` when the cell is not editable.
+    ///
+    fn start_edit(&mut self, text: Option<&str>) -> bool {
+        let Some(row) = self.selected else {
+            return false;
+        };
+        let column = self.current_column;
+        if !self.model.is_editable(row, column) {
+            return false;
+        }
+        self.editing = Some((row, column));
+        match text {
+            Some(text) => self.editor.set_text(text),
+            None => {
+                self.editor.set_text(&self.model.cell_text(row, column));
+                self.editor.select_all();
+            }
+        }
+        focus::set_focus(self.editor.focus_id());
+        self.scroll_to_current();
+        self.place_editor();
+        true
+    }
+
+    ///
+    /// Edit the current cell, or call `on_row_activated` with the selected row when the cell
+    /// is not editable.
+    ///
+    fn edit_or_activate(&mut self) {
+        if self.start_edit(None) {
+            return;
+        }
+        if let (Some(row), Some(handler)) = (self.selected, self.on_row_activated.as_mut()) {
+            handler(row);
+        }
+    }
+
+    ///
+    /// Store the edited text in the model, and sort again.
+    ///
+    fn commit_edit(&mut self) {
+        let Some((row, column)) = self.editing.take() else {
+            return;
+        };
+        if row < self.model.row_count() && column < self.model.column_count() {
+            let text = self.editor.text().to_string();
+            self.model.set_cell_text(row, column, &text);
+        }
+        self.end_edit();
+        self.update_order();
+        self.scroll_to_current();
+    }
+
+    fn cancel_edit(&mut self) {
+        self.editing = None;
+        self.end_edit();
+    }
+
+    ///
+    /// Move the focus back from the editor to the `Table`.
+    ///
+    fn end_edit(&mut self) {
+        if focus::is_focused(self.editor.focus_id()) {
+            focus::set_focus(self.focus_id);
+        }
+    }
+
+    ///
+    /// Move the editor over its cell, after scrolling or resizing.
+    ///
+    fn place_editor(&mut self) {
+        let Some((row, column)) = self.editing else {
+            return;
+        };
+        let (Some(&view), Some(display)) = (self.view_of.get(row), self.display_of(column)) else {
+            return;
+        };
+        let (x, y) = (self.column_x(display), self.row_y(view));
+        self.editor.set_position(x, y);
+        self.editor.set_size(
+            self.columns[display].width as usize,
+            self.row_height as usize,
+        );
+    }
+
+    fn toggle_sort(&mut self, display: usize) {
+        let column = self.columns[display].index;
+        let sort = match self.sort {
+            Some((sorted, SortOrder::Ascending)) if sorted == column => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+        };
+        self.sort_by(Some((column, sort)));
+    }
+
+    ///
+    /// Returns the display position a dragged column would move to, when dropped at `x`.
+    ///
+    fn drop_target(&self, display: usize, x: f64) -> usize {
+        (0..self.columns.len())
+            .filter(|&other| other != display)
+            .filter(|&other| self.column_x(other) + self.columns[other].width / 2.0 < x)
+            .count()
+    }
+
+    fn move_column(&mut self, display: usize, x: f64) {
+        let target = self.drop_target(display, x);
+        let column = self.columns.remove(display);
+        self.columns.insert(target, column);
+        self.update_layout();
+    }
+
+    ///
+    /// Handle resizing, moving and sorting by the columns. Returns true if the event was for
+    /// the header.
+    ///
+    fn handle_header_mouse_event(&mut self, input: &MouseInput) -> bool {
+        let (x, y) = (input.position.x, input.position.y);
+        if let Some(drag) = self.header_drag {
+            if !input.is_elsewhere() {
+                self.header_drag = match drag {
+                    HeaderDrag::Resize {
+                        column,
+                        start_x,
+                        start_width,
+                    } => {
+                        self.columns[column].width =
+                            (start_width + x - start_x).max(MIN_COLUMN_WIDTH);
+                        self.update_layout();
+                        Some(drag)
+                    }
+                    HeaderDrag::Press { column, start_x }
+                        if (x - start_x).abs() > DRAG_THRESHOLD =>
+                    {
+                        Some(HeaderDrag::Move { column, x })
+                    }
+                    HeaderDrag::Press { .. } => Some(drag),
+                    HeaderDrag::Move { column, .. } => Some(HeaderDrag::Move { column, x }),
+                };
+            }
+            if input.just_released {
+                match self.header_drag.take() {
+                    Some(HeaderDrag::Press { column, .. }) => self.toggle_sort(column),
+                    Some(HeaderDrag::Move { column, x }) => self.move_column(column, x),
+                    _ => {}
+                }
+            }
+            return true;
+        }
+        if !input.pressed || !self.in_header(x, y) {
+            return false;
+        }
+        if let Some(column) = self.resize_handle_at(x) {
+            self.header_drag = Some(HeaderDrag::Resize {
+                column,
+                start_x: x,
+                start_width: self.columns[column].width,
+            });
+        } else if let Some(column) = self.column_at(x) {
+            self.header_drag = Some(HeaderDrag::Press { column, start_x: x });
+        }
+        true
+    }
+
+    ///
+    /// Handle the keys that move the current cell. Returns false for other keys.
+    ///
+    fn handle_navigation(&mut self, key: &NamedKey) -> bool {
+        let count = self.order.len();
+        if count == 0 || self.columns.is_empty() {
+            return false;
+        }
+        let last = count - 1;
+        let page = ((self.rows_area().size.height as f64 / self.row_height) as usize).max(1);
+        let view = self.current_view();
+        let display = self.display_of(self.current_column).unwrap_or(0);
+        let view = match (key, view) {
+            (NamedKey::ArrowDown, Some(view)) => (view + 1).min(last),
+            (NamedKey::ArrowUp, Some(view)) => view.saturating_sub(1),
+            (NamedKey::PageDown, Some(view)) => (view + page).min(last),
+            (NamedKey::PageUp, Some(view)) => view.saturating_sub(page),
+            (NamedKey::ArrowLeft, view) => {
+                self.current_column = self.columns[display.saturating_sub(1)].index;
+                view.unwrap_or(0)
+            }
+            (NamedKey::ArrowRight, view) => {
+                let next = (display + 1).min(self.columns.len() - 1);
+                self.current_column = self.columns[next].index;
+                view.unwrap_or(0)
+            }
+            (NamedKey::End, _) => last,
+            (
+                NamedKey::ArrowDown
+                | NamedKey::ArrowUp
+                | NamedKey::PageDown
+                | NamedKey::PageUp
+                | NamedKey::Home,
+                _,
+            ) => 0,
+            _ => return false,
+        };
+        self.select(self.order[view]);
+        true
+    }
+
+    ///
+    /// Draw the cells of the display `columns`, in the visible rows within `area`.
+    ///
+    fn draw_cells(&self, context: &mut BufferContext, area: &Rectangle, columns: Range<usize>) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let text_color = style.edit_text_color.value;
+        let grid_color = style.border_color.value;
+        let line_height = context.line_height() as f64;
+        let count = self.model.row_count();
+        let focused = self.is_focused();
+        let (ax, aw) = (area.point.x, area.size.width);
+        let base = self.row_y(0);
+        let first = ((area.point.y - base) / self.row_height).floor().max(0.0) as usize;
+        let end = ((area.point.y + area.size.height as f64 - base) / self.row_height)
+            .ceil()
+            .max(0.0) as usize;
+        context.with_clip(area, |context| {
+            context.fill_rect(
+                ax,
+                area.point.y,
+                aw,
+                area.size.height,
+                style.edit_back_color.value,
+            );
+            for view in first..end.min(self.order.len()) {
+                let row = self.order[view];
+                if row >= count {
+                    continue;
+                }
+                let y = self.row_y(view);
+                let height = self.row_height as u32;
+                if self.selected == Some(row) {
+                    context.blend_rect(ax, y, aw, height, SELECTION_COLOR, SELECTION_ALPHA);
+                } else if self.hovered == Some(view) {
+                    context.blend_rect(ax, y, aw, height, style.fore_color.value, HOVER_ALPHA);
+                }
+                for display in columns.clone() {
+                    let column = self.columns[display];
+                    let x = self.column_x(display);
+                    if x + column.width < ax {
+                        continue;
+                    }
+                    if x > ax + aw as f64 {
+                        break;
+                    }
+                    let cell = Rectangle::new(x, y, column.width as u32, height);
+                    let text = self.model.cell_text(row, column.index);
+                    let text_y = y + (self.row_height - line_height) / 2.0;
+                    context.with_clip(&cell, |context| {
+                        context.draw_text(&text, x + PADDING, text_y, text_color);
+                    });
+                    let right = x + column.width - 1.0;
+                    context.blend_rect(right, y, 1, height, grid_color, GRID_ALPHA);
+                    let current = self.selected == Some(row) && self.current_column == column.index;
+                    if current && focused && self.editing.is_none() {
+                        context.draw_border(
+                            x,
+                            y,
+                            cell.size.width,
+                            height,
+                            1,
+                            style.cursor_color.value,
+                        );
+                    }
+                }
+                let bottom = y + self.row_height - 1.0;
+                context.blend_rect(ax, bottom, aw, 1, grid_color, GRID_ALPHA);
+            }
+        });
+    }
+
+    ///
+    /// Draw the header cells of the display `columns`, within `area`.
+    ///
+    fn draw_header(&self, context: &mut BufferContext, area: &Rectangle, columns: Range<usize>) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let pressed = context.theme.style_for(&ComponentState::Pressed);
+        let line_height = context.line_height() as f64;
+        let (y, height) = (self.header_y(), self.header_height());
+        let (ax, aw) = (area.point.x, area.size.width);
+        context.with_clip(area, |context| {
+            context.fill_rect(ax, y, aw, height as u32, style.back_color.value);
+            for display in columns {
+                let column = self.columns[display];
+                let x = self.column_x(display);
+                if x + column.width < ax || x > ax + aw as f64 {
+                    continue;
+                }
+                let dragged = matches!(
+                    self.header_drag,
+                    Some(HeaderDrag::Move { column: moving, .. }) if moving == display
+                );
+                if dragged {
+                    context.fill_rect(
+                        x,
+                        y,
+                        column.width as u32,
+                        height as u32,
+                        pressed.back_color.value,
+                    );
+                }
+                let cell = Rectangle::new(x, y, column.width as u32, height as u32);
+                let title = self.model.column_title(column.index);
+                let text_y = y + (height - line_height) / 2.0;
+                context.with_clip(&cell, |context| {
+                    context.draw_text(&title, x + PADDING, text_y, style.fore_color.value);
+                    if let Some((sorted, order)) = self.sort
+                        && sorted == column.index
+                    {
+                        let (cx, cy) = (x + column.width - 12.0, y + height / 2.0);
+                        let tip = match order {
+                            SortOrder::Ascending => -3.0,
+                            SortOrder::Descending => 3.0,
+                        };
+                        context.draw_line(cx - 4.0, cy - tip, cx, cy + tip, style.fore_color.value);
+                        context.draw_line(cx, cy + tip, cx + 4.0, cy - tip, style.fore_color.value);
+                    }
+                });
+                let right = x + column.width - 1.0;
+                context.fill_rect(right, y, 1, height as u32, style.border_color.value);
+            }
+            context.fill_rect(ax, y + height - 1.0, aw, 1, style.border_color.value);
+        });
+    }
+
+    ///
+    /// Draw where a dragged column would move to.
+    ///
+    fn draw_drop_marker(&self, context: &mut BufferContext) {
+        let Some(HeaderDrag::Move { column, x }) = self.header_drag else {
+            return;
+        };
+        let target = self.drop_target(column, x);
+        let others: Vec<usize> = (0..self.columns.len()).filter(|&c| c != column).collect();
+        let marker_x = match others.get(target) {
+            Some(&other) => self.column_x(other),
+            None => others.last().map_or(self.viewport.point.x, |&other| {
+                self.column_x(other) + self.columns[other].width
+            }),
+        };
+        let style = context.theme.style_for(&ComponentState::Active);
+        let viewport = &self.viewport;
+        context.with_clip(viewport, |context| {
+            context.fill_rect(
+                marker_x - 1.0,
+                viewport.point.y,
+                2,
+                viewport.size.height,
+                style.cursor_color.value,
+            );
+        });
+    }
+}
+
+impl<M: TableModel> Component for Table<M> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        self.apply_changes();
+        let (x, y) = (input.position.x, input.position.y);
+        self.cursor_over = self.bounds().contains(x, y);
+        if self.editing.is_some() {
+            let editor = self.editor.get_size();
+            let editor_position = self.editor.get_position();
+            let on_editor = Rectangle::new(
+                editor_position.x,
+                editor_position.y,
+                editor.width,
+                editor.height,
+            )
+            .contains(x, y)
+                && self.rows_area().contains(x, y);
+            self.editor.handle_mouse_event(input);
+            if input.pressed {
+                if on_editor {
+                    return;
+                }
+                self.commit_edit();
+            }
+        }
+        if input.pressed {
+            if self.bounds().contains(x, y) {
+                focus::set_focus(self.focus_id);
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+        }
+        if self.vertical.handle_mouse_event(&input) || self.horizontal.handle_mouse_event(&input) {
+            self.place_editor();
+            return;
+        }
+        if self.handle_header_mouse_event(&input) {
+            return;
+        }
+        self.hovered = if self.viewport.contains(x, y) {
+            self.row_at(y)
+        } else {
+            None
+        };
+        if !input.pressed {
+            return;
+        }
+        let (Some(view), Some(display)) = (self.hovered, self.column_at(x)) else {
+            return;
+        };
+        let row = self.order[view];
+        let column = self.columns[display].index;
+        let double_click = self
+            .last_click
+            .is_some_and(|(cell, at)| cell == (row, column) && at.elapsed() <= DOUBLE_CLICK_TIME);
+        self.current_column = column;
+        self.select(row);
+        if double_click {
+            self.last_click = None;
+            self.edit_or_activate();
+        } else {
+            self.last_click = Some(((row, column), Instant::now()));
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+        if !self.cursor_over {
+            return;
+        }
+        let (dx, dy) = match delta {
+            MouseScrollDelta::LineDelta(x, y) => (
+                -*x as f64 * WHEEL_ROWS * self.row_height,
+                -*y as f64 * WHEEL_ROWS * self.row_height,
+            ),
+            MouseScrollDelta::PixelDelta(p) => (-p.x, -p.y),
+        };
+        //
+        // Shift turns the wheel into horizontal scrolling.
+        //
+        let (dx, dy) = if event::modifiers().shift_key() {
+            (dx + dy, 0.0)
+        } else {
+            (dx, dy)
+        };
+        self.apply_changes();
+        self.vertical.scroll_by(dy);
+        self.horizontal.scroll_by(dx);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        self.apply_changes();
+        if self.editing.is_some() {
+            let pressed = event.state == ElementState::Pressed;
+            match &event.logical_key {
+                Key::Named(NamedKey::Enter) if pressed => self.commit_edit(),
+                Key::Named(NamedKey::Escape) if pressed => self.cancel_edit(),
+                _ => self.editor.handle_keyboard_event(event),
+            }
+            return;
+        }
+        if !focus::is_focused(self.focus_id) || event.state != ElementState::Pressed {
+            return;
+        }
+        let modifiers = event::modifiers();
+        match &event.logical_key {
+            Key::Named(key) if self.handle_navigation(key) => {}
+            Key::Named(NamedKey::Enter) => self.edit_or_activate(),
+            Key::Named(NamedKey::F2) => {
+                self.start_edit(None);
+            }
+            Key::Character(text) if !modifiers.control_key() && !modifiers.alt_key() => {
+                self.start_edit(Some(text));
+            }
+            _ => {}
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.apply_changes();
+        if self.vertical.animate(now) | self.horizontal.animate(now) {
+            self.place_editor();
+        }
+    }
+
+    ///
+    /// Keeps the order and width of the columns, the sort order, the selected row and the
+    /// scroll position.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(TableState {
+            columns: self.columns.clone(),
+            sort: self.sort,
+            selected: self.selected,
+            scroll: (self.horizontal.target(), self.vertical.target()),
+        }))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<TableState>() {
+            let count = self.model.column_count();
+            let same_columns = state.columns.len() == count
+                && (0..count).all(|index| state.columns.iter().any(|c| c.index == index));
+            if same_columns {
+                self.columns = state.columns;
+            }
+            self.sort = state.sort.filter(|&(column, _)| column < count);
+            self.update_order();
+            self.selected = state.selected.filter(|&row| row < self.order.len());
+            self.horizontal.set_offset(state.scroll.0);
+            self.vertical.set_offset(state.scroll.1);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = self.is_focused();
+        let style = context.theme.style_for(&ComponentState::Active);
+        let (x, y, w, h) = (
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        );
+        context.fill_rect(x, y, w, h, style.edit_back_color.value);
+
+        //
+        // The columns that scroll are drawn first, then the frozen column over them.
+        //
+        let rows = self.rows_area();
+        let (top, bottom) = (rows.point.y, rows.point.y + rows.size.height as f64);
+        let scrolling = self.scrolling_area(top, bottom);
+        let frozen = self.frozen_area(top, bottom);
+        self.draw_cells(context, &scrolling, self.scrolling_columns());
+        if self.frozen_first_column && !self.columns.is_empty() {
+            self.draw_cells(context, &frozen, 0..1);
+        }
+        if let Some((_, column)) = self.editing {
+            let editor_area = if self.frozen_first_column && self.display_of(column) == Some(0) {
+                frozen.clone()
+            } else {
+                scrolling.clone()
+            };
+            context.with_clip(&editor_area, |context| self.editor.draw(context));
+        }
+
+        //
+        // The header is drawn over the rows, which scroll under it.
+        //
+        let header_top = self.header_y().max(self.viewport.point.y);
+        let header_bottom = self.header_y() + self.header_height();
+        if header_bottom > header_top {
+            let scrolling = self.scrolling_area(header_top, header_bottom);
+            self.draw_header(context, &scrolling, self.scrolling_columns());
+            if self.frozen_first_column && !self.columns.is_empty() {
+                let frozen = self.frozen_area(header_top, header_bottom);
+                self.draw_header(context, &frozen, 0..1);
+            }
+        }
+        if self.frozen_first_column && !self.columns.is_empty() {
+            let edge = self.viewport.point.x + self.frozen_width() - 1.0;
+            let viewport_height = self.viewport.size.height;
+            context.with_clip(&self.viewport, |context| {
+                context.fill_rect(edge, y, 1, viewport_height, style.border_color.value);
+            });
+        }
+        self.draw_drop_marker(context);
+
+        self.vertical.draw(context);
+        self.horizontal.draw(context);
+        if self.vertical.is_shown() && self.horizontal.is_shown() {
+            let corner_x = self.viewport.point.x + self.viewport.size.width as f64;
+            let corner_y = self.viewport.point.y + self.viewport.size.height as f64;
+            context.fill_rect(
+                corner_x,
+                corner_y,
+                SCROLLBAR_WIDTH,
+                SCROLLBAR_WIDTH,
+                style.back_color.value,
+            );
+        }
+
+        let border = if focused {
+            style.cursor_color.value
+        } else {
+            style.border_color.value
+        };
+        context.draw_border(x, y, w, h, 1, border);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -6,10 +6,12 @@
 
 use crate::component::style::ColorFactor;
 use crate::component::{Component, ComponentState};
-use crate::event::MouseInput;
+use crate::event::{self, MouseInput};
+use crate::event::focus::{self, FocusId};
 use crate::geometry::{Point, Size};
 use crate::view::BufferContext;
-use rusttype::{PositionedGlyph, Scale, point};
+use std::any::Any;
+use rusttype::Scale;
 use winit::keyboard::{Key, NamedKey};
 
 ///
@@ -25,7 +27,7 @@ pub struct TextArea {
     pub cursor_index: usize,
     pub position: Point,
     pub size: Size,
-    pub focused: bool,
+    focus_id: FocusId,
     component_state: ComponentState,
     visible_scrolling_offset: f32,
     dragging_scrollbar: bool,
@@ -35,6 +37,15 @@ pub struct TextArea {
     scrollbar_width: usize,
 }
 
+///
+/// The state of a `TextArea` kept by `Component::save_state()`.
+///
+struct TextAreaState {
+    text: String,
+    cursor_index: usize,
+    visible_scrolling_offset: f32,
+}
+
 impl Clone for TextArea {
     fn clone(&self) -> Self {
         Self {
@@ -42,7 +53,7 @@ impl Clone for TextArea {
             cursor_index: self.cursor_index.clone(),
             position: self.position.clone(),
             size: self.size.clone(),
-            focused: self.focused.clone(),
+            focus_id: FocusId::for_text_input(),
             component_state: self.component_state.clone(),
             visible_scrolling_offset: self.visible_scrolling_offset.clone(),
             dragging_scrollbar: self.dragging_scrollbar.clone(),
@@ -64,7 +75,7 @@ impl TextArea {
                 width: 200,
                 height: 50,
             },
-            focused: false,
+            focus_id: FocusId::for_text_input(),
             component_state: ComponentState::Active,
             visible_scrolling_offset: 0.0,
             dragging_scrollbar: false,
@@ -109,6 +120,20 @@ impl TextArea {
         self.handle_event();
     }
 
+    ///
+    /// Returns the `FocusId` of the `TextArea`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    ///
+    /// Returns true if the `TextArea` has keyboard focus.
+    ///
+    pub fn is_focused(&self) -> bool {
+        focus::is_focused(self.focus_id)
+    }
+
     ///
     /// Checks if the specified `px` and `py` coordinates are inside the
     /// bounding box of the `TextArea`.
@@ -133,28 +158,21 @@ impl TextArea {
     }
 
     fn draw_background(&self, context: &mut BufferContext) {
-        let px = self.position.x as usize;
-        let py = self.position.y as usize;
-        let w = self.size.width;
-        let h = self.size.height;
-        let screen_w = context.screen_size.width as usize;
-
-        // Background
-        for y in 0..h {
-            for x in 0..w {
-                let offset = (py + y as usize) * screen_w + (px + x as usize);
-                if offset < context.buffer.len() {
-                    //
-                    // TODO: Move to dedicated style variables
-                    //
-                    context.buffer[offset] = if self.focused {
-                        0xFFEEEEFF // background when focused
-                    } else {
-                        0xFFFFFFFF // background when not focused
-                    };
-                }
-            }
-        }
+        //
+        // TODO: Move to dedicated style variables
+        //
+        let background_color = if self.is_focused() {
+            0xFFEEEEFF // background when focused
+        } else {
+            0xFFFFFFFF // background when not focused
+        };
+        context.fill_rect(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+            background_color,
+        );
     }
 
     ///
@@ -227,108 +245,35 @@ impl TextArea {
                 continue;
             }
 
-            let glyphs: Vec<PositionedGlyph> = context
-                .font
-                .layout(
-                    line,
-                    font_scale,
-                    point(start_x as f32, base_y + line_height * i as f32),
-                )
-                .collect();
-
             let text_color = context
                 .theme
                 .style_for(&self.component_state)
                 .edit_text_color
                 .value;
-
-            for glyph in glyphs {
-                if let Some(bb) = glyph.pixel_bounding_box() {
-                    glyph.draw(|gx, gy, v| {
-                        let x = gx as i32 + bb.min.x;
-                        let y = gy as i32 + bb.min.y;
-                        if x >= 0
-                            && x < context.screen_size.width as i32
-                            && y >= 0
-                            && y < context.screen_size.height as i32
-                        {
-                            let idx = y as usize * context.screen_size.width as usize + x as usize;
-                            if idx < context.buffer.len() {
-                                context.buffer[idx] =
-                                    TextArea::basic_aa(context.buffer[idx], text_color, v);
-                            }
-                        }
-                    });
-                }
-            }
+            context.draw_text(
+                line,
+                start_x as f64,
+                (line_y - v_metrics.ascent) as f64,
+                text_color,
+            );
         }
 
         lines.iter().count()
     }
 
-    fn basic_aa(bg: u32, fg: u32, alpha: f32) -> u32 {
-        let inv = 1.0 - alpha;
-
-        let br = ((bg >> 16) & 0xFF) as f32;
-        let bg_ = ((bg >> 8) & 0xFF) as f32;
-        let bb = (bg & 0xFF) as f32;
-
-        let fr = ((fg >> 16) & 0xFF) as f32;
-        let fg_ = ((fg >> 8) & 0xFF) as f32;
-        let fb = (fg & 0xFF) as f32;
-
-        let r = (br * inv + fr * alpha).round() as u32;
-        let g = (bg_ * inv + fg_ * alpha).round() as u32;
-        let b = (bb * inv + fb * alpha).round() as u32;
-
-        (r << 16) | (g << 8) | b
-    }
-
     ///
-    /// Draw the Button border.
+    /// Draw the TextArea border.
     ///
     fn draw_border(&self, context: &mut BufferContext) {
-        // let border_color = if self.focused {
-        //     self.component_style_focused.border_color.value
-        // } else {
-        //     self.component_style.border_color.value
-        // };
-        // let bw = if self.focused {
-        //     self.component_style_focused.border_width
-        // } else {
-        //     self.component_style.border_width
-        // };
-        let border_width = context.theme.style_for(&self.component_state).border_width;
-        let border_color = context
-            .theme
-            .style_for(&self.component_state)
-            .border_color
-            .value;
-        let x0 = self.position.x as usize;
-        let y0 = self.position.y as usize;
-        let x1 = self.position.x as usize + self.size.width as usize;
-        let y1 = self.position.y as usize + self.size.height as usize;
-        let screen_width = context.screen_size.width as usize;
-        let screen_height = context.screen_size.height as usize;
-        let clipped_x0 = x0.min(screen_width);
-        let clipped_y0 = y0.min(screen_height);
-        let clipped_x1 = x1.min(screen_width);
-        let clipped_y1 = y1.min(screen_height);
-        for y in clipped_y0..clipped_y1 {
-            for x in clipped_x0..clipped_x1 {
-                let is_top = y < y0 + border_width;
-                let is_bottom = y >= y1.saturating_sub(border_width);
-                let is_left = x < x0 + border_width;
-                let is_right = x >= x1.saturating_sub(border_width);
-
-                if is_top || is_bottom || is_left || is_right {
-                    let idx = y * screen_width + x;
-                    if idx < context.buffer.len() {
-                        context.buffer[idx] = border_color;
-                    }
-                }
-            }
-        }
+        let style = context.theme.style_for(&self.component_state);
+        context.draw_border(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+            style.border_width,
+            style.border_color.value,
+        );
     }
 }
 
@@ -345,10 +290,10 @@ impl Component for TextArea {
             // );
             if input.pressed {
                 if self.contains(input.position.x, input.position.y) {
-                    self.focused = true;
+                    focus::set_focus(self.focus_id);
                     self.component_state = ComponentState::Focused;
                 } else {
-                    self.focused = false;
+                    focus::clear_focus(self.focus_id);
                     self.component_state = ComponentState::Active;
                 }
                 self.dragging_scrollbar =
@@ -375,10 +320,10 @@ impl Component for TextArea {
             //
             if input.just_released {
                 if self.contains(input.position.x, input.position.y) {
-                    self.focused = true;
+                    focus::set_focus(self.focus_id);
                     self.component_state = ComponentState::Focused;
                 } else {
-                    self.focused = false;
+                    focus::clear_focus(self.focus_id);
                     self.component_state = ComponentState::Active;
                 }
                 //
@@ -395,7 +340,7 @@ impl Component for TextArea {
         delta: &winit::event::MouseScrollDelta,
         _phase: &winit::event::TouchPhase,
     ) {
-        if self.focused {
+        if self.is_focused() {
             match delta {
                 winit::event::MouseScrollDelta::LineDelta(_x, y) => {
                     self.visible_scrolling_offset =
@@ -409,8 +354,12 @@ impl Component for TextArea {
     }
 
     fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
-        if self.focused && event.state == winit::event::ElementState::Pressed {
+        if self.is_focused() && event.state == winit::event::ElementState::Pressed {
             match &event.logical_key {
+                //
+                // Alt + key is a shortcut, such as a `Label` mnemonic, rather than text.
+                //
+                Key::Character(_) if event::modifiers().alt_key() => {}
                 Key::Character(s) => {
                     self.insert_str(s);
                 }
@@ -449,6 +398,25 @@ impl Component for TextArea {
         }
     }
 
+    ///
+    /// Keeps the text, cursor and scroll position.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(TextAreaState {
+            text: self.text.clone(),
+            cursor_index: self.cursor_index,
+            visible_scrolling_offset: self.visible_scrolling_offset,
+        }))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<TextAreaState>() {
+            self.text = state.text;
+            self.cursor_index = state.cursor_index.min(self.text.len());
+            self.visible_scrolling_offset = state.visible_scrolling_offset;
+        }
+    }
+
     fn draw(&self, context: &mut BufferContext) {
         // let px = self.position.x as usize;
         // let py = self.position.y as usize;
@@ -485,30 +453,27 @@ impl Component for TextArea {
         let scrollbar_color_track = back_color.lighten(ColorFactor::double()).value;
 
         // Draw scrollbar track
-        for y in 0..track_h {
-            for x in 0..self.scrollbar_width {
-                let idx = (area_y + y) * context.screen_size.width as usize + (track_x + x);
-                if idx < context.buffer.len() {
-                    context.buffer[idx] = scrollbar_color_track; //0xFFE0E0E0;
-                }
-            }
-        }
+        context.fill_rect(
+            track_x as f64,
+            area_y as f64,
+            self.scrollbar_width as u32,
+            track_h as u32,
+            scrollbar_color_track,
+        );
 
         //
         // Draw scrollbar thumb
         //
-        let thumb_top = thumb_y_offset.round() as usize;
-        for y in 0..(thumb_height as usize) {
-            let ty = thumb_top + y;
-            if ty >= track_h {
-                break;
-            }
-            for x in 0..self.scrollbar_width {
-                let idx = (area_y + ty) * context.screen_size.width as usize + (track_x + x);
-                if idx < context.buffer.len() {
-                    context.buffer[idx] = scrollbar_color_thumb; //0x00FF0000;
-                }
-            }
+        let thumb_top = thumb_y_offset.round().max(0.0) as usize;
+        if thumb_top < track_h {
+            let thumb_h = (thumb_height as usize).min(track_h - thumb_top);
+            context.fill_rect(
+                track_x as f64,
+                (area_y + thumb_top) as f64,
+                self.scrollbar_width as u32,
+                thumb_h as u32,
+                scrollbar_color_thumb,
+            );
         }
 
         //
@@ -516,7 +481,7 @@ impl Component for TextArea {
         //
         // TODO: Code is not wrapping. Need to move this to text drawing function?
         //
-        if self.focused {
+        if self.is_focused() {
             // let cx = px + self.cursor_index * 6;
             // let cy = py + 10;
             // if cx < screen_w && cy < screen_h {
@@ -0,0 +1,623 @@
+//!
+//! # Structura Component: TextField.
+//!
+//! A single-line editable text `Component`, for forms.
+//!
+//! - Scrolls horizontally to keep the caret visible.
+//! - Placeholder text, shown while the field is empty.
+//! - An optional maximum length, in characters.
+//! - Password mode, showing a mask character in place of each character.
+//! - Optionally selects all text when it gains focus.
+//! - `Enter` calls `on_submit`.
+//!
+//! Editing keys: `Left` / `Right` / `Home` / `End` move the caret, with `Shift` to select;
+//! `Backspace` / `Delete`; `Ctrl+A` selects all; `Ctrl+C` / `Ctrl+X` / `Ctrl+V` copy, cut and
+//! paste (copy and cut are disabled in password mode).
+//!
+
+use crate::clipboard;
+use crate::component::metrics;
+use crate::component::{Component, ComponentState};
+use crate::event::focus::{self, FocusId};
+use crate::event::{self, MouseInput};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::{BufferContext, blend_color};
+use std::any::Any;
+use winit::event::ElementState;
+use winit::keyboard::{Key, NamedKey};
+
+/// Space between the border and the text, on each side.
+const PADDING: f64 = 8.0;
+
+/// Opacity of the placeholder text, blended over the background.
+const PLACEHOLDER_ALPHA: f32 = 0.45;
+
+/// Color of the highlight behind selected text.
+const SELECTION_COLOR: u32 = 0xFF3399FF;
+
+/// Opacity of the selection highlight, from `0.0` to `1.0`.
+const SELECTION_ALPHA: f32 = 0.4;
+
+/// Width of the caret, in pixels.
+const CARET_WIDTH: u32 = 2;
+
+/// Handler called with the text of a `TextField`.
+type TextHandler = Box<dyn FnMut(&str)>;
+
+///
+/// The state of a `TextField` kept by `Component::save_state()`.
+///
+struct TextFieldState {
+    text: String,
+    caret: usize,
+}
+
+///
+/// TextField control for editing a single line of text.
+///
+pub struct TextField {
+    position: Point,
+    size: Size,
+    text: String,
+    placeholder: String,
+    max_length: Option<usize>,
+    password_mask: Option<char>,
+    select_all_on_focus: bool,
+
+    /// Caret position, in characters.
+    caret: usize,
+
+    /// The other end of the selection, in characters, when text is selected.
+    anchor: Option<usize>,
+
+    /// Horizontal scroll offset of the text, in pixels.
+    scroll_x: f64,
+    selecting: bool,
+    had_focus: bool,
+    focus_id: FocusId,
+    component_state: ComponentState,
+    on_text_change: Option<TextHandler>,
+    on_submit: Option<TextHandler>,
+}
+
+impl Default for TextField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextField {
+    ///
+    /// Constructor, sized for one line of text.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 300,
+                height: (metrics::line_height() as f64 + PADDING * 2.0).ceil() as u32,
+            },
+            text: String::new(),
+            placeholder: String::new(),
+            max_length: None,
+            password_mask: None,
+            select_all_on_focus: false,
+            caret: 0,
+            anchor: None,
+            scroll_x: 0.0,
+            selecting: false,
+            had_focus: false,
+            focus_id: FocusId::for_text_input(),
+            component_state: ComponentState::Active,
+            on_text_change: None,
+            on_submit: None,
+        }
+    }
+
+    ///
+    /// Text shown in a dimmed color while the field is empty.
+    ///
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_string();
+        self
+    }
+
+    ///
+    /// Limit the text to `max_length` characters. Typing and pasting stop at the limit.
+    ///
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self.truncate_to_max_length();
+        self
+    }
+
+    ///
+    /// Show `mask` in place of each character, e.g. `'•'` for passwords.
+    ///
+    pub fn password(mut self, mask: char) -> Self {
+        self.password_mask = Some(mask);
+        self
+    }
+
+    ///
+    /// Select all text whenever the field gains focus.
+    ///
+    pub fn select_all_on_focus(mut self, select_all_on_focus: bool) -> Self {
+        self.select_all_on_focus = select_all_on_focus;
+        self
+    }
+
+    ///
+    /// Called with the new text whenever the user edits it.
+    ///
+    pub fn on_text_change<F: FnMut(&str) + 'static>(mut self, f: F) -> Self {
+        self.on_text_change = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Called with the text when `Enter` is pressed.
+    ///
+    pub fn on_submit<F: FnMut(&str) + 'static>(mut self, f: F) -> Self {
+        self.on_submit = Some(Box::new(f));
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    ///
+    /// Replace the text, moving the caret to the end. Does not call `on_text_change`.
+    ///
+    pub fn set_text(&mut self, text: &str) {
+        self.text = single_line(text);
+        self.truncate_to_max_length();
+        self.caret = self.char_count();
+        self.anchor = None;
+        self.ensure_caret_visible();
+    }
+
+    ///
+    /// Returns the `FocusId` of the `TextField`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    pub fn is_focused(&self) -> bool {
+        focus::is_focused(self.focus_id)
+    }
+
+    ///
+    /// Select all text, leaving the caret at the end.
+    ///
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.caret = self.char_count();
+        self.ensure_caret_visible();
+    }
+
+    ///
+    /// Returns the selected text, or an empty string.
+    ///
+    pub fn selected_text(&self) -> String {
+        match self.selection() {
+            Some((start, end)) => self.text.chars().skip(start).take(end - start).collect(),
+            None => String::new(),
+        }
+    }
+
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map(|(index, _)| index)
+            .unwrap_or(self.text.len())
+    }
+
+    fn truncate_to_max_length(&mut self) {
+        if let Some(max_length) = self.max_length {
+            let end = self.byte_index(max_length);
+            self.text.truncate(end);
+            self.caret = self.caret.min(max_length);
+        }
+    }
+
+    ///
+    /// Returns the text as it is drawn: masked in password mode.
+    ///
+    fn display_text(&self) -> String {
+        match self.password_mask {
+            Some(mask) => std::iter::repeat_n(mask, self.char_count()).collect(),
+            None => self.text.clone(),
+        }
+    }
+
+    ///
+    /// Returns the x offset of the character at `index` from the start of the text.
+    ///
+    fn offset_of(&self, index: usize) -> f64 {
+        let prefix: String = self.display_text().chars().take(index).collect();
+        metrics::text_width(&prefix) as f64
+    }
+
+    fn content_width(&self) -> f64 {
+        (self.size.width as f64 - PADDING * 2.0).max(0.0)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    ///
+    /// Scroll horizontally so that the caret is inside the visible area, and no space is
+    /// wasted after the end of the text.
+    ///
+    fn ensure_caret_visible(&mut self) {
+        let caret_x = self.offset_of(self.caret);
+        let width = self.content_width();
+        if caret_x - self.scroll_x > width {
+            self.scroll_x = caret_x - width;
+        }
+        if caret_x < self.scroll_x {
+            self.scroll_x = caret_x;
+        }
+        let text_width = self.offset_of(self.char_count()) + CARET_WIDTH as f64;
+        self.scroll_x = self.scroll_x.min((text_width - width).max(0.0));
+    }
+
+    ///
+    /// Returns the character index closest to the window x coordinate `x`.
+    ///
+    fn index_at(&self, x: f64) -> usize {
+        let target = x - (self.position.x + PADDING) + self.scroll_x;
+        let mut offset = 0.0;
+        for (i, c) in self.display_text().chars().enumerate() {
+            let width = metrics::text_width(&c.to_string()) as f64;
+            if target < offset + width / 2.0 {
+                return i;
+            }
+            offset += width;
+        }
+        self.char_count()
+    }
+
+    ///
+    /// Returns the selection as an ordered `(start, end)` range, if it is not empty.
+    ///
+    fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.anchor?;
+        let range = (anchor.min(self.caret), anchor.max(self.caret));
+        (range.0 < range.1).then_some(range)
+    }
+
+    ///
+    /// Remove the selected text, if any. Returns true if text was removed.
+    ///
+    fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.selection() else {
+            self.anchor = None;
+            return false;
+        };
+        let (start_byte, end_byte) = (self.byte_index(start), self.byte_index(end));
+        self.text.replace_range(start_byte..end_byte, "");
+        self.caret = start;
+        self.anchor = None;
+        true
+    }
+
+    ///
+    /// Replace the selection with `text`, cut short at the maximum length.
+    ///
+    fn insert(&mut self, text: &str) {
+        let mut changed = self.delete_selection();
+        let text = single_line(text);
+        let room = self
+            .max_length
+            .map(|max_length| max_length.saturating_sub(self.char_count()))
+            .unwrap_or(usize::MAX);
+        let text: String = text.chars().take(room).collect();
+        if !text.is_empty() {
+            let byte = self.byte_index(self.caret);
+            self.text.insert_str(byte, &text);
+            self.caret += text.chars().count();
+            changed = true;
+        }
+        if changed {
+            self.text_changed();
+        }
+    }
+
+    ///
+    /// Delete the selection, or the character before (`Backspace`) or after (`Delete`) the
+    /// caret.
+    ///
+    fn delete(&mut self, forward: bool) {
+        if !self.delete_selection() {
+            let range = if forward {
+                (self.caret < self.char_count()).then_some(self.caret..self.caret + 1)
+            } else {
+                (self.caret > 0).then(|| self.caret - 1..self.caret)
+            };
+            let Some(range) = range else {
+                return;
+            };
+            let (start, end) = (self.byte_index(range.start), self.byte_index(range.end));
+            self.text.replace_range(start..end, "");
+            self.caret = range.start;
+        }
+        self.text_changed();
+    }
+
+    fn text_changed(&mut self) {
+        self.ensure_caret_visible();
+        if let Some(handler) = self.on_text_change.as_mut() {
+            handler(&self.text);
+        }
+    }
+
+    ///
+    /// Move the caret to `index`, extending the selection when `select` is true.
+    ///
+    fn move_caret(&mut self, index: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
+        }
+        self.caret = index.min(self.char_count());
+        self.ensure_caret_visible();
+    }
+
+    ///
+    /// Select all text if the field has just gained focus, and `select_all_on_focus` is set.
+    ///
+    fn check_focus_gained(&mut self) {
+        let has_focus = self.is_focused();
+        if has_focus && !self.had_focus && self.select_all_on_focus {
+            self.select_all();
+        }
+        self.had_focus = has_focus;
+    }
+
+    fn handle_shortcut(&mut self, key: &str) {
+        match key {
+            "a" => self.select_all(),
+            "c" if self.password_mask.is_none() => {
+                let selected = self.selected_text();
+                if !selected.is_empty() {
+                    clipboard::set_text(&selected);
+                }
+            }
+            "x" if self.password_mask.is_none() => {
+                let selected = self.selected_text();
+                if !selected.is_empty() {
+                    clipboard::set_text(&selected);
+                    self.delete_selection();
+                    self.text_changed();
+                }
+            }
+            "v" => {
+                if let Some(text) = clipboard::text() {
+                    self.insert(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Component for TextField {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        if self.component_state == ComponentState::Disabled {
+            return;
+        }
+        let (x, y) = (input.position.x, input.position.y);
+        let inside = self.bounds().contains(x, y);
+        self.component_state = if inside {
+            ComponentState::Hovered
+        } else {
+            ComponentState::Active
+        };
+
+        if input.pressed && !self.selecting {
+            if inside {
+                focus::set_focus(self.focus_id);
+                let gaining_focus = !self.had_focus;
+                self.had_focus = true;
+                if gaining_focus && self.select_all_on_focus {
+                    self.select_all();
+                } else {
+                    let select = event::modifiers().shift_key();
+                    self.move_caret(self.index_at(x), select);
+                    self.selecting = true;
+                }
+            } else {
+                focus::clear_focus(self.focus_id);
+                self.had_focus = false;
+                self.anchor = None;
+            }
+        } else if self.selecting {
+            self.move_caret(self.index_at(x), true);
+        }
+        if input.just_released {
+            self.selecting = false;
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        _delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        self.check_focus_gained();
+        if !self.is_focused() || event.state != ElementState::Pressed {
+            return;
+        }
+        let modifiers = event::modifiers();
+        let shift = modifiers.shift_key();
+        match &event.logical_key {
+            //
+            // Alt + key is a shortcut, such as a `Label` mnemonic, rather than text.
+            //
+            Key::Character(_) if modifiers.alt_key() => {}
+            Key::Character(key) if modifiers.control_key() => {
+                self.handle_shortcut(&key.to_lowercase());
+            }
+            Key::Character(text) => self.insert(text),
+            Key::Named(NamedKey::Space) => self.insert(" "),
+            Key::Named(NamedKey::Backspace) => self.delete(false),
+            Key::Named(NamedKey::Delete) => self.delete(true),
+            Key::Named(NamedKey::ArrowLeft) => {
+                let index = match self.selection() {
+                    Some((start, _)) if !shift => start,
+                    _ => self.caret.saturating_sub(1),
+                };
+                self.move_caret(index, shift);
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                let index = match self.selection() {
+                    Some((_, end)) if !shift => end,
+                    _ => self.caret + 1,
+                };
+                self.move_caret(index, shift);
+            }
+            Key::Named(NamedKey::Home) => self.move_caret(0, shift),
+            Key::Named(NamedKey::End) => self.move_caret(self.char_count(), shift),
+            Key::Named(NamedKey::Enter) => {
+                if let Some(handler) = self.on_submit.as_mut() {
+                    handler(&self.text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ///
+    /// Keeps the text and caret position.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(TextFieldState {
+            text: self.text.clone(),
+            caret: self.caret,
+        }))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<TextFieldState>() {
+            self.text = state.text;
+            self.truncate_to_max_length();
+            self.caret = state.caret.min(self.char_count());
+            self.anchor = None;
+            self.ensure_caret_visible();
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = self.is_focused();
+        let state = if focused {
+            ComponentState::Focused
+        } else {
+            self.component_state.clone()
+        };
+        let style = context.theme.style_for(&state);
+        let (x, y, w, h) = (
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        );
+        context.fill_rect(x, y, w, h, style.edit_back_color.value);
+
+        let line_height = context.line_height() as f64;
+        let text_x = x + PADDING - self.scroll_x;
+        let text_y = y + (h as f64 - line_height) / 2.0;
+        let content = Rectangle::new(
+            x + PADDING,
+            y,
+            self.content_width().ceil() as u32 + CARET_WIDTH,
+            h,
+        );
+        context.with_clip(&content, |context| {
+            if self.text.is_empty() {
+                let color = blend_color(
+                    style.edit_back_color.value,
+                    style.edit_text_color.value,
+                    PLACEHOLDER_ALPHA,
+                );
+                context.draw_text(&self.placeholder, x + PADDING, text_y, 0xFF000000 | color);
+            } else {
+                if focused && let Some((start, end)) = self.selection() {
+                    let from = self.offset_of(start);
+                    let to = self.offset_of(end);
+                    context.blend_rect(
+                        text_x + from,
+                        text_y,
+                        (to - from).ceil() as u32,
+                        line_height as u32,
+                        SELECTION_COLOR,
+                        SELECTION_ALPHA,
+                    );
+                }
+                context.draw_text(
+                    &self.display_text(),
+                    text_x,
+                    text_y,
+                    style.edit_text_color.value,
+                );
+            }
+            if focused {
+                let caret_x = text_x + self.offset_of(self.caret);
+                context.fill_rect(
+                    caret_x,
+                    text_y,
+                    CARET_WIDTH,
+                    line_height as u32,
+                    style.cursor_color.value,
+                );
+            }
+        });
+
+        context.draw_border(x, y, w, h, style.border_width, style.border_color.value);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.ensure_caret_visible();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
+
+///
+/// Replace line breaks with spaces, for text typed or pasted into a single line.
+///
+fn single_line(text: &str) -> String {
+    text.replace("\r\n", " ").replace(['\r', '\n'], " ")
+}
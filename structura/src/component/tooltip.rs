@@ -1,34 +1,88 @@
 //!
-//! # Structura Component: TextArea.
+//! # Structura Component: Tooltip.
 //!
-//! A toopltip that shows text and floats over another `Component`.
+//! A tooltip that shows text and floats over another `Component`.
+//!
+//! Wrap any `Component` in a `WithTooltip` to attach tooltip text to it:
+//!
+//! ```ignore
+//! let save = WithTooltip::new(Button::default().set_text("Save".to_string()), "Save the file")
+//!     .delay(Duration::from_millis(800));
+//! ```
+//!
+//! - The tooltip appears once the cursor has rested over the `Component` for the hover
+//!   delay, below and to the right of the cursor, or above it when there is no room below. It
+//!   always stays inside the window.
+//! - It hides when the cursor leaves the `Component`, and on a click or key press. After a
+//!   click or key press, it does not appear again until the cursor leaves and comes back.
+//! - It is drawn with the theme's text colors and border.
 //!
 
-use crate::component::Component;
+use crate::component::metrics;
+use crate::component::{Component, ComponentState};
+use crate::container::Container;
 use crate::event::MouseInput;
-use crate::geometry::{Point, Size};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::layer::{self, Layer, LayerId, Placement};
+use crate::timer::{self, TimerId};
 use crate::view::BufferContext;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::event::ElementState;
+
+///
+/// Default time the cursor rests over a `Component` before its tooltip appears.
+///
+pub const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
+
+/// Default offset of the tooltip from the cursor: below the pointer, and level with it.
+const CURSOR_OFFSET: Point = Point { x: 0.0, y: 24.0 };
+
+/// Space between the tooltip and the cursor when the tooltip is above the cursor.
+const CURSOR_GAP_ABOVE: f64 = 4.0;
+
+/// Space between the border and the text, on each side.
+const PADDING: f64 = 6.0;
 
+///
+/// A box with one or more lines of text, shown in a layer by `WithTooltip`.
+///
 pub struct Tooltip {
     text: String,
     position: Point,
     size: Size,
-    visible: bool,
 }
 
 impl Tooltip {
-    pub fn new(text: String, position: Point, size: Size) -> Self {
+    ///
+    /// Constructor, sized to fit `text`.
+    ///
+    pub fn new(text: &str) -> Self {
+        let widest = text
+            .lines()
+            .map(|line| metrics::text_width(line) as f64)
+            .fold(0.0, f64::max);
+        let lines = text.lines().count().max(1);
         Self {
-            text,
-            position,
-            size,
-            visible: true,
+            text: text.to_string(),
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: (widest + PADDING * 2.0).ceil() as u32,
+                height: (lines as f64 * metrics::line_height() as f64 + PADDING * 2.0).ceil()
+                    as u32,
+            },
         }
     }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
 }
 
 impl Component for Tooltip {
-    fn handle_mouse_event(&mut self, input: MouseInput) {}
+    fn handle_mouse_event(&mut self, _input: MouseInput) {}
 
     fn handle_mouse_wheel_event(
         &mut self,
@@ -40,19 +94,16 @@ impl Component for Tooltip {
     fn handle_keyboard_event(&mut self, _event: &winit::event::KeyEvent) {}
 
     fn draw(&self, context: &mut BufferContext) {
-        if !self.visible {
-            return;
+        let style = context.theme.style_for(&ComponentState::Active);
+        let (x, y) = (self.position.x, self.position.y);
+        let (w, h) = (self.size.width, self.size.height);
+        context.fill_rect(x, y, w, h, style.edit_back_color.value);
+        context.draw_border(x, y, w, h, 1, style.border_color.value);
+        let line_height = context.line_height() as f64;
+        for (i, line) in self.text.lines().enumerate() {
+            let line_y = y + PADDING + i as f64 * line_height;
+            context.draw_text(line, x + PADDING, line_y, style.edit_text_color.value);
         }
-
-        //
-        // TODO: Add utility functions to BufferContext
-        //
-
-        // Draw rectangle background
-        //context.fill_rect(self.position.x, self.position.y, self.size.w, self.size.h, 0xFF000000);
-
-        // Draw the tooltip text
-        //context.draw_text(&self.text, self.position.x + 5.0, self.position.y + 5.0, 0xFFFFFFFF);
     }
 
     fn set_position(&mut self, x: f64, y: f64) {
@@ -74,3 +125,243 @@ impl Component for Tooltip {
         self.size.clone()
     }
 }
+
+///
+/// Tooltip state shared with the hover delay timer.
+///
+struct TooltipState {
+    text: RefCell<String>,
+    offset: Cell<Point>,
+    cursor: Cell<Point>,
+    timer: Cell<Option<TimerId>>,
+    layer: Cell<Option<LayerId>>,
+}
+
+impl TooltipState {
+    ///
+    /// Open the tooltip layer next to the cursor. Called by the hover delay timer.
+    ///
+    fn show(&self) {
+        self.timer.set(None);
+        let text = self.text.borrow();
+        if text.is_empty() || self.layer.get().is_some() {
+            return;
+        }
+        let cursor = self.cursor.get();
+        let offset = self.offset.get();
+        //
+        // Anchored below a rectangle from just above the cursor to the offset below it, so
+        // that the tooltip flips to just above the cursor when there is no room below.
+        //
+        let anchor = Rectangle::new(
+            cursor.x + offset.x,
+            cursor.y - CURSOR_GAP_ABOVE,
+            0,
+            (offset.y + CURSOR_GAP_ABOVE).max(0.0) as u32,
+        );
+        let mut tooltip = Tooltip::new(&text);
+        tooltip.set_position(cursor.x + offset.x, cursor.y + offset.y);
+        let layer = Layer::new(Box::new(tooltip))
+            .anchored(anchor, Placement::Below)
+            .interactive(false);
+        self.layer.set(Some(layer::open(layer)));
+    }
+
+    ///
+    /// Cancel the hover delay timer, and close the tooltip layer if it is open.
+    ///
+    fn hide(&self) {
+        if let Some(timer) = self.timer.take() {
+            timer::cancel(timer);
+        }
+        if let Some(layer) = self.layer.take() {
+            layer::close(layer);
+        }
+    }
+}
+
+///
+/// Wraps a `Component` or `ContainerComponent`, showing tooltip text when the cursor rests
+/// over it. Everything else is passed straight through.
+///
+pub struct WithTooltip<T> {
+    inner: T,
+    state: Rc<TooltipState>,
+    delay: Duration,
+    hovering: bool,
+    suppressed: bool,
+}
+
+impl<T: Component> WithTooltip<T> {
+    ///
+    /// Constructor.
+    ///
+    pub fn new(inner: T, text: &str) -> Self {
+        Self {
+            inner,
+            state: Rc::new(TooltipState {
+                text: RefCell::new(text.to_string()),
+                offset: Cell::new(CURSOR_OFFSET),
+                cursor: Cell::new(Point { x: 0.0, y: 0.0 }),
+                timer: Cell::new(None),
+                layer: Cell::new(None),
+            }),
+            delay: TOOLTIP_DELAY,
+            hovering: false,
+            suppressed: false,
+        }
+    }
+
+    ///
+    /// Set the time the cursor rests over the `Component` before the tooltip appears.
+    ///
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    ///
+    /// Set the offset of the tooltip from the cursor, when it is shown below the cursor.
+    ///
+    pub fn cursor_offset(self, x: f64, y: f64) -> Self {
+        self.state.offset.set(Point { x, y });
+        self
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn tooltip_text(&self) -> String {
+        self.state.text.borrow().clone()
+    }
+
+    ///
+    /// Replace the tooltip text. An empty text shows no tooltip. Takes effect the next time
+    /// the tooltip appears.
+    ///
+    pub fn set_tooltip_text(&mut self, text: &str) {
+        *self.state.text.borrow_mut() = text.to_string();
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let position = self.inner.get_position();
+        let size = self.inner.get_size();
+        Rectangle::new(position.x, position.y, size.width, size.height)
+    }
+
+    ///
+    /// Restart the hover delay, unless the tooltip is already shown.
+    ///
+    fn restart_timer(&self) {
+        if self.state.layer.get().is_some() {
+            return;
+        }
+        if let Some(timer) = self.state.timer.take() {
+            timer::cancel(timer);
+        }
+        let state = self.state.clone();
+        let timer = timer::start(self.delay, move || state.show());
+        self.state.timer.set(Some(timer));
+    }
+}
+
+impl<T> Drop for WithTooltip<T> {
+    fn drop(&mut self) {
+        self.state.hide();
+    }
+}
+
+impl<T: Component> Component for WithTooltip<T> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        self.inner.handle_mouse_event(input);
+
+        if !self.bounds().contains(input.position.x, input.position.y) {
+            if self.hovering {
+                self.state.hide();
+            }
+            self.hovering = false;
+            self.suppressed = false;
+            return;
+        }
+        self.hovering = true;
+        if input.pressed || input.just_released {
+            self.state.hide();
+            self.suppressed = true;
+        }
+        if !self.suppressed {
+            self.state.cursor.set(input.position);
+            self.restart_timer();
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        self.inner.handle_mouse_wheel_event(delta, phase);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        self.inner.handle_keyboard_event(event);
+        if event.state == ElementState::Pressed {
+            self.state.hide();
+            self.suppressed = self.hovering;
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.inner.animate(now);
+    }
+
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        self.inner.save_state()
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        self.inner.restore_state(state);
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        self.inner.draw(context);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.inner.set_position(x, y);
+    }
+
+    fn get_position(&self) -> Point {
+        self.inner.get_position()
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.inner.set_size(width, height);
+    }
+
+    fn get_size(&self) -> Size {
+        self.inner.get_size()
+    }
+}
+
+impl<T: Container + Component> Container for WithTooltip<T> {
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.inner.push(component);
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        self.inner.resize(width, height);
+    }
+
+    fn update_layout(&mut self) {
+        self.inner.update_layout();
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        self.inner.fills_parent_container()
+    }
+}
@@ -0,0 +1,884 @@
+//!
+//! # Structura Component: TreeView.
+//!
+//! A scrolling view of hierarchical nodes, e.g. folders and files, or the outline of a
+//! document.
+//!
+//! The nodes come from a `TreeModel`. The `TreeView` asks for the children of a node only when
+//! it is first expanded, and keeps them while it is collapsed. A model that is slow to give
+//! the children, e.g. reading a network drive, returns `None` and passes them later to the
+//! `ChildLoader`, from any thread. Meanwhile the node shows a "Loading…" row.
+//!
+//! ```ignore
+//! struct Files;
+//!
+//! impl TreeModel for Files {
+//!     type Node = PathBuf;
+//!     fn roots(&self) -> Vec<PathBuf> { vec![PathBuf::from("/")] }
+//!     fn has_children(&self, path: &PathBuf) -> bool { path.is_dir() }
+//!     fn children(&self, path: &PathBuf, loader: ChildLoader<PathBuf>) -> Option<Vec<PathBuf>> {
+//!         let path = path.clone();
+//!         std::thread::spawn(move || loader.finish(read_dir(&path)));
+//!         None
+//!     }
+//!     fn text(&self, path: &PathBuf) -> String { file_name(path) }
+//! }
+//!
+//! let tree = TreeView::new(Files).on_activate(|path| open(path));
+//! ```
+//!
+//! - A click on the arrow, or a double-click on the row, expands or collapses a node.
+//! - With focus, `Up` / `Down`, `PageUp` / `PageDown` and `Home` / `End` move the selection.
+//!   `Right` expands the selected node, then moves to its first child. `Left` collapses it,
+//!   or moves to its parent. `Enter` or a double-click activates the selected node.
+//! - Only the visible rows are drawn, with guides showing the indentation of each level.
+//!
+
+use crate::animation;
+use crate::component::icon::Icon;
+use crate::component::list::{DOUBLE_CLICK_TIME, HOVER_ALPHA, SELECTION_ALPHA, SELECTION_COLOR};
+use crate::component::scrollbar::{SCROLLBAR_WIDTH, ScrollBar};
+use crate::component::{Component, ComponentState, metrics};
+use crate::dispatch;
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Orientation, Point, Rectangle, Size};
+use crate::view::{BufferContext, blend_color};
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use winit::event::{ElementState, MouseScrollDelta};
+use winit::keyboard::{Key, NamedKey};
+
+/// Space left of the rows, and between the icon and the text.
+const PADDING: f64 = 4.0;
+
+/// Indentation of each level, which also holds the expand arrow.
+const INDENT: f64 = 18.0;
+
+/// Rows scrolled by one notch of the mouse wheel.
+const WHEEL_ROWS: f64 = 3.0;
+
+/// Opacity of the indentation guides.
+const GUIDE_ALPHA: f32 = 0.3;
+
+/// Opacity of the text of the loading row, which is dimmed.
+const LOADING_ALPHA: f32 = 0.6;
+
+/// Text of the row shown while children are loading.
+const LOADING_TEXT: &str = "Loading…";
+
+///
+/// The nodes shown by a `TreeView`.
+///
+/// A `Node` identifies a node of the tree, e.g. a path or an id. The `TreeView` keeps the
+/// `Node`s it has loaded, and compares them to find a node again after `TreeView::refresh()`.
+///
+pub trait TreeModel {
+    type Node: Clone + PartialEq + 'static;
+
+    ///
+    /// Returns the nodes at the top level of the tree.
+    ///
+    fn roots(&self) -> Vec<Self::Node>;
+
+    ///
+    /// Returns true if `node` can be expanded. Called for every loaded node, so it should be
+    /// quick, without loading the children.
+    ///
+    fn has_children(&self, node: &Self::Node) -> bool;
+
+    ///
+    /// Returns the children of `node`, called when it is first expanded.
+    ///
+    /// To load the children in the background instead, return `None`, and pass them to
+    /// `loader.finish()` when they are ready, e.g. with no children if loading fails.
+    ///
+    fn children(
+        &self,
+        node: &Self::Node,
+        loader: ChildLoader<Self::Node>,
+    ) -> Option<Vec<Self::Node>>;
+
+    fn text(&self, node: &Self::Node) -> String;
+
+    ///
+    /// Returns the icon shown before the text of `node`, which may depend on whether it is
+    /// expanded.
+    ///
+    fn icon(&self, _node: &Self::Node, _expanded: bool) -> Option<Icon> {
+        None
+    }
+}
+
+type Slot<N> = Arc<Mutex<Option<Vec<N>>>>;
+
+///
+/// Receives the children of a node that are loaded in the background. Can be sent to other
+/// threads when the `Node`s can.
+///
+pub struct ChildLoader<N> {
+    slot: Slot<N>,
+}
+
+impl<N> ChildLoader<N> {
+    ///
+    /// Give the loaded children to the `TreeView`, which shows them at its next frame.
+    ///
+    pub fn finish(self, children: Vec<N>) {
+        *self.slot.lock().unwrap() = Some(children);
+        dispatch::post(animation::request_frame);
+    }
+}
+
+enum Children<N> {
+    Unloaded,
+    Loading(Slot<N>),
+
+    /// The indices of the children in `TreeView::entries`.
+    Loaded(Vec<usize>),
+}
+
+///
+/// A loaded node.
+///
+struct Entry<N> {
+    node: N,
+    parent: Option<usize>,
+    depth: usize,
+    has_children: bool,
+    expanded: bool,
+    children: Children<N>,
+}
+
+///
+/// A visible row: a node, or the placeholder of a node whose children are loading.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Row {
+    Node(usize),
+    Loading(usize),
+}
+
+/// Called with a node, e.g. when it is selected.
+type NodeHandler<N> = Box<dyn FnMut(&N)>;
+
+///
+/// The state of a `TreeView` kept by `Component::save_state()`.
+///
+struct TreeViewState<N> {
+    expanded: Vec<N>,
+    selected: Option<N>,
+    scroll: f64,
+}
+
+///
+/// TreeView control showing the nodes of a `TreeModel`.
+///
+pub struct TreeView<M: TreeModel> {
+    position: Point,
+    size: Size,
+    model: M,
+
+    /// Every loaded node, including the children of collapsed nodes.
+    entries: Vec<Entry<M::Node>>,
+    roots: Vec<usize>,
+    rows: Vec<Row>,
+
+    /// Entries whose children are loading in the background.
+    loading: Vec<usize>,
+
+    /// Nodes to expand, and to select, when they are loaded, after `refresh()`.
+    pending_expanded: Vec<M::Node>,
+    pending_selected: Option<M::Node>,
+    row_height: f64,
+    show_guides: bool,
+    selected: Option<usize>,
+    hovered: Option<usize>,
+
+    /// True while the cursor is over the `TreeView`, so the wheel scrolls it.
+    cursor_over: bool,
+
+    /// Holds the scroll offset, in pixels from the top of the first row.
+    scroll: ScrollBar,
+    last_click: Option<(usize, Instant)>,
+    focus_id: FocusId,
+    on_selection_changed: Option<NodeHandler<M::Node>>,
+    on_activate: Option<NodeHandler<M::Node>>,
+}
+
+impl<M: TreeModel> TreeView<M> {
+    ///
+    /// Constructor, showing the roots of `model`, collapsed.
+    ///
+    pub fn new(model: M) -> Self {
+        let mut tree = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 300,
+                height: 400,
+            },
+            model,
+            entries: Vec::new(),
+            roots: Vec::new(),
+            rows: Vec::new(),
+            loading: Vec::new(),
+            pending_expanded: Vec::new(),
+            pending_selected: None,
+            row_height: (metrics::line_height() as f64 + 8.0).ceil(),
+            show_guides: true,
+            selected: None,
+            hovered: None,
+            cursor_over: false,
+            scroll: ScrollBar::new(Orientation::Vertical),
+            last_click: None,
+            focus_id: FocusId::new(),
+            on_selection_changed: None,
+            on_activate: None,
+        };
+        tree.reload();
+        tree
+    }
+
+    ///
+    /// Set the height of every row.
+    ///
+    pub fn row_height(mut self, row_height: f64) -> Self {
+        self.row_height = row_height.max(1.0);
+        self
+    }
+
+    ///
+    /// Show or hide the vertical guides at each level of indentation. Shown by default.
+    ///
+    pub fn show_guides(mut self, show_guides: bool) -> Self {
+        self.show_guides = show_guides;
+        self
+    }
+
+    ///
+    /// Add event handler, called with the node the user selects.
+    ///
+    pub fn on_selection_changed<F: FnMut(&M::Node) + 'static>(mut self, f: F) -> Self {
+        self.on_selection_changed = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Add event handler, called with a node when it is double-clicked, or when `Enter` is
+    /// pressed on the selected node.
+    ///
+    pub fn on_activate<F: FnMut(&M::Node) + 'static>(mut self, f: F) -> Self {
+        self.on_activate = Some(Box::new(f));
+        self
+    }
+
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    ///
+    /// Returns the model, to change the nodes. Call `refresh()` afterwards.
+    ///
+    pub fn model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
+    ///
+    /// Load the nodes from the model again, keeping the expanded and selected nodes that are
+    /// still in the tree.
+    ///
+    pub fn refresh(&mut self) {
+        self.pending_expanded = self.expanded_nodes();
+        self.pending_selected = self.selected().cloned();
+        self.reload();
+    }
+
+    pub fn selected(&self) -> Option<&M::Node> {
+        self.selected.map(|entry| &self.entries[entry].node)
+    }
+
+    ///
+    /// Select `node`, expanding its parents and scrolling it into view. Returns false if it has
+    /// not been loaded. Does not call `on_selection_changed`.
+    ///
+    pub fn set_selected(&mut self, node: &M::Node) -> bool {
+        let Some(entry) = self.find(node) else {
+            return false;
+        };
+        let mut parent = self.entries[entry].parent;
+        while let Some(ancestor) = parent {
+            self.expand_entry(ancestor);
+            parent = self.entries[ancestor].parent;
+        }
+        self.selected = Some(entry);
+        self.scroll_into_view(entry);
+        true
+    }
+
+    ///
+    /// Expand `node`, loading its children if needed. Returns false if it has not been
+    /// loaded.
+    ///
+    pub fn expand(&mut self, node: &M::Node) -> bool {
+        self.find(node)
+            .map(|entry| self.expand_entry(entry))
+            .is_some()
+    }
+
+    ///
+    /// Collapse `node`. Returns false if it has not been loaded.
+    ///
+    pub fn collapse(&mut self, node: &M::Node) -> bool {
+        self.find(node)
+            .map(|entry| self.collapse_entry(entry))
+            .is_some()
+    }
+
+    pub fn is_expanded(&self, node: &M::Node) -> bool {
+        self.find(node)
+            .is_some_and(|entry| self.entries[entry].expanded)
+    }
+
+    ///
+    /// Returns the `FocusId` of the `TreeView`, e.g. for `Label::label_for()`.
+    ///
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    fn find(&self, node: &M::Node) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.node == *node)
+    }
+
+    fn expanded_nodes(&self) -> Vec<M::Node> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.expanded)
+            .map(|entry| entry.node.clone())
+            .collect()
+    }
+
+    ///
+    /// Forget the loaded nodes, and load the roots from the model.
+    ///
+    fn reload(&mut self) {
+        self.entries.clear();
+        self.loading.clear();
+        self.selected = None;
+        self.hovered = None;
+        let roots = self.model.roots();
+        self.roots = self.add_entries(roots, None);
+        self.update_rows();
+    }
+
+    ///
+    /// Add loaded nodes, then expand and select those pending from `refresh()`. Returns their
+    /// indices.
+    ///
+    fn add_entries(&mut self, nodes: Vec<M::Node>, parent: Option<usize>) -> Vec<usize> {
+        let depth = parent.map_or(0, |parent| self.entries[parent].depth + 1);
+        let start = self.entries.len();
+        for node in nodes {
+            let has_children = self.model.has_children(&node);
+            if self.pending_selected.as_ref() == Some(&node) {
+                self.pending_selected = None;
+                self.selected = Some(self.entries.len());
+            }
+            self.entries.push(Entry {
+                node,
+                parent,
+                depth,
+                has_children,
+                expanded: false,
+                children: Children::Unloaded,
+            });
+        }
+        let added: Vec<usize> = (start..self.entries.len()).collect();
+        for &entry in &added {
+            if self.pending_expanded.contains(&self.entries[entry].node) {
+                self.expand_entry(entry);
+            }
+        }
+        added
+    }
+
+    fn expand_entry(&mut self, entry: usize) {
+        if !self.entries[entry].has_children || self.entries[entry].expanded {
+            return;
+        }
+        self.entries[entry].expanded = true;
+        if let Children::Unloaded = self.entries[entry].children {
+            let slot = Arc::new(Mutex::new(None));
+            let loader = ChildLoader { slot: slot.clone() };
+            match self.model.children(&self.entries[entry].node, loader) {
+                Some(children) => self.set_children(entry, children),
+                None => {
+                    self.entries[entry].children = Children::Loading(slot);
+                    self.loading.push(entry);
+                    //
+                    // The model may have finished already.
+                    //
+                    self.poll_loading();
+                }
+            }
+        }
+        self.update_rows();
+    }
+
+    fn collapse_entry(&mut self, entry: usize) {
+        if !self.entries[entry].expanded {
+            return;
+        }
+        self.entries[entry].expanded = false;
+        //
+        // The selection moves up to the collapsed node, rather than hiding.
+        //
+        if let Some(selected) = self.selected
+            && self.is_descendant(selected, entry)
+        {
+            self.select(entry);
+        }
+        self.update_rows();
+    }
+
+    fn toggle_entry(&mut self, entry: usize) {
+        if self.entries[entry].expanded {
+            self.collapse_entry(entry);
+        } else {
+            self.expand_entry(entry);
+        }
+    }
+
+    fn is_descendant(&self, entry: usize, ancestor: usize) -> bool {
+        let mut parent = self.entries[entry].parent;
+        while let Some(current) = parent {
+            if current == ancestor {
+                return true;
+            }
+            parent = self.entries[current].parent;
+        }
+        false
+    }
+
+    fn set_children(&mut self, entry: usize, children: Vec<M::Node>) {
+        let children = self.add_entries(children, Some(entry));
+        self.entries[entry].children = Children::Loaded(children);
+    }
+
+    ///
+    /// Show the children that have finished loading in the background.
+    ///
+    fn poll_loading(&mut self) {
+        let mut finished = Vec::new();
+        self.loading.retain(|&entry| {
+            let Children::Loading(slot) = &self.entries[entry].children else {
+                return false;
+            };
+            match slot.lock().unwrap().take() {
+                Some(children) => {
+                    finished.push((entry, children));
+                    false
+                }
+                None => true,
+            }
+        });
+        if finished.is_empty() {
+            return;
+        }
+        for (entry, children) in finished {
+            self.set_children(entry, children);
+        }
+        self.update_rows();
+    }
+
+    ///
+    /// List the visible rows: the roots, and the children of expanded nodes.
+    ///
+    fn update_rows(&mut self) {
+        self.rows.clear();
+        let mut stack: Vec<usize> = self.roots.iter().rev().copied().collect();
+        while let Some(entry) = stack.pop() {
+            self.rows.push(Row::Node(entry));
+            if !self.entries[entry].expanded {
+                continue;
+            }
+            match &self.entries[entry].children {
+                Children::Loading(_) => self.rows.push(Row::Loading(entry)),
+                Children::Loaded(children) => stack.extend(children.iter().rev()),
+                Children::Unloaded => {}
+            }
+        }
+        self.hovered = self.hovered.filter(|&row| row < self.rows.len());
+        self.update_scrollbar();
+    }
+
+    fn update_scrollbar(&mut self) {
+        let content = self.rows.len() as f64 * self.row_height;
+        self.scroll.set_lengths(content, self.size.height as f64);
+        self.scroll.set_track(Rectangle::new(
+            self.position.x + self.size.width.saturating_sub(SCROLLBAR_WIDTH) as f64,
+            self.position.y,
+            SCROLLBAR_WIDTH.min(self.size.width),
+            self.size.height,
+        ));
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    ///
+    /// Returns the bounds of the rows, left of the scrollbar.
+    ///
+    fn rows_bounds(&self) -> Rectangle {
+        let scrollbar = if self.scroll.is_shown() {
+            SCROLLBAR_WIDTH
+        } else {
+            0
+        };
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width.saturating_sub(scrollbar),
+            self.size.height,
+        )
+    }
+
+    fn row_at(&self, y: f64) -> Option<usize> {
+        let offset = y - self.position.y + self.scroll.offset();
+        if offset < 0.0 {
+            return None;
+        }
+        let row = (offset / self.row_height) as usize;
+        (row < self.rows.len()).then_some(row)
+    }
+
+    fn row_of(&self, entry: usize) -> Option<usize> {
+        self.rows.iter().position(|&row| row == Row::Node(entry))
+    }
+
+    ///
+    /// Returns the left edge of the arrow of a node at `depth`, which is followed by its icon
+    /// and text.
+    ///
+    fn indent_x(&self, depth: usize) -> f64 {
+        self.position.x + PADDING + depth as f64 * INDENT
+    }
+
+    fn scroll_into_view(&mut self, entry: usize) {
+        if let Some(row) = self.row_of(entry) {
+            let top = row as f64 * self.row_height;
+            self.scroll.scroll_into_view(top, top + self.row_height);
+        }
+    }
+
+    ///
+    /// Select `entry` for the user, and scroll it into view.
+    ///
+    fn select(&mut self, entry: usize) {
+        self.scroll_into_view(entry);
+        if self.selected == Some(entry) {
+            return;
+        }
+        self.selected = Some(entry);
+        if let Some(handler) = self.on_selection_changed.as_mut() {
+            handler(&self.entries[entry].node);
+        }
+    }
+
+    fn activate(&mut self, entry: usize) {
+        if let Some(handler) = self.on_activate.as_mut() {
+            handler(&self.entries[entry].node);
+        }
+    }
+
+    ///
+    /// Select the node at `row`, or the nearest one before it when it is a loading placeholder.
+    ///
+    fn select_row(&mut self, row: usize) {
+        let entry = match self.rows.get(row) {
+            Some(Row::Node(entry)) => *entry,
+            Some(Row::Loading(parent)) => *parent,
+            None => return,
+        };
+        self.select(entry);
+    }
+
+    ///
+    /// Handle the keys that move the selection, expand and collapse. Returns false for other
+    /// keys.
+    ///
+    fn handle_navigation(&mut self, key: &NamedKey) -> bool {
+        if self.rows.is_empty() {
+            return false;
+        }
+        let last = self.rows.len() - 1;
+        let current = self.selected.and_then(|entry| self.row_of(entry));
+        let page = ((self.size.height as f64 / self.row_height) as usize).max(1);
+        let row = match (key, current) {
+            (NamedKey::ArrowDown, Some(row)) => {
+                //
+                // Step over a loading placeholder, which cannot be selected.
+                //
+                let next = (row + 1..=last).find(|&row| matches!(self.rows[row], Row::Node(_)));
+                next.unwrap_or(row)
+            }
+            (NamedKey::ArrowUp, Some(row)) => row.saturating_sub(1),
+            (NamedKey::PageDown, Some(row)) => (row + page).min(last),
+            (NamedKey::PageUp, Some(row)) => row.saturating_sub(page),
+            (NamedKey::ArrowRight, Some(row)) => {
+                let Row::Node(entry) = self.rows[row] else {
+                    return true;
+                };
+                if !self.entries[entry].expanded {
+                    self.expand_entry(entry);
+                    return true;
+                }
+                match &self.entries[entry].children {
+                    Children::Loaded(children) if !children.is_empty() => row + 1,
+                    _ => row,
+                }
+            }
+            (NamedKey::ArrowLeft, Some(row)) => {
+                let Row::Node(entry) = self.rows[row] else {
+                    return true;
+                };
+                if self.entries[entry].expanded {
+                    self.collapse_entry(entry);
+                    return true;
+                }
+                match self.entries[entry].parent {
+                    Some(parent) => self.row_of(parent).unwrap_or(row),
+                    None => row,
+                }
+            }
+            (NamedKey::End, _) => last,
+            (
+                NamedKey::ArrowDown
+                | NamedKey::ArrowUp
+                | NamedKey::PageDown
+                | NamedKey::PageUp
+                | NamedKey::Home,
+                _,
+            ) => 0,
+            _ => return false,
+        };
+        self.select_row(row);
+        true
+    }
+
+    ///
+    /// Draw the arrow of an expandable node: pointing right when collapsed, and down when
+    /// expanded.
+    ///
+    fn draw_arrow(&self, context: &mut BufferContext, x: f64, y: f64, expanded: bool, color: u32) {
+        let (cx, cy) = (x + INDENT / 2.0, y + self.row_height / 2.0);
+        let half = 4.0;
+        if expanded {
+            context.draw_line(cx - half, cy - half / 2.0, cx, cy + half / 2.0, color);
+            context.draw_line(cx, cy + half / 2.0, cx + half, cy - half / 2.0, color);
+        } else {
+            context.draw_line(cx - half / 2.0, cy - half, cx + half / 2.0, cy, color);
+            context.draw_line(cx + half / 2.0, cy, cx - half / 2.0, cy + half, color);
+        }
+    }
+}
+
+impl<M: TreeModel> Component for TreeView<M> {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let (x, y) = (input.position.x, input.position.y);
+        self.update_scrollbar();
+        let on_rows = self.rows_bounds().contains(x, y);
+        self.hovered = if on_rows { self.row_at(y) } else { None };
+        self.cursor_over = self.bounds().contains(x, y);
+
+        if input.pressed {
+            if self.bounds().contains(x, y) {
+                focus::set_focus(self.focus_id);
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+        }
+        if self.scroll.handle_mouse_event(&input) || !input.pressed {
+            return;
+        }
+        let Some(Row::Node(entry)) = self.hovered.map(|row| self.rows[row]) else {
+            return;
+        };
+        let arrow_x = self.indent_x(self.entries[entry].depth);
+        if self.entries[entry].has_children && (arrow_x..arrow_x + INDENT).contains(&x) {
+            self.toggle_entry(entry);
+            return;
+        }
+        let double_click = self
+            .last_click
+            .is_some_and(|(last, at)| last == entry && at.elapsed() <= DOUBLE_CLICK_TIME);
+        self.select(entry);
+        if double_click {
+            self.last_click = None;
+            self.toggle_entry(entry);
+            self.activate(entry);
+        } else {
+            self.last_click = Some((entry, Instant::now()));
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        _phase: &winit::event::TouchPhase,
+    ) {
+        if !self.cursor_over {
+            return;
+        }
+        let dy = match delta {
+            MouseScrollDelta::LineDelta(_, y) => -*y as f64 * WHEEL_ROWS * self.row_height,
+            MouseScrollDelta::PixelDelta(p) => -p.y,
+        };
+        self.update_scrollbar();
+        self.scroll.scroll_by(dy);
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if !focus::is_focused(self.focus_id) || event.state != ElementState::Pressed {
+            return;
+        }
+        match &event.logical_key {
+            Key::Named(key) if self.handle_navigation(key) => {}
+            Key::Named(NamedKey::Enter) => {
+                if let Some(entry) = self.selected {
+                    self.activate(entry);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.poll_loading();
+        self.scroll.animate(now);
+    }
+
+    ///
+    /// Keeps the expanded and selected nodes, and the scroll position.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(TreeViewState {
+            expanded: self.expanded_nodes(),
+            selected: self.selected().cloned(),
+            scroll: self.scroll.target(),
+        }))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<TreeViewState<M::Node>>() {
+            self.pending_expanded = state.expanded;
+            self.pending_selected = state.selected;
+            self.reload();
+            self.scroll.set_offset(state.scroll);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let focused = focus::is_focused(self.focus_id);
+        let style = context.theme.style_for(&ComponentState::Active);
+        let (x, y, w, h) = (
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        );
+        context.fill_rect(x, y, w, h, style.edit_back_color.value);
+
+        //
+        // Only the rows from the first visible one to the bottom edge are drawn.
+        //
+        let scroll = self.scroll.offset();
+        let first = (scroll / self.row_height) as usize;
+        let visible = (h as f64 / self.row_height).ceil() as usize + 1;
+        let rows = self.rows_bounds();
+        let line_height = context.line_height() as f64;
+        let text_color = style.edit_text_color.value;
+        let loading_color = blend_color(style.edit_back_color.value, text_color, LOADING_ALPHA);
+        context.with_clip(&rows, |context| {
+            for (index, row) in self.rows.iter().enumerate().skip(first).take(visible) {
+                let row_y = y + index as f64 * self.row_height - scroll;
+                let (bw, bh) = (rows.size.width, self.row_height as u32);
+                let text_y = row_y + (self.row_height - line_height) / 2.0;
+                let (entry, depth) = match *row {
+                    Row::Node(entry) => (entry, self.entries[entry].depth),
+                    Row::Loading(parent) => (parent, self.entries[parent].depth + 1),
+                };
+                if self.show_guides {
+                    for level in 0..depth {
+                        let guide_x = (self.indent_x(level) + INDENT / 2.0).floor();
+                        context.blend_rect(guide_x, row_y, 1, bh, text_color, GUIDE_ALPHA);
+                    }
+                }
+                let content_x = self.indent_x(depth) + INDENT;
+                if let Row::Loading(_) = row {
+                    context.draw_text(LOADING_TEXT, content_x, text_y, loading_color);
+                    continue;
+                }
+
+                let selected = self.selected == Some(entry);
+                if selected {
+                    context.blend_rect(x, row_y, bw, bh, SELECTION_COLOR, SELECTION_ALPHA);
+                } else if self.hovered == Some(index) {
+                    context.blend_rect(x, row_y, bw, bh, style.fore_color.value, HOVER_ALPHA);
+                }
+                let node = &self.entries[entry];
+                if node.has_children {
+                    let arrow_x = self.indent_x(depth);
+                    self.draw_arrow(context, arrow_x, row_y, node.expanded, text_color);
+                }
+                let mut text_x = content_x;
+                if let Some(icon) = self.model.icon(&node.node, node.expanded) {
+                    let icon_y = row_y + (self.row_height - icon.height() as f64) / 2.0;
+                    icon.draw(context, text_x, icon_y.round(), text_color);
+                    text_x += icon.width() as f64 + PADDING;
+                }
+                let text = self.model.text(&node.node);
+                context.draw_text(&text, text_x, text_y, text_color);
+                if selected && focused {
+                    context.draw_border(x, row_y, bw, bh, 1, style.cursor_color.value);
+                }
+            }
+        });
+        self.scroll.draw(context);
+
+        let border = if focused {
+            style.cursor_color.value
+        } else {
+            style.border_color.value
+        };
+        context.draw_border(x, y, w, h, 1, border);
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.update_scrollbar();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.update_scrollbar();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,254 @@
+//!
+//! # **Structura**: Accordion
+//!
+//! Implements `Container` and `Component`.
+//!
+//! A vertical stack of `Expander` sections, where at most one section is open at a time:
+//! opening a section collapses the one that was open.
+//!
+//! ```text
+//! |-----------------------------|
+//! | > Section 1                 |
+//! |-----------------------------|
+//! | v Section 2                 |
+//! |-----------------------------|
+//! |  Child Component            |
+//! |-----------------------------|
+//! | > Section 3                 |
+//! |-----------------------------|
+//! ```
+//!
+//! Like the `Expander`, the `Accordion` takes its width from its parent, and its height from
+//! its sections.
+//!
+
+use crate::component::Component;
+use crate::container::Container;
+use crate::container::expander::Expander;
+use crate::event::MouseInput;
+use crate::geometry::{Point, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::time::Instant;
+
+///
+/// Accordion: Implements `Container` and `Component`.
+///
+pub struct Accordion {
+    position: Point,
+    width: u32,
+    sections: Vec<Expander>,
+    open: Option<usize>,
+    on_change: Option<Box<dyn FnMut(Option<usize>)>>,
+}
+
+impl Default for Accordion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Accordion {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            width: 0,
+            sections: vec![],
+            open: None,
+            on_change: None,
+        }
+    }
+
+    ///
+    /// Called with the index of the open section, or `None`, whenever it changes.
+    ///
+    pub fn on_change<F: FnMut(Option<usize>) + 'static>(mut self, callback: F) -> Self {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    ///
+    /// Add a collapsed section with the specified `title` and child `Component`.
+    ///
+    pub fn add_section(&mut self, title: String, content: Box<dyn Component>) {
+        self.sections.push(Expander::new(title, content));
+        self.update_layout();
+    }
+
+    pub fn section_count(&self) -> usize {
+        self.sections.len()
+    }
+
+    ///
+    /// Returns the index of the open section.
+    ///
+    pub fn open_section(&self) -> Option<usize> {
+        self.open
+    }
+
+    ///
+    /// Open the section at `index`, collapsing the section that was open. `None` collapses
+    /// all sections.
+    ///
+    pub fn set_open_section(&mut self, index: Option<usize>) {
+        let index = index.filter(|index| *index < self.sections.len());
+        for (i, section) in self.sections.iter_mut().enumerate() {
+            section.set_expanded(Some(i) == index);
+        }
+        self.set_open(index);
+    }
+
+    fn set_open(&mut self, index: Option<usize>) {
+        if self.open != index {
+            self.open = index;
+            if let Some(callback) = self.on_change.as_mut() {
+                callback(index);
+            }
+        }
+    }
+
+    ///
+    /// After the sections handle an event, keep only the most recently opened section open.
+    ///
+    fn sync_open_section(&mut self) {
+        let newly_opened = self
+            .sections
+            .iter()
+            .enumerate()
+            .position(|(i, section)| section.is_expanded() && Some(i) != self.open);
+        match newly_opened {
+            Some(index) => self.set_open_section(Some(index)),
+            None => {
+                let still_open = self
+                    .open
+                    .filter(|index| self.sections[*index].is_expanded());
+                self.set_open(still_open);
+            }
+        }
+    }
+}
+
+impl Container for Accordion {
+    ///
+    /// Add the specified `Component` as a new section, with a default title.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        let title = format!("Section {}", self.sections.len() + 1);
+        self.add_section(title, component);
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        false
+    }
+
+    ///
+    /// Called when the parent `Container` resizes.
+    ///
+    /// The `Accordion` height follows its sections, so parent changes are ignored.
+    ///
+    fn resize(&mut self, _width: usize, _height: usize) {}
+
+    ///
+    /// Stack the sections vertically, at the `Accordion` width.
+    ///
+    fn update_layout(&mut self) {
+        let mut current_y = self.position.y;
+        for section in self.sections.iter_mut() {
+            section.set_position(self.position.x, current_y);
+            section.set_size(self.width as usize, 0);
+            current_y += section.get_size().height as f64;
+        }
+    }
+}
+
+impl Component for Accordion {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        for section in self.sections.iter_mut() {
+            section.handle_mouse_event(input);
+        }
+        self.sync_open_section();
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        for section in self.sections.iter_mut() {
+            section.handle_mouse_wheel_event(delta, phase);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        for section in self.sections.iter_mut() {
+            section.handle_keyboard_event(event);
+        }
+        self.sync_open_section();
+    }
+
+    ///
+    /// Sections change height while animating, so the layout is updated after each frame.
+    ///
+    fn animate(&mut self, now: Instant) {
+        for section in self.sections.iter_mut() {
+            section.animate(now);
+        }
+        self.update_layout();
+    }
+
+    ///
+    /// Keeps the open section.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.open))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(open) = state.downcast::<Option<usize>>() {
+            let open = open.filter(|index| *index < self.sections.len());
+            for (i, section) in self.sections.iter_mut().enumerate() {
+                section.restore_state(Box::new(Some(i) == open));
+            }
+            self.open = open;
+            self.update_layout();
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        for section in self.sections.iter() {
+            section.draw(context);
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position.x = x;
+        self.position.y = y;
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    ///
+    /// Sets the width only: the height follows the sections.
+    ///
+    fn set_size(&mut self, width: usize, _height: usize) {
+        self.width = width as u32;
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        Size {
+            width: self.width,
+            height: self
+                .sections
+                .iter()
+                .map(|section| section.get_size().height)
+                .sum(),
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//!
+//! # **Structura**: AnchorPane
+//!
+//! Implements `Container` and `Component`.
+//!
+//! Positions each child by anchoring its edges to the edges of the `AnchorPane`, for
+//! canvas-like screens where children are pinned to edges or corners:
+//!
+//! - Anchored to one edge of an axis: the child keeps its own size, at the anchor distance from
+//!   that edge. E.g. `Anchors::new().right(20.0).bottom(20.0)` pins a child to the bottom-right
+//!   corner.
+//! - Anchored to both edges of an axis: the child stretches between the two edges.
+//! - Not anchored on an axis: the child keeps the position it had when added, relative to the
+//!   `AnchorPane`.
+//!
+//! An anchor distance is a fixed offset in pixels, plus an optional percentage of the
+//! `AnchorPane` size. Children are re-positioned whenever the `AnchorPane` resizes.
+//!
+
+use crate::component::Component;
+use crate::container::{Container, MouseGrab};
+use crate::event::MouseInput;
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::time::Instant;
+
+///
+/// The distance of a child edge from the matching edge of the `AnchorPane`.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Anchor {
+    /// Percentage (`0.0` to `100.0`) of the `AnchorPane` width or height.
+    pub percent: f64,
+
+    /// Fixed distance in pixels, added to the percentage.
+    pub offset: f64,
+}
+
+impl Anchor {
+    ///
+    /// An anchor at a fixed distance in pixels.
+    ///
+    pub fn offset(offset: f64) -> Self {
+        Self {
+            percent: 0.0,
+            offset,
+        }
+    }
+
+    ///
+    /// An anchor at a percentage of the `AnchorPane` size, plus a fixed distance in pixels.
+    ///
+    pub fn percent(percent: f64, offset: f64) -> Self {
+        Self { percent, offset }
+    }
+
+    ///
+    /// Returns the distance in pixels, for an `AnchorPane` width or height of `length`.
+    ///
+    fn resolve(&self, length: u32) -> f64 {
+        length as f64 * self.percent / 100.0 + self.offset
+    }
+}
+
+///
+/// The anchors of each edge of a child `Component`. Edges without an anchor are free.
+///
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Anchors {
+    pub left: Option<Anchor>,
+    pub top: Option<Anchor>,
+    pub right: Option<Anchor>,
+    pub bottom: Option<Anchor>,
+}
+
+impl Anchors {
+    ///
+    /// Constructor, with no edges anchored.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Anchor all four edges at `offset` pixels, stretching the child to fill the
+    /// `AnchorPane`.
+    ///
+    pub fn fill(offset: f64) -> Self {
+        Self::new()
+            .left(offset)
+            .top(offset)
+            .right(offset)
+            .bottom(offset)
+    }
+
+    pub fn left(mut self, offset: f64) -> Self {
+        self.left = Some(Anchor::offset(offset));
+        self
+    }
+
+    pub fn top(mut self, offset: f64) -> Self {
+        self.top = Some(Anchor::offset(offset));
+        self
+    }
+
+    pub fn right(mut self, offset: f64) -> Self {
+        self.right = Some(Anchor::offset(offset));
+        self
+    }
+
+    pub fn bottom(mut self, offset: f64) -> Self {
+        self.bottom = Some(Anchor::offset(offset));
+        self
+    }
+
+    pub fn left_percent(mut self, percent: f64, offset: f64) -> Self {
+        self.left = Some(Anchor::percent(percent, offset));
+        self
+    }
+
+    pub fn top_percent(mut self, percent: f64, offset: f64) -> Self {
+        self.top = Some(Anchor::percent(percent, offset));
+        self
+    }
+
+    pub fn right_percent(mut self, percent: f64, offset: f64) -> Self {
+        self.right = Some(Anchor::percent(percent, offset));
+        self
+    }
+
+    pub fn bottom_percent(mut self, percent: f64, offset: f64) -> Self {
+        self.bottom = Some(Anchor::percent(percent, offset));
+        self
+    }
+}
+
+///
+/// Resolve the start and length of a child along one axis.
+///
+/// - `start`, `end`: the anchors of the two edges on this axis.
+/// - `origin`, `preferred`: the child position (relative to the `AnchorPane`) and size when
+///   it was added, used for free edges.
+/// - `length`: the `AnchorPane` size on this axis.
+///
+fn resolve_axis(
+    start: Option<Anchor>,
+    end: Option<Anchor>,
+    origin: f64,
+    preferred: u32,
+    length: u32,
+) -> (f64, u32) {
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            let from = start.resolve(length);
+            let to = length as f64 - end.resolve(length);
+            (from, (to - from).max(0.0) as u32)
+        }
+        (Some(start), None) => (start.resolve(length), preferred),
+        (None, Some(end)) => (
+            length as f64 - end.resolve(length) - preferred as f64,
+            preferred,
+        ),
+        (None, None) => (origin, preferred),
+    }
+}
+
+///
+/// A child `Component` with its anchors, and its position and size when it was added.
+///
+struct AnchoredChild {
+    component: Box<dyn Component>,
+    anchors: Anchors,
+    origin: Point,
+    preferred: Size,
+}
+
+impl AnchoredChild {
+    fn bounds(&self) -> Rectangle {
+        let position = self.component.get_position();
+        let size = self.component.get_size();
+        Rectangle::new(position.x, position.y, size.width, size.height)
+    }
+}
+
+///
+/// AnchorPane: Implements `Container` and `Component`.
+///
+pub struct AnchorPane {
+    position: Point,
+    size: Size,
+    children: Vec<AnchoredChild>,
+
+    /// The child that was pressed, which sees the mouse events until the button is released.
+    grab: MouseGrab,
+}
+
+impl Default for AnchorPane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnchorPane {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            children: vec![],
+            grab: MouseGrab::default(),
+        }
+    }
+
+    ///
+    /// Add the specified `Component` with the specified `anchors`.
+    ///
+    /// The current size of the `Component` is used for any axis where it is not stretched,
+    /// and its current position, relative to the `AnchorPane`, for any axis without anchors.
+    ///
+    pub fn add(&mut self, component: Box<dyn Component>, anchors: Anchors) {
+        let origin = component.get_position();
+        let preferred = component.get_size();
+        self.children.push(AnchoredChild {
+            component,
+            anchors,
+            origin,
+            preferred,
+        });
+        self.update_layout();
+    }
+
+    ///
+    /// Replace the anchors of the child at `index`.
+    ///
+    pub fn set_anchors(&mut self, index: usize, anchors: Anchors) {
+        if let Some(child) = self.children.get_mut(index) {
+            child.anchors = anchors;
+            self.update_layout();
+        }
+    }
+
+    ///
+    /// Returns the index of the topmost child at the specified coordinates.
+    ///
+    fn child_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.children
+            .iter()
+            .rposition(|child| child.bounds().contains(x, y))
+    }
+}
+
+impl Container for AnchorPane {
+    ///
+    /// Add the specified `Component` without anchors, at its current position relative to the
+    /// `AnchorPane`.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.add(component, Anchors::new());
+    }
+
+    ///
+    /// The `AnchorPane` always fills its parent `Container`.
+    ///
+    fn fills_parent_container(&self) -> bool {
+        true
+    }
+
+    ///
+    /// Called when the parent `Container` resizes.
+    ///
+    /// Passes in the parent container `width` and `height`.
+    ///
+    fn resize(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    ///
+    /// Position and size each child from its anchors.
+    ///
+    fn update_layout(&mut self) {
+        for child in self.children.iter_mut() {
+            let anchors = child.anchors;
+            let (x, width) = resolve_axis(
+                anchors.left,
+                anchors.right,
+                child.origin.x,
+                child.preferred.width,
+                self.size.width,
+            );
+            let (y, height) = resolve_axis(
+                anchors.top,
+                anchors.bottom,
+                child.origin.y,
+                child.preferred.height,
+                self.size.height,
+            );
+            child
+                .component
+                .set_position(self.position.x + x, self.position.y + y);
+            child.component.set_size(width as usize, height as usize);
+        }
+    }
+}
+
+impl Component for AnchorPane {
+    ///
+    /// Children may overlap, so only the topmost child under the cursor sees the event where
+    /// it happened. The other children see it as happening elsewhere. See `MouseGrab`.
+    ///
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let under_cursor = self.child_at(input.position.x, input.position.y);
+        let target = self.grab.target(&input, under_cursor);
+        for (index, child) in self.children.iter_mut().enumerate() {
+            if target.is_none() || target == Some(index) {
+                child.component.handle_mouse_event(input);
+            } else {
+                child.component.handle_mouse_event(input.elsewhere());
+            }
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        for child in self.children.iter_mut() {
+            child.component.handle_mouse_wheel_event(delta, phase);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        for child in self.children.iter_mut() {
+            child.component.handle_keyboard_event(event);
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        for child in self.children.iter_mut() {
+            child.component.animate(now);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        for child in self.children.iter() {
+            child.component.draw(context);
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position.x = x;
+        self.position.y = y;
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
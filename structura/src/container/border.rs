@@ -2,30 +2,90 @@
 //! # **Structura**: BorderLayout
 //!
 //! Implements `Container` and `Component`.
+//!
+//! ```text
+//! |------------------------|
+//! | North                  |
+//! |------------------------|
+//! |      |          |      |
+//! | West |  Center  | East |
+//! |      |          |      |
+//! |------------------------|
+//! | South                  |
+//! |------------------------|
+//! ```
+//!
+//! ## Resize rules
+//!
+//! - Center: Resizes `width` and `height` when `BorderLayout` resizes.
+//! - North: Resizes `width` when `BorderLayout` resizes.
+//! - South: Resizes `width` when `BorderLayout` resizes.
+//! - West: Resizes `height` when `BorderLayout` resizes.
+//! - East: Resizes `height` when `BorderLayout` resizes.
+//!
+//! The `height` of North and South, and the `width` of West and East, are taken from the
+//! size of the region `Component` when it is set, and kept by the `BorderLayout`, so that a
+//! region shrunk by a small window grows back when the window grows. They can be changed with
+//! `set_region_size()`, or, when splitters are enabled, by dragging the gap between a region
+//! and the Center.
+//!
+
+use crate::component::{Component, ComponentState};
+use crate::container::Container;
+use crate::event::MouseInput;
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::time::Instant;
+
+/// The smallest gap used between regions when splitters are enabled, so they can be grabbed.
+const MIN_SPLITTER_SIZE: u32 = 6;
+
+/// The smallest size a region can be dragged to with a splitter.
+const MIN_REGION_SIZE: u32 = 20;
+
 ///
-/// |------------------------|
-/// | North                  |
-/// |------------------------|
-/// |      |          |      |
-/// | West |  Center  | East |
-/// |      |          |      |
-/// |------------------------|
-/// | South                  |
-/// |------------------------|
+/// The five regions of a `BorderLayout`.
 ///
-/// ## Resize rules
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Region {
+    North,
+    West,
+    Center,
+    East,
+    South,
+}
+
+impl Region {
+    ///
+    /// The order in which `Container::push()` fills the regions.
+    ///
+    const PUSH_ORDER: [Region; 5] = [
+        Region::North,
+        Region::West,
+        Region::Center,
+        Region::East,
+        Region::South,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            Region::North => 0,
+            Region::West => 1,
+            Region::Center => 2,
+            Region::East => 3,
+            Region::South => 4,
+        }
+    }
+}
+
 ///
-/// - Center: Resizes `width` and `height` when `BorderLayout` resizes.
-/// - North: Resizes `width` when `BorderLayout` resizes.
-/// - South: Resizes `width` when `BorderLayout` resizes.
-/// - West: Resizes `height` when `BorderLayout` resizes.
-/// - East: Resizes `height` when `BorderLayout` resizes.
+/// A splitter that is being dragged, resizing a region.
 ///
-use crate::component::Component;
-use crate::container::{Container, ContainerComponent};
-use crate::event::MouseInput;
-use crate::geometry::{Point, Size};
-use crate::view::BufferContext;
+struct SplitterDrag {
+    region: Region,
+    start: Point,
+    start_size: u32,
+}
 
 ///
 /// BorderLayout: Implements `Container` and `Component`.
@@ -33,11 +93,26 @@ use crate::view::BufferContext;
 pub struct BorderLayout {
     position: Point,
     size: Size,
-    east: Option<Box<dyn ContainerComponent>>,
-    west: Option<Box<dyn ContainerComponent>>,
-    north: Option<Box<dyn ContainerComponent>>,
-    south: Option<Box<dyn ContainerComponent>>,
-    center: Option<Box<dyn ContainerComponent>>,
+    east: Option<Box<dyn Component>>,
+    west: Option<Box<dyn Component>>,
+    north: Option<Box<dyn Component>>,
+    south: Option<Box<dyn Component>>,
+    center: Option<Box<dyn Component>>,
+
+    /// The preferred size of each region along its resizable axis, in `Region::PUSH_ORDER`.
+    /// Unused for the Center.
+    preferred_sizes: [u32; 5],
+    horizontal_gap: u32,
+    vertical_gap: u32,
+    splitters: bool,
+    hovered_splitter: Option<Region>,
+    drag: Option<SplitterDrag>,
+}
+
+impl Default for BorderLayout {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BorderLayout {
@@ -53,32 +128,309 @@ impl BorderLayout {
             north: None,
             south: None,
             center: None,
+            preferred_sizes: [0; 5],
+            horizontal_gap: 0,
+            vertical_gap: 0,
+            splitters: false,
+            hovered_splitter: None,
+            drag: None,
         }
     }
-    pub fn set_east(&mut self, east: Box<dyn ContainerComponent>) {
-        self.east = Some(east);
+    pub fn set_east(&mut self, east: Box<dyn Component>) {
+        self.set_region(Region::East, east);
+    }
+    pub fn set_west(&mut self, west: Box<dyn Component>) {
+        self.set_region(Region::West, west);
     }
-    pub fn set_west(&mut self, east: Box<dyn ContainerComponent>) {
-        self.west = Some(east);
+    pub fn set_north(&mut self, north: Box<dyn Component>) {
+        self.set_region(Region::North, north);
     }
-    pub fn set_north(&mut self, east: Box<dyn ContainerComponent>) {
-        self.north = Some(east);
+    pub fn set_south(&mut self, south: Box<dyn Component>) {
+        self.set_region(Region::South, south);
     }
-    pub fn set_south(&mut self, east: Box<dyn ContainerComponent>) {
-        self.south = Some(east);
+    pub fn set_center(&mut self, center: Box<dyn Component>) {
+        self.set_region(Region::Center, center);
+    }
+
+    ///
+    /// Set the `Component` for the specified region, replacing any existing `Component`.
+    ///
+    pub fn set_region(&mut self, region: Region, component: Box<dyn Component>) {
+        let size = component.get_size();
+        let preferred = match region {
+            Region::North | Region::South => size.height,
+            _ => size.width,
+        };
+        *self.region_mut(region) = Some(component);
+        self.set_region_size(region, preferred);
     }
-    pub fn set_center(&mut self, east: Box<dyn ContainerComponent>) {
-        self.center = Some(east);
+
+    ///
+    /// Set the preferred size of a region: the `height` of North and South, or the `width` of
+    /// West and East. Ignored for the Center, which takes the space that is left.
+    ///
+    pub fn set_region_size(&mut self, region: Region, size: u32) {
+        self.preferred_sizes[region.index()] = size;
+        self.update_layout();
+    }
+
+    ///
+    /// Remove and return the `Component` in the specified region.
+    ///
+    pub fn take_region(&mut self, region: Region) -> Option<Box<dyn Component>> {
+        let component = self.region_mut(region).take();
+        self.update_layout();
+        component
+    }
+
+    ///
+    /// Set the horizontal gap between West, Center and East, and the vertical gap between
+    /// North, the middle regions, and South.
+    ///
+    pub fn set_gap(&mut self, horizontal: u32, vertical: u32) {
+        self.horizontal_gap = horizontal;
+        self.vertical_gap = vertical;
+        self.update_layout();
+    }
+
+    ///
+    /// Enable draggable splitters in the gaps between the Center and the other regions.
+    ///
+    pub fn set_splitters(&mut self, splitters: bool) {
+        self.splitters = splitters;
+        self.update_layout();
+    }
+
+    ///
+    /// Add the `Component` to the first empty region, in the order N -> W -> C -> E -> S.
+    ///
+    /// When all regions are in use, the `Component` is returned as the error.
+    ///
+    pub fn try_push(&mut self, component: Box<dyn Component>) -> Result<(), Box<dyn Component>> {
+        match Region::PUSH_ORDER
+            .into_iter()
+            .find(|region| self.region(*region).is_none())
+        {
+            Some(region) => {
+                self.set_region(region, component);
+                Ok(())
+            }
+            None => Err(component),
+        }
+    }
+
+    fn region(&self, region: Region) -> Option<&dyn Component> {
+        match region {
+            Region::North => self.north.as_deref(),
+            Region::West => self.west.as_deref(),
+            Region::Center => self.center.as_deref(),
+            Region::East => self.east.as_deref(),
+            Region::South => self.south.as_deref(),
+        }
+    }
+
+    fn region_mut(&mut self, region: Region) -> &mut Option<Box<dyn Component>> {
+        match region {
+            Region::North => &mut self.north,
+            Region::West => &mut self.west,
+            Region::Center => &mut self.center,
+            Region::East => &mut self.east,
+            Region::South => &mut self.south,
+        }
+    }
+
+    fn regions_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Component>> {
+        [
+            &mut self.north,
+            &mut self.west,
+            &mut self.center,
+            &mut self.east,
+            &mut self.south,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    ///
+    /// The gaps between regions, which are widened to hold splitters when enabled.
+    ///
+    fn gaps(&self) -> (u32, u32) {
+        if self.splitters {
+            (
+                self.horizontal_gap.max(MIN_SPLITTER_SIZE),
+                self.vertical_gap.max(MIN_SPLITTER_SIZE),
+            )
+        } else {
+            (self.horizontal_gap, self.vertical_gap)
+        }
+    }
+
+    ///
+    /// The preferred size of a region along its resizable axis: `height` for North and South,
+    /// `width` for West and East. Zero for an empty region.
+    ///
+    fn preferred_size(&self, region: Region) -> u32 {
+        match (region, self.region(region)) {
+            (Region::Center, _) | (_, None) => 0,
+            _ => self.preferred_sizes[region.index()],
+        }
+    }
+
+    ///
+    /// Compute the bounds of each region, and of the gaps between them, from the current
+    /// position, size, and region preferred sizes.
+    ///
+    fn layout(&self) -> BorderBounds {
+        let (hgap, vgap) = self.gaps();
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width, self.size.height);
+
+        let north_h = self.preferred_size(Region::North).min(height);
+        let north_gap = if self.north.is_some() { vgap } else { 0 };
+        let south_h = self
+            .preferred_size(Region::South)
+            .min(height.saturating_sub(north_h + north_gap));
+        let south_gap = if self.south.is_some() { vgap } else { 0 };
+        let middle_y = y + (north_h + north_gap) as f64;
+        let middle_h = height.saturating_sub(north_h + north_gap + south_h + south_gap);
+
+        let west_w = self.preferred_size(Region::West).min(width);
+        let west_gap = if self.west.is_some() { hgap } else { 0 };
+        let east_w = self
+            .preferred_size(Region::East)
+            .min(width.saturating_sub(west_w + west_gap));
+        let east_gap = if self.east.is_some() { hgap } else { 0 };
+        let center_x = x + (west_w + west_gap) as f64;
+        let center_w = width.saturating_sub(west_w + west_gap + east_w + east_gap);
+
+        let south_y = y + (height - south_h) as f64;
+        let east_x = x + (width - east_w) as f64;
+        BorderBounds {
+            north: Rectangle::new(x, y, width, north_h),
+            south: Rectangle::new(x, south_y, width, south_h),
+            west: Rectangle::new(x, middle_y, west_w, middle_h),
+            east: Rectangle::new(east_x, middle_y, east_w, middle_h),
+            center: Rectangle::new(center_x, middle_y, center_w, middle_h),
+            north_gap: Rectangle::new(x, y + north_h as f64, width, north_gap),
+            south_gap: Rectangle::new(x, south_y - south_gap as f64, width, south_gap),
+            west_gap: Rectangle::new(x + west_w as f64, middle_y, west_gap, middle_h),
+            east_gap: Rectangle::new(east_x - east_gap as f64, middle_y, east_gap, middle_h),
+        }
+    }
+
+    ///
+    /// Find the splitter at the specified coordinates.
+    ///
+    fn splitter_at(&self, x: f64, y: f64) -> Option<Region> {
+        if !self.splitters {
+            return None;
+        }
+        let bounds = self.layout();
+        [
+            (Region::North, &bounds.north_gap),
+            (Region::South, &bounds.south_gap),
+            (Region::West, &bounds.west_gap),
+            (Region::East, &bounds.east_gap),
+        ]
+        .into_iter()
+        .find(|(region, gap)| self.region(*region).is_some() && gap.contains(x, y))
+        .map(|(region, _)| region)
+    }
+
+    ///
+    /// Resize the region being dragged, following the mouse.
+    ///
+    fn drag_splitter_to(&mut self, x: f64, y: f64) {
+        let Some(drag) = self.drag.as_ref() else {
+            return;
+        };
+        let (dx, dy) = (x - drag.start.x, y - drag.start.y);
+        let delta = match drag.region {
+            Region::North => dy,
+            Region::South => -dy,
+            Region::West => dx,
+            Region::East => -dx,
+            Region::Center => 0.0,
+        };
+        let region = drag.region;
+        let available = self.available_size(region);
+        let new_size = (drag.start_size as f64 + delta)
+            .round()
+            .clamp(MIN_REGION_SIZE.min(available) as f64, available as f64)
+            as u32;
+        self.set_region_size(region, new_size);
+    }
+
+    ///
+    /// The largest size a region can be dragged to, leaving a minimum size for the Center.
+    ///
+    fn available_size(&self, region: Region) -> u32 {
+        let (hgap, vgap) = self.gaps();
+        let (total, other) = match region {
+            Region::North => (self.size.height, Region::South),
+            Region::South => (self.size.height, Region::North),
+            Region::West => (self.size.width, Region::East),
+            Region::East => (self.size.width, Region::West),
+            Region::Center => return 0,
+        };
+        let gap = match region {
+            Region::North | Region::South => vgap,
+            _ => hgap,
+        };
+        let other_size = self.preferred_size(other);
+        let other_gap = if self.region(other).is_some() { gap } else { 0 };
+        total.saturating_sub(other_size + other_gap + gap + MIN_REGION_SIZE)
+    }
+}
+
+///
+/// The computed bounds of each region of a `BorderLayout`, and of the gaps beside the Center.
+///
+struct BorderBounds {
+    north: Rectangle,
+    south: Rectangle,
+    west: Rectangle,
+    east: Rectangle,
+    center: Rectangle,
+    north_gap: Rectangle,
+    south_gap: Rectangle,
+    west_gap: Rectangle,
+    east_gap: Rectangle,
+}
+
+impl BorderBounds {
+    fn region(&self, region: Region) -> &Rectangle {
+        match region {
+            Region::North => &self.north,
+            Region::West => &self.west,
+            Region::Center => &self.center,
+            Region::East => &self.east,
+            Region::South => &self.south,
+        }
+    }
+
+    fn gap(&self, region: Region) -> Option<&Rectangle> {
+        match region {
+            Region::North => Some(&self.north_gap),
+            Region::West => Some(&self.west_gap),
+            Region::East => Some(&self.east_gap),
+            Region::South => Some(&self.south_gap),
+            Region::Center => None,
+        }
     }
 }
 
 impl Container for BorderLayout {
-    fn push(&mut self, _component: Box<dyn Component>) {
-        //
-        // TODO: Return an error?
-        //
-        // TODO: Cycle through and add: N -> W -> C -> E -> S.
-        //
+    ///
+    /// Add the `Component` to the first empty region, in the order N -> W -> C -> E -> S.
+    ///
+    /// When all regions are in use the `Component` is dropped, and an error is printed. Use
+    /// `BorderLayout::try_push()` to handle this case.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        if self.try_push(component).is_err() {
+            eprintln!("Error: BorderLayout::push() called when all regions are in use");
+        }
     }
 
     ///
@@ -94,72 +446,49 @@ impl Container for BorderLayout {
     /// Passes in the parent container `width` and `height`.
     ///
     fn resize(&mut self, width: usize, height: usize) {
-        println!("BorderLayout::resize");
         self.size.width = width as u32;
         self.size.height = height as u32;
         self.update_layout();
     }
-    fn update_layout(&mut self) {
-        let ww = if let Some(comp) = self.west.as_mut() {
-            comp.get_size().width
-        } else {
-            0
-        };
-        let ew = if let Some(comp) = self.east.as_mut() {
-            comp.get_size().width
-        } else {
-            0
-        };
-        let nh = if let Some(comp) = self.north.as_mut() {
-            comp.get_size().height
-        } else {
-            0
-        };
-        let sh = if let Some(comp) = self.south.as_mut() {
-            comp.get_size().height
-        } else {
-            0
-        };
 
-        let cw = self.size.width - ww - ew;
-        let ch = self.size.height - nh - sh;
-
-        if let Some(comp) = self.east.as_mut() {
-            comp.resize(comp.get_size().width as usize, self.size.height as usize);
-        }
-        if let Some(comp) = self.west.as_mut() {
-            comp.resize(comp.get_size().width as usize, self.size.height as usize);
-        };
-        if let Some(comp) = self.north.as_mut() {
-            comp.set_position(self.position.x, self.position.y);
-            comp.resize(self.size.width as usize, comp.get_size().height as usize);
-        }
-        if let Some(comp) = self.south.as_mut() {
-            comp.resize(self.size.width as usize, comp.get_size().height as usize);
-        }
-        if let Some(comp) = self.center.as_mut() {
-            comp.set_position(ww as f64, nh as f64);
-            comp.resize(cw as usize, ch as usize);
+    ///
+    /// Position and size each region within the `BorderLayout`.
+    ///
+    fn update_layout(&mut self) {
+        let bounds = self.layout();
+        for region in Region::PUSH_ORDER {
+            let rect = bounds.region(region).clone();
+            if let Some(comp) = self.region_mut(region).as_mut() {
+                comp.set_position(rect.point.x, rect.point.y);
+                comp.set_size(rect.size.width as usize, rect.size.height as usize);
+            }
         }
     }
 }
 
 impl Component for BorderLayout {
     fn handle_mouse_event(&mut self, input: MouseInput) {
-        if let Some(comp) = self.east.as_mut() {
-            comp.handle_mouse_event(input);
-        }
-        if let Some(comp) = self.west.as_mut() {
-            comp.handle_mouse_event(input);
-        };
-        if let Some(comp) = self.north.as_mut() {
-            comp.handle_mouse_event(input);
+        let (x, y) = (input.position.x, input.position.y);
+        if input.pressed {
+            if let Some(region) = self.splitter_at(x, y) {
+                self.drag = Some(SplitterDrag {
+                    region,
+                    start: input.position,
+                    start_size: self.preferred_size(region),
+                });
+            }
+        } else if self.drag.is_some() {
+            self.drag_splitter_to(x, y);
         }
-        if let Some(comp) = self.south.as_mut() {
-            comp.handle_mouse_event(input);
+        self.hovered_splitter = self.splitter_at(x, y);
+
+        let dragging = self.drag.is_some();
+        if input.just_released {
+            self.drag = None;
         }
-        if let Some(comp) = self.center.as_mut() {
-            comp.handle_mouse_event(input);
+        let child_input = if dragging { input.elsewhere() } else { input };
+        for comp in self.regions_mut() {
+            comp.handle_mouse_event(child_input);
         }
     }
 
@@ -168,56 +497,58 @@ impl Component for BorderLayout {
         delta: &winit::event::MouseScrollDelta,
         phase: &winit::event::TouchPhase,
     ) {
-        if let Some(comp) = self.east.as_mut() {
-            comp.handle_mouse_wheel_event(delta, phase);
-        }
-        if let Some(comp) = self.west.as_mut() {
-            comp.handle_mouse_wheel_event(delta, phase);
-        };
-        if let Some(comp) = self.north.as_mut() {
-            comp.handle_mouse_wheel_event(delta, phase);
-        }
-        if let Some(comp) = self.south.as_mut() {
-            comp.handle_mouse_wheel_event(delta, phase);
-        }
-        if let Some(comp) = self.center.as_mut() {
+        for comp in self.regions_mut() {
             comp.handle_mouse_wheel_event(delta, phase);
         }
     }
 
     fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
-        if let Some(comp) = self.east.as_mut() {
+        for comp in self.regions_mut() {
             comp.handle_keyboard_event(event);
         }
-        if let Some(comp) = self.west.as_mut() {
-            comp.handle_keyboard_event(event);
-        };
-        if let Some(comp) = self.north.as_mut() {
-            comp.handle_keyboard_event(event);
-        }
-        if let Some(comp) = self.south.as_mut() {
-            comp.handle_keyboard_event(event);
-        }
-        if let Some(comp) = self.center.as_mut() {
-            comp.handle_keyboard_event(event);
+    }
+
+    fn animate(&mut self, now: Instant) {
+        for comp in self.regions_mut() {
+            comp.animate(now);
         }
     }
 
     fn draw(&self, context: &mut BufferContext) {
-        if let Some(comp) = self.east.as_deref() {
-            comp.draw(context);
-        }
-        if let Some(comp) = self.west.as_deref() {
-            comp.draw(context);
-        };
-        if let Some(comp) = self.north.as_deref() {
-            comp.draw(context);
+        let bounds = self.layout();
+        for region in Region::PUSH_ORDER {
+            if let Some(comp) = self.region(region) {
+                context.with_clip(bounds.region(region), |context| {
+                    comp.draw(context);
+                });
+            }
         }
-        if let Some(comp) = self.south.as_deref() {
-            comp.draw(context);
+        if !self.splitters {
+            return;
         }
-        if let Some(comp) = self.center.as_deref() {
-            comp.draw(context);
+        for region in Region::PUSH_ORDER {
+            let Some(gap) = bounds.gap(region) else {
+                continue;
+            };
+            if self.region(region).is_none() {
+                continue;
+            }
+            let dragged = self.drag.as_ref().is_some_and(|d| d.region == region);
+            let state = if dragged {
+                ComponentState::Pressed
+            } else if self.hovered_splitter == Some(region) {
+                ComponentState::Hovered
+            } else {
+                ComponentState::Disabled
+            };
+            let color = context.theme.style_for(&state).back_color.value;
+            context.fill_rect(
+                gap.point.x,
+                gap.point.y,
+                gap.size.width,
+                gap.size.height,
+                color,
+            );
         }
     }
 
@@ -234,6 +565,7 @@ impl Component for BorderLayout {
     fn set_size(&mut self, width: usize, height: usize) {
         self.size.width = width as u32;
         self.size.height = height as u32;
+        self.update_layout();
     }
 
     fn get_size(&self) -> Size {
@@ -0,0 +1,658 @@
+//!
+//! # **Structura**: ConstraintLayout
+//!
+//! Implements `Container` and `Component`.
+//!
+//! Positions children by solving linear constraints between their edges, e.g.
+//! `label.right + 8 == field.left` or `field.width >= 200`, for layouts that are hard to
+//! express with nested rows and columns.
+//!
+//! Each constraint has a `Strength`. `Strength::REQUIRED` constraints must hold; weaker
+//! constraints are satisfied where possible, stronger ones first. The constraints are kept in
+//! an incremental Cassowary solver, so adding or removing a constraint, or resizing the
+//! `ConstraintLayout`, only re-solves what changed.
+//!
+//! ```ignore
+//! let mut layout = ConstraintLayout::new();
+//! let label = layout.add("label", Box::new(Button::default()));
+//! let field = layout.add("field", Box::new(TextArea::new()));
+//! let parent = layout.bounds();
+//!
+//! layout.equal(label.left(), parent.left() + 8.0, Strength::REQUIRED).ok();
+//! layout.equal(label.right() + 8.0, field.left(), Strength::REQUIRED).ok();
+//! layout.at_least(field.width(), 200.0, Strength::STRONG).ok();
+//! layout.equal(field.right(), parent.right() - 8.0, Strength::MEDIUM).ok();
+//! ```
+//!
+//! Constraints that conflict are reported as `ConstraintDiagnostic`s, see
+//! `ConstraintLayout::diagnostics()`.
+//!
+
+use crate::component::Component;
+use crate::container::{Container, MouseGrab};
+use crate::event::MouseInput;
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use kasuari::{Constraint, Solver, Variable};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
+
+pub use kasuari::{Expression, RelationalOperator, Strength};
+
+///
+/// Strength used to hold the `ConstraintLayout` at its own size. Just below
+/// `Strength::REQUIRED`, which the solver does not allow for sizes that change.
+///
+const PARENT_STRENGTH: Strength = Strength::new(1_000_000_000.0);
+
+///
+/// A solved value further than this from its constraint, in pixels, is reported as unsatisfied.
+///
+const TOLERANCE: f64 = 0.5;
+
+///
+/// The solver variables for the edges of a child, or of the `ConstraintLayout` itself.
+///
+/// Coordinates are relative to the `ConstraintLayout` position.
+///
+#[derive(Debug, Copy, Clone)]
+pub struct Bounds {
+    left: Variable,
+    top: Variable,
+    width: Variable,
+    height: Variable,
+}
+
+impl Bounds {
+    fn new() -> Self {
+        Self {
+            left: Variable::new(),
+            top: Variable::new(),
+            width: Variable::new(),
+            height: Variable::new(),
+        }
+    }
+
+    pub fn left(&self) -> Expression {
+        Expression::from_variable(self.left)
+    }
+
+    pub fn top(&self) -> Expression {
+        Expression::from_variable(self.top)
+    }
+
+    pub fn width(&self) -> Expression {
+        Expression::from_variable(self.width)
+    }
+
+    pub fn height(&self) -> Expression {
+        Expression::from_variable(self.height)
+    }
+
+    pub fn right(&self) -> Expression {
+        self.left() + self.width()
+    }
+
+    pub fn bottom(&self) -> Expression {
+        self.top() + self.height()
+    }
+
+    pub fn center_x(&self) -> Expression {
+        self.left() + self.width() / 2.0
+    }
+
+    pub fn center_y(&self) -> Expression {
+        self.top() + self.height() / 2.0
+    }
+}
+
+///
+/// Identifies a constraint added to a `ConstraintLayout`.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ConstraintId(usize);
+
+///
+/// Why a constraint does not hold.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DiagnosticKind {
+    /// A `Strength::REQUIRED` constraint conflicts with the required constraints already in
+    /// place. It was not added.
+    Unsatisfiable,
+
+    /// A weaker constraint lost to stronger ones, and is off by `error` pixels.
+    Unsatisfied { error: f64 },
+}
+
+///
+/// A constraint that does not hold, with a readable description such as
+/// `label.left + label.width + 8 == field.left`.
+///
+/// `constraint` is `None` when the `ConstraintLayout` itself cannot fit its size.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintDiagnostic {
+    pub constraint: Option<ConstraintId>,
+    pub description: String,
+    pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for ConstraintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            DiagnosticKind::Unsatisfiable => {
+                write!(f, "unsatisfiable constraint: {}", self.description)
+            }
+            DiagnosticKind::Unsatisfied { error } => {
+                write!(
+                    f,
+                    "unsatisfied constraint: {} (off by {:.1}px)",
+                    self.description, error
+                )
+            }
+        }
+    }
+}
+
+///
+/// A child `Component` with its solver variables.
+///
+struct ConstrainedChild {
+    component: Box<dyn Component>,
+    bounds: Bounds,
+}
+
+impl ConstrainedChild {
+    fn rectangle(&self) -> Rectangle {
+        let position = self.component.get_position();
+        let size = self.component.get_size();
+        Rectangle::new(position.x, position.y, size.width, size.height)
+    }
+}
+
+///
+/// A constraint added to the solver, with its description.
+///
+struct ActiveConstraint {
+    constraint: Constraint,
+    description: String,
+}
+
+///
+/// ConstraintLayout: Implements `Container` and `Component`.
+///
+pub struct ConstraintLayout {
+    position: Point,
+    size: Size,
+    solver: Solver,
+    parent: Bounds,
+    names: HashMap<Variable, String>,
+    children: Vec<ConstrainedChild>,
+    constraints: HashMap<ConstraintId, ActiveConstraint>,
+    next_constraint: usize,
+    rejected: Vec<ConstraintDiagnostic>,
+    unsatisfied: Vec<ConstraintDiagnostic>,
+
+    /// The child that was pressed, which sees the mouse events until the button is released.
+    grab: MouseGrab,
+}
+
+impl Default for ConstraintLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConstraintLayout {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        let parent = Bounds::new();
+        let mut layout = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            solver: Solver::new(),
+            parent,
+            names: HashMap::new(),
+            children: vec![],
+            constraints: HashMap::new(),
+            next_constraint: 0,
+            rejected: vec![],
+            unsatisfied: vec![],
+            grab: MouseGrab::default(),
+        };
+        layout.name_bounds("parent", &parent);
+
+        // The parent origin is fixed, its size follows `Container::resize()`.
+        let fixed = [
+            Constraint::new(parent.left(), RelationalOperator::Equal, Strength::REQUIRED),
+            Constraint::new(parent.top(), RelationalOperator::Equal, Strength::REQUIRED),
+        ];
+        for constraint in fixed {
+            layout.solver.add_constraint(constraint).ok();
+        }
+        layout
+            .solver
+            .add_edit_variable(parent.width, PARENT_STRENGTH)
+            .ok();
+        layout
+            .solver
+            .add_edit_variable(parent.height, PARENT_STRENGTH)
+            .ok();
+        layout
+    }
+
+    ///
+    /// Returns the `Bounds` of the `ConstraintLayout` itself, named `parent` in diagnostics.
+    /// Its `left` and `top` are always `0`.
+    ///
+    pub fn bounds(&self) -> Bounds {
+        self.parent
+    }
+
+    ///
+    /// Add the specified `Component`, returning its `Bounds` for use in constraints. The `name`
+    /// is used in diagnostics.
+    ///
+    /// The child keeps its current size and position (relative to the `ConstraintLayout`)
+    /// until constraints say otherwise: these are added as `Strength::WEAK` constraints.
+    ///
+    pub fn add(&mut self, name: &str, component: Box<dyn Component>) -> Bounds {
+        let bounds = Bounds::new();
+        self.name_bounds(name, &bounds);
+
+        let origin = component.get_position();
+        let preferred = component.get_size();
+        let defaults = [
+            (
+                bounds.width(),
+                RelationalOperator::GreaterOrEqual,
+                0.0,
+                Strength::REQUIRED,
+            ),
+            (
+                bounds.height(),
+                RelationalOperator::GreaterOrEqual,
+                0.0,
+                Strength::REQUIRED,
+            ),
+            (
+                bounds.left(),
+                RelationalOperator::Equal,
+                origin.x,
+                Strength::WEAK,
+            ),
+            (
+                bounds.top(),
+                RelationalOperator::Equal,
+                origin.y,
+                Strength::WEAK,
+            ),
+            (
+                bounds.width(),
+                RelationalOperator::Equal,
+                preferred.width as f64,
+                Strength::WEAK,
+            ),
+            (
+                bounds.height(),
+                RelationalOperator::Equal,
+                preferred.height as f64,
+                Strength::WEAK,
+            ),
+        ];
+        for (lhs, relation, rhs, strength) in defaults {
+            self.solver
+                .add_constraint(Constraint::new(lhs - rhs, relation, strength))
+                .ok();
+        }
+
+        self.children.push(ConstrainedChild { component, bounds });
+        self.update_layout();
+        bounds
+    }
+
+    ///
+    /// Add the constraint `lhs <relation> rhs` with the specified `strength`, and re-solve.
+    ///
+    /// A `Strength::REQUIRED` constraint that conflicts with the existing required constraints
+    /// is not added: the returned `ConstraintDiagnostic` is also kept in `diagnostics()`.
+    ///
+    pub fn constrain(
+        &mut self,
+        lhs: impl Into<Expression>,
+        relation: RelationalOperator,
+        rhs: impl Into<Expression>,
+        strength: Strength,
+    ) -> Result<ConstraintId, ConstraintDiagnostic> {
+        let lhs = lhs.into();
+        let rhs = rhs.into();
+        let id = ConstraintId(self.next_constraint);
+        self.next_constraint += 1;
+
+        let description = format!(
+            "{} {} {}",
+            self.describe(&lhs),
+            relation,
+            self.describe(&rhs)
+        );
+        let constraint = Constraint::new(lhs - rhs, relation, strength);
+        if self.solver.add_constraint(constraint.clone()).is_err() {
+            let diagnostic = ConstraintDiagnostic {
+                constraint: Some(id),
+                description,
+                kind: DiagnosticKind::Unsatisfiable,
+            };
+            eprintln!("ConstraintLayout: {diagnostic}");
+            self.rejected.push(diagnostic.clone());
+            return Err(diagnostic);
+        }
+
+        self.constraints.insert(
+            id,
+            ActiveConstraint {
+                constraint,
+                description,
+            },
+        );
+        self.update_layout();
+        Ok(id)
+    }
+
+    ///
+    /// Add the constraint `lhs == rhs`.
+    ///
+    pub fn equal(
+        &mut self,
+        lhs: impl Into<Expression>,
+        rhs: impl Into<Expression>,
+        strength: Strength,
+    ) -> Result<ConstraintId, ConstraintDiagnostic> {
+        self.constrain(lhs, RelationalOperator::Equal, rhs, strength)
+    }
+
+    ///
+    /// Add the constraint `lhs >= rhs`.
+    ///
+    pub fn at_least(
+        &mut self,
+        lhs: impl Into<Expression>,
+        rhs: impl Into<Expression>,
+        strength: Strength,
+    ) -> Result<ConstraintId, ConstraintDiagnostic> {
+        self.constrain(lhs, RelationalOperator::GreaterOrEqual, rhs, strength)
+    }
+
+    ///
+    /// Add the constraint `lhs <= rhs`.
+    ///
+    pub fn at_most(
+        &mut self,
+        lhs: impl Into<Expression>,
+        rhs: impl Into<Expression>,
+        strength: Strength,
+    ) -> Result<ConstraintId, ConstraintDiagnostic> {
+        self.constrain(lhs, RelationalOperator::LessOrEqual, rhs, strength)
+    }
+
+    ///
+    /// Remove a constraint, and re-solve. Also clears any diagnostic for the constraint.
+    ///
+    pub fn remove_constraint(&mut self, id: ConstraintId) {
+        if let Some(active) = self.constraints.remove(&id) {
+            self.solver.remove_constraint(&active.constraint).ok();
+        }
+        self.rejected
+            .retain(|diagnostic| diagnostic.constraint != Some(id));
+        self.update_layout();
+    }
+
+    ///
+    /// Returns the constraints that currently do not hold: rejected required constraints,
+    /// followed by weaker constraints that lost to stronger ones at the current size.
+    ///
+    pub fn diagnostics(&self) -> impl Iterator<Item = &ConstraintDiagnostic> {
+        self.rejected.iter().chain(self.unsatisfied.iter())
+    }
+
+    fn name_bounds(&mut self, name: &str, bounds: &Bounds) {
+        self.names.insert(bounds.left, format!("{name}.left"));
+        self.names.insert(bounds.top, format!("{name}.top"));
+        self.names.insert(bounds.width, format!("{name}.width"));
+        self.names.insert(bounds.height, format!("{name}.height"));
+    }
+
+    ///
+    /// Returns a readable form of `expression`, using the variable names.
+    ///
+    fn describe(&self, expression: &Expression) -> String {
+        let mut text = String::new();
+        for term in expression.terms.iter() {
+            let name = self
+                .names
+                .get(&term.variable)
+                .map(String::as_str)
+                .unwrap_or("?");
+            let coefficient = term.coefficient;
+            let sign = if coefficient < 0.0 { "-" } else { "+" };
+            if text.is_empty() {
+                if coefficient < 0.0 {
+                    text.push('-');
+                }
+            } else {
+                text.push_str(&format!(" {sign} "));
+            }
+            if coefficient.abs() != 1.0 {
+                text.push_str(&format!("{}*", coefficient.abs()));
+            }
+            text.push_str(name);
+        }
+        let constant = expression.constant;
+        if text.is_empty() {
+            text = format!("{constant}");
+        } else if constant != 0.0 {
+            let sign = if constant < 0.0 { "-" } else { "+" };
+            text.push_str(&format!(" {sign} {}", constant.abs()));
+        }
+        text
+    }
+
+    fn value_of(&self, expression: &Expression) -> f64 {
+        expression
+            .terms
+            .iter()
+            .map(|term| term.coefficient * self.solver.get_value(term.variable))
+            .sum::<f64>()
+            + expression.constant
+    }
+
+    ///
+    /// Collect the constraints that do not hold after solving, reporting any that newly fail.
+    /// Skipped until the `ConstraintLayout` has a size, as nothing fits before then.
+    ///
+    fn check_constraints(&mut self) {
+        let mut unsatisfied = vec![];
+        if self.size.width == 0 && self.size.height == 0 {
+            self.unsatisfied = unsatisfied;
+            return;
+        }
+        for (id, active) in self.constraints.iter() {
+            let value = self.value_of(active.constraint.expr());
+            let error = match active.constraint.op() {
+                RelationalOperator::Equal => value.abs(),
+                RelationalOperator::GreaterOrEqual => (-value).max(0.0),
+                RelationalOperator::LessOrEqual => value.max(0.0),
+            };
+            if error > TOLERANCE {
+                unsatisfied.push(ConstraintDiagnostic {
+                    constraint: Some(*id),
+                    description: active.description.clone(),
+                    kind: DiagnosticKind::Unsatisfied { error },
+                });
+            }
+        }
+        unsatisfied.sort_by_key(|diagnostic| diagnostic.constraint.map(|id| id.0));
+
+        let sizes = [
+            ("parent.width", self.parent.width, self.size.width),
+            ("parent.height", self.parent.height, self.size.height),
+        ];
+        for (name, variable, expected) in sizes {
+            let error = (self.solver.get_value(variable) - expected as f64).abs();
+            if error > TOLERANCE {
+                unsatisfied.push(ConstraintDiagnostic {
+                    constraint: None,
+                    description: format!("{name} == {expected}"),
+                    kind: DiagnosticKind::Unsatisfied { error },
+                });
+            }
+        }
+
+        for diagnostic in unsatisfied.iter() {
+            let known = self.unsatisfied.iter().any(|previous| {
+                previous.constraint == diagnostic.constraint
+                    && previous.description == diagnostic.description
+            });
+            if !known {
+                eprintln!("ConstraintLayout: {diagnostic}");
+            }
+        }
+        self.unsatisfied = unsatisfied;
+    }
+
+    ///
+    /// Returns the index of the topmost child at the specified coordinates.
+    ///
+    fn child_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.children
+            .iter()
+            .rposition(|child| child.rectangle().contains(x, y))
+    }
+}
+
+impl Container for ConstraintLayout {
+    ///
+    /// Add the specified `Component` with a generated name, `child0`, `child1`, etc. Use
+    /// `ConstraintLayout::add()` to get its `Bounds` for constraints.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        let name = format!("child{}", self.children.len());
+        self.add(&name, component);
+    }
+
+    ///
+    /// The `ConstraintLayout` always fills its parent `Container`.
+    ///
+    fn fills_parent_container(&self) -> bool {
+        true
+    }
+
+    ///
+    /// Called when the parent `Container` resizes.
+    ///
+    /// Passes in the parent container `width` and `height`.
+    ///
+    fn resize(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    ///
+    /// Re-solve the constraints for the current size, then position and size each child.
+    ///
+    fn update_layout(&mut self) {
+        self.solver
+            .suggest_value(self.parent.width, self.size.width as f64)
+            .ok();
+        self.solver
+            .suggest_value(self.parent.height, self.size.height as f64)
+            .ok();
+        self.solver.fetch_changes();
+        self.check_constraints();
+
+        for child in self.children.iter_mut() {
+            let bounds = child.bounds;
+            let x = self.solver.get_value(bounds.left).round();
+            let y = self.solver.get_value(bounds.top).round();
+            let width = self.solver.get_value(bounds.width).round().max(0.0);
+            let height = self.solver.get_value(bounds.height).round().max(0.0);
+            child
+                .component
+                .set_position(self.position.x + x, self.position.y + y);
+            child.component.set_size(width as usize, height as usize);
+        }
+    }
+}
+
+impl Component for ConstraintLayout {
+    ///
+    /// Children may overlap, so only the topmost child under the cursor sees the event where
+    /// it happened. The other children see it as happening elsewhere. See `MouseGrab`.
+    ///
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let under_cursor = self.child_at(input.position.x, input.position.y);
+        let target = self.grab.target(&input, under_cursor);
+        for (index, child) in self.children.iter_mut().enumerate() {
+            if target.is_none() || target == Some(index) {
+                child.component.handle_mouse_event(input);
+            } else {
+                child.component.handle_mouse_event(input.elsewhere());
+            }
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        for child in self.children.iter_mut() {
+            child.component.handle_mouse_wheel_event(delta, phase);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        for child in self.children.iter_mut() {
+            child.component.handle_keyboard_event(event);
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        for child in self.children.iter_mut() {
+            child.component.animate(now);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        for child in self.children.iter() {
+            child.component.draw(context);
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position.x = x;
+        self.position.y = y;
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,379 @@
+//!
+//! # **Structura**: Expander
+//!
+//! Implements `Container` and `Component`.
+//!
+//! A header with a disclosure arrow and a title, above a single child `Component` that is
+//! shown or hidden when the header is clicked:
+//!
+//! ```text
+//! |-----------------------------|
+//! | v Title                     |
+//! |-----------------------------|
+//! |  Child Component            |
+//! |-----------------------------|
+//! ```
+//!
+//! - Click the header, or press `Space` / `Enter` after clicking it, to expand or collapse.
+//!   Other keys, and keys pressed after clicking the child, go to the child.
+//! - The height animates between the header height and the header plus the child height, so
+//!   the siblings in a parent `Column` or `Row` reflow smoothly.
+//! - The `Expander` takes its width from its parent, and its height from the child.
+//!
+
+use crate::animation::Tween;
+use crate::component::{Component, ComponentState};
+use crate::container::Container;
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::time::{Duration, Instant};
+use winit::event::ElementState;
+use winit::keyboard::{Key, NamedKey};
+
+/// Text color for the title and disclosure arrow.
+const TITLE_COLOR: u32 = 0xFFFFFFFF;
+
+/// Default time taken to expand or collapse.
+const ANIMATION_DURATION: Duration = Duration::from_millis(200);
+
+///
+/// Expander: Implements `Container` and `Component`.
+///
+pub struct Expander {
+    position: Point,
+    width: u32,
+    title: String,
+    content: Option<Box<dyn Component>>,
+    header_height: u32,
+    expanded: bool,
+    progress: Tween,
+    hovered: bool,
+    pressed: bool,
+
+    /// Focused by a click on the header.
+    focus_id: FocusId,
+    on_toggle: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Expander {
+    ///
+    /// Constructor, collapsed.
+    ///
+    pub fn new(title: String, content: Box<dyn Component>) -> Self {
+        let mut expander = Self {
+            position: Point { x: 0.0, y: 0.0 },
+            width: content.get_size().width,
+            title,
+            content: Some(content),
+            header_height: 50,
+            expanded: false,
+            progress: Tween::new(0.0, ANIMATION_DURATION),
+            hovered: false,
+            pressed: false,
+            focus_id: FocusId::new(),
+            on_toggle: None,
+        };
+        expander.update_layout();
+        expander
+    }
+
+    ///
+    /// Start expanded or collapsed, without animating.
+    ///
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self.progress.set(if expanded { 1.0 } else { 0.0 });
+        self
+    }
+
+    pub fn set_header_height(mut self, header_height: u32) -> Self {
+        self.header_height = header_height;
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Set the time taken to expand or collapse. A zero `Duration` switches immediately.
+    ///
+    pub fn set_animation_duration(mut self, duration: Duration) -> Self {
+        self.progress.set_duration(duration);
+        self
+    }
+
+    ///
+    /// Called with the new state whenever the `Expander` expands or collapses.
+    ///
+    pub fn on_toggle<F: FnMut(bool) + 'static>(mut self, callback: F) -> Self {
+        self.on_toggle = Some(Box::new(callback));
+        self
+    }
+
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        self.title = title;
+    }
+
+    ///
+    /// Returns true if the `Expander` is expanded, or animating towards expanded.
+    ///
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    ///
+    /// Expand or collapse, animating the change.
+    ///
+    pub fn set_expanded(&mut self, expanded: bool) {
+        if self.expanded == expanded {
+            return;
+        }
+        self.expanded = expanded;
+        self.progress.animate_to(if expanded { 1.0 } else { 0.0 });
+        if let Some(callback) = self.on_toggle.as_mut() {
+            callback(expanded);
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.set_expanded(!self.expanded);
+    }
+
+    fn content_height(&self) -> u32 {
+        self.content
+            .as_ref()
+            .map(|content| content.get_size().height)
+            .unwrap_or(0)
+    }
+
+    ///
+    /// Returns the visible height of the child, which grows and shrinks while animating.
+    ///
+    fn visible_content_height(&self) -> u32 {
+        (self.content_height() as f64 * self.progress.value()).round() as u32
+    }
+
+    fn header_bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.width,
+            self.header_height,
+        )
+    }
+
+    fn content_bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y + self.header_height as f64,
+            self.width,
+            self.visible_content_height(),
+        )
+    }
+
+    ///
+    /// Draw the disclosure arrow: pointing right when collapsed, turning to point down as the
+    /// `Expander` expands.
+    ///
+    fn draw_arrow(&self, context: &mut BufferContext) {
+        let cx = self.position.x + 20.0;
+        let cy = self.position.y + self.header_height as f64 / 2.0;
+        let angle = self.progress.value() * std::f64::consts::FRAC_PI_2;
+        let (sin, cos) = angle.sin_cos();
+        let rotate = |x: f64, y: f64| (cx + x * cos - y * sin, cy + x * sin + y * cos);
+        let (x0, y0) = rotate(-3.0, -7.0);
+        let (x1, y1) = rotate(4.0, 0.0);
+        let (x2, y2) = rotate(-3.0, 7.0);
+        context.draw_line(x0, y0, x1, y1, TITLE_COLOR);
+        context.draw_line(x1, y1, x2, y2, TITLE_COLOR);
+    }
+}
+
+impl Container for Expander {
+    ///
+    /// Replace the child `Component`.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.content = Some(component);
+        self.update_layout();
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        false
+    }
+
+    ///
+    /// Called when the parent `Container` resizes.
+    ///
+    /// The `Expander` height follows its child, so parent changes are ignored.
+    ///
+    fn resize(&mut self, _width: usize, _height: usize) {}
+
+    ///
+    /// Position the child below the header, at the `Expander` width.
+    ///
+    fn update_layout(&mut self) {
+        let x = self.position.x;
+        let y = self.position.y + self.header_height as f64;
+        let width = self.width as usize;
+        if let Some(content) = self.content.as_mut() {
+            let height = content.get_size().height as usize;
+            content.set_position(x, y);
+            content.set_size(width, height);
+        }
+    }
+}
+
+impl Component for Expander {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let (x, y) = (input.position.x, input.position.y);
+        let over_header = self.header_bounds().contains(x, y);
+        self.hovered = over_header;
+        if input.pressed {
+            if over_header {
+                focus::set_focus(self.focus_id);
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+            self.pressed = over_header;
+        }
+        if input.just_released {
+            if self.pressed && over_header {
+                self.toggle();
+            }
+            self.pressed = false;
+        }
+
+        let visible = self.content_bounds();
+        if let Some(content) = self.content.as_mut() {
+            if visible.size.height > 0 && visible.contains(x, y) {
+                content.handle_mouse_event(input);
+            } else {
+                content.handle_mouse_event(input.elsewhere());
+            }
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        if self.expanded
+            && let Some(content) = self.content.as_mut()
+        {
+            content.handle_mouse_wheel_event(delta, phase);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        let toggle_key = matches!(
+            event.logical_key,
+            Key::Named(NamedKey::Space) | Key::Named(NamedKey::Enter)
+        );
+        if focus::is_focused(self.focus_id) && toggle_key {
+            if event.state == ElementState::Pressed && !event.repeat {
+                self.toggle();
+            }
+            return;
+        }
+        if self.expanded
+            && let Some(content) = self.content.as_mut()
+        {
+            content.handle_keyboard_event(event);
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        self.progress.update(now);
+        if let Some(content) = self.content.as_mut() {
+            content.animate(now);
+        }
+    }
+
+    ///
+    /// Keeps whether the `Expander` is expanded.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.expanded))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(expanded) = state.downcast::<bool>() {
+            self.expanded = *expanded;
+            self.progress.set(if self.expanded { 1.0 } else { 0.0 });
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let visible = self.content_bounds();
+        if visible.size.height > 0
+            && let Some(content) = self.content.as_ref()
+        {
+            context.with_clip(&visible, |context| {
+                content.draw(context);
+            });
+        }
+
+        let state = if self.pressed {
+            ComponentState::Pressed
+        } else if self.hovered {
+            ComponentState::Hovered
+        } else if focus::is_focused(self.focus_id) {
+            ComponentState::Focused
+        } else {
+            ComponentState::Active
+        };
+        let style = context.theme.style_for(&state);
+        let header = self.header_bounds();
+        let (x, y, w, h) = (
+            header.point.x,
+            header.point.y,
+            header.size.width,
+            header.size.height,
+        );
+        context.fill_rect(x, y, w, h, style.back_color.value);
+        context.draw_border(x, y, w, h, style.border_width, style.border_color.value);
+        self.draw_arrow(context);
+
+        let title_bounds = Rectangle::new(x + 40.0, y, w.saturating_sub(50), h);
+        let text_y = y + (h as f64 - context.line_height() as f64) / 2.0;
+        context.with_clip(&title_bounds, |context| {
+            context.draw_text(&self.title, x + 40.0, text_y, TITLE_COLOR);
+        });
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position.x = x;
+        self.position.y = y;
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    ///
+    /// Sets the width only: the height follows the header and child.
+    ///
+    fn set_size(&mut self, width: usize, _height: usize) {
+        self.width = width as u32;
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        Size {
+            width: self.width,
+            height: self.header_height + self.visible_content_height(),
+        }
+    }
+}
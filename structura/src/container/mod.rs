@@ -4,12 +4,21 @@
 //! Container types
 //!
 
+pub mod accordion;
+pub mod anchor;
 pub mod border;
 pub mod column;
+pub mod constraint;
+pub mod expander;
 pub mod panel;
 pub mod row;
+pub mod scroll;
+pub mod statusbar;
+pub mod tabs;
+pub mod toolbar;
 
 use crate::component::Component;
+use crate::event::MouseInput;
 
 ///
 /// Container node for building UI component trees.
@@ -43,8 +52,59 @@ pub trait Container {
     fn fills_parent_container(&self) -> bool;
 }
 
+///
+/// A boxed `Container` is a `Container`, so that wrappers such as `Styled` can hold trait
+/// objects.
+///
+impl<T: Container + ?Sized> Container for Box<T> {
+    fn push(&mut self, component: Box<dyn Component>) {
+        (**self).push(component);
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        (**self).resize(width, height);
+    }
+
+    fn update_layout(&mut self) {
+        (**self).update_layout();
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        (**self).fills_parent_container()
+    }
+}
+
 ///
 /// Composite `Container` + `Component` trait.
 ///
 pub trait ContainerComponent: Container + Component {}
 impl<T> ContainerComponent for T where T: Container + Component {}
+
+///
+/// Keeps the mouse events on the child that was pressed until the button is released, for a
+/// `Container` whose children can overlap.
+///
+/// The `Container` asks `target()` which child sees each event where it happened, and passes
+/// the event to the other children with `MouseInput::elsewhere()`. Dragging a `Slider` then
+/// keeps working when the cursor crosses another child.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MouseGrab {
+    child: Option<usize>,
+}
+
+impl MouseGrab {
+    ///
+    /// Returns the index of the child that sees `input` where it happened: the pressed child
+    /// while the button is held, otherwise `under_cursor`.
+    ///
+    pub fn target(&mut self, input: &MouseInput, under_cursor: Option<usize>) -> Option<usize> {
+        let target = self.child.or(under_cursor);
+        if input.pressed {
+            self.child = target;
+        } else if input.just_released {
+            self.child = None;
+        }
+        target
+    }
+}
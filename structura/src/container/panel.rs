@@ -7,6 +7,7 @@ use crate::container::Container;
 use crate::event::MouseInput;
 use crate::geometry::{Point, Size};
 use crate::view::BufferContext;
+use std::time::Instant;
 
 ///
 /// A Container that holds a single component, and resizes to fit the bounds of its parent.
@@ -83,6 +84,12 @@ impl Component for Panel {
         }
     }
 
+    fn animate(&mut self, now: Instant) {
+        if let Some(comp) = self.component.as_mut() {
+            comp.animate(now);
+        }
+    }
+
     fn draw(&self, context: &mut BufferContext) {
         if let Some(comp) = self.component.as_deref() {
             comp.draw(context);
@@ -102,6 +109,7 @@ impl Component for Panel {
     fn set_size(&mut self, width: usize, height: usize) {
         self.size.width = width as u32;
         self.size.height = height as u32;
+        self.update_layout();
     }
 
     fn get_size(&self) -> Size {
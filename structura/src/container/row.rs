@@ -7,6 +7,7 @@ use crate::container::Container;
 use crate::event::MouseInput;
 use crate::geometry::{Point, Size};
 use crate::view::BufferContext;
+use std::time::Instant;
 
 ///
 /// Horizontal Row of components.
@@ -97,6 +98,16 @@ impl Component for Row {
         }
     }
 
+    ///
+    /// Children may change size while animating, so the layout is updated after each frame.
+    ///
+    fn animate(&mut self, now: Instant) {
+        for child in self.children.iter_mut() {
+            child.animate(now);
+        }
+        self.update_layout();
+    }
+
     fn draw(&self, context: &mut BufferContext) {
         for child in self.children.iter() {
             child.draw(context);
@@ -0,0 +1,612 @@
+//!
+//! # **Structura**: ScrollView
+//!
+//! Implements `Container` and `Component`.
+//!
+//! Shows a viewport onto a single child `Component` that may be larger than the `ScrollView`.
+//!
+//! - The child is positioned at the viewport origin minus the scroll offset, so it draws and
+//!   handles mouse input without knowing it has been scrolled.
+//! - Drawing of the child is clipped to the viewport.
+//! - Horizontal and vertical scrollbars are shown when the child overflows the viewport. The
+//!   thumb can be dragged, and clicking the track scrolls by one page.
+//! - The mouse wheel scrolls when the cursor is over the viewport. Hold `Shift` to scroll
+//!   horizontally. Once the view cannot scroll further, the wheel goes to the child, e.g. a
+//!   nested `ScrollView` or `TextArea`.
+//! - Arrow keys, `PageUp`, `PageDown`, `Home` and `End` scroll once the `ScrollView` has been
+//!   clicked, unless the click gave focus to a child.
+//!
+
+use crate::component::scrollbar;
+use crate::component::style::ColorFactor;
+use crate::component::{Component, ComponentState};
+use crate::container::Container;
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Orientation, Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::time::Instant;
+use winit::event::{ElementState, MouseScrollDelta};
+use winit::keyboard::{Key, NamedKey};
+
+///
+/// The part of a scrollbar under the mouse.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScrollbarPart {
+    Thumb,
+    TrackBefore,
+    TrackAfter,
+}
+
+///
+/// A scrollbar thumb that is being dragged.
+///
+struct ThumbDrag {
+    orientation: Orientation,
+    /// Distance from the start of the thumb to the mouse, along the scrollbar.
+    grab_offset: f64,
+}
+
+///
+/// ScrollView: Implements `Container` and `Component`.
+///
+pub struct ScrollView {
+    position: Point,
+    size: Size,
+    component: Option<Box<dyn Component>>,
+    scroll_x: f64,
+    scroll_y: f64,
+    scrollbar_width: u32,
+    line_scroll_amount: f64,
+    cursor: Option<Point>,
+    drag: Option<ThumbDrag>,
+    hovered: Option<(Orientation, ScrollbarPart)>,
+    focus_id: FocusId,
+}
+
+impl Default for ScrollView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScrollView {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            component: None,
+            scroll_x: 0.0,
+            scroll_y: 0.0,
+            scrollbar_width: 20,
+            line_scroll_amount: 40.0,
+            cursor: None,
+            drag: None,
+            hovered: None,
+            focus_id: FocusId::new(),
+        }
+    }
+
+    ///
+    /// Set the width of the scrollbars.
+    ///
+    pub fn set_scrollbar_width(mut self, scrollbar_width: u32) -> Self {
+        self.scrollbar_width = scrollbar_width;
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Set the distance scrolled by one line of mouse wheel or arrow key input.
+    ///
+    pub fn set_line_scroll_amount(mut self, amount: f64) -> Self {
+        self.line_scroll_amount = amount;
+        self
+    }
+
+    ///
+    /// Returns the current scroll offset of the child `Component`.
+    ///
+    pub fn scroll_offset(&self) -> Point {
+        Point {
+            x: self.scroll_x,
+            y: self.scroll_y,
+        }
+    }
+
+    ///
+    /// Scroll so that the point `(x, y)` of the child `Component` is at the viewport origin.
+    ///
+    pub fn scroll_to(&mut self, x: f64, y: f64) {
+        self.scroll_x = x;
+        self.scroll_y = y;
+        self.update_layout();
+    }
+
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    ///
+    /// Scroll by the specified amounts.
+    ///
+    pub fn scroll_by(&mut self, dx: f64, dy: f64) {
+        self.scroll_to(self.scroll_x + dx, self.scroll_y + dy);
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    fn content_size(&self) -> Size {
+        match self.component.as_ref() {
+            Some(comp) => comp.get_size(),
+            None => Size {
+                width: 0,
+                height: 0,
+            },
+        }
+    }
+
+    ///
+    /// Returns whether the horizontal and vertical scrollbars are shown. Showing one scrollbar
+    /// reduces the viewport, which may cause the other one to be needed.
+    ///
+    fn scrollbars_shown(&self) -> (bool, bool) {
+        let content = self.content_size();
+        let mut horizontal = content.width > self.size.width;
+        let mut vertical = content.height > self.size.height;
+        if horizontal && !vertical {
+            vertical = content.height > self.size.height.saturating_sub(self.scrollbar_width);
+        }
+        if vertical && !horizontal {
+            horizontal = content.width > self.size.width.saturating_sub(self.scrollbar_width);
+        }
+        (horizontal, vertical)
+    }
+
+    ///
+    /// The area in which the child `Component` is visible.
+    ///
+    fn viewport(&self) -> Rectangle {
+        let (horizontal, vertical) = self.scrollbars_shown();
+        let width = if vertical {
+            self.size.width.saturating_sub(self.scrollbar_width)
+        } else {
+            self.size.width
+        };
+        let height = if horizontal {
+            self.size.height.saturating_sub(self.scrollbar_width)
+        } else {
+            self.size.height
+        };
+        Rectangle::new(self.position.x, self.position.y, width, height)
+    }
+
+    fn max_scroll(&self) -> (f64, f64) {
+        let content = self.content_size();
+        let viewport = self.viewport();
+        (
+            (content.width as f64 - viewport.size.width as f64).max(0.0),
+            (content.height as f64 - viewport.size.height as f64).max(0.0),
+        )
+    }
+
+    fn scroll_for(&self, orientation: Orientation) -> f64 {
+        match orientation {
+            Orientation::Horizontal => self.scroll_x,
+            Orientation::Vertical => self.scroll_y,
+        }
+    }
+
+    fn set_scroll_for(&mut self, orientation: Orientation, value: f64) {
+        match orientation {
+            Orientation::Horizontal => self.scroll_to(value, self.scroll_y),
+            Orientation::Vertical => self.scroll_to(self.scroll_x, value),
+        }
+    }
+
+    ///
+    /// Returns the track of the scrollbar, or `None` when the scrollbar is hidden.
+    ///
+    fn track_bounds(&self, orientation: Orientation) -> Option<Rectangle> {
+        let (horizontal, vertical) = self.scrollbars_shown();
+        let viewport = self.viewport();
+        match orientation {
+            Orientation::Horizontal if horizontal => Some(Rectangle::new(
+                viewport.point.x,
+                viewport.point.y + viewport.size.height as f64,
+                viewport.size.width,
+                self.scrollbar_width,
+            )),
+            Orientation::Vertical if vertical => Some(Rectangle::new(
+                viewport.point.x + viewport.size.width as f64,
+                viewport.point.y,
+                self.scrollbar_width,
+                viewport.size.height,
+            )),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the `(start, length)` of the scrollbar thumb, along the track.
+    ///
+    fn thumb_extent(&self, orientation: Orientation, track: &Rectangle) -> (f64, f64) {
+        let content = self.content_size();
+        let (track_start, track_length, content_length) = match orientation {
+            Orientation::Horizontal => {
+                (track.point.x, track.size.width as f64, content.width as f64)
+            }
+            Orientation::Vertical => (
+                track.point.y,
+                track.size.height as f64,
+                content.height as f64,
+            ),
+        };
+        //
+        // The track runs along the whole viewport, so its length is the viewport length too.
+        //
+        scrollbar::thumb_extent(
+            track_start,
+            track_length,
+            content_length,
+            track_length,
+            self.scroll_for(orientation),
+        )
+    }
+
+    fn thumb_bounds(&self, orientation: Orientation) -> Option<Rectangle> {
+        let track = self.track_bounds(orientation)?;
+        let (start, length) = self.thumb_extent(orientation, &track);
+        Some(match orientation {
+            Orientation::Horizontal => {
+                Rectangle::new(start, track.point.y, length as u32, track.size.height)
+            }
+            Orientation::Vertical => {
+                Rectangle::new(track.point.x, start, track.size.width, length as u32)
+            }
+        })
+    }
+
+    ///
+    /// Find the scrollbar part at the specified coordinates.
+    ///
+    fn hit_test(&self, x: f64, y: f64) -> Option<(Orientation, ScrollbarPart)> {
+        for orientation in [Orientation::Horizontal, Orientation::Vertical] {
+            let Some(track) = self.track_bounds(orientation) else {
+                continue;
+            };
+            if !track.contains(x, y) {
+                continue;
+            }
+            let (start, length) = self.thumb_extent(orientation, &track);
+            let along = match orientation {
+                Orientation::Horizontal => x,
+                Orientation::Vertical => y,
+            };
+            let part = if along < start {
+                ScrollbarPart::TrackBefore
+            } else if along >= start + length {
+                ScrollbarPart::TrackAfter
+            } else {
+                ScrollbarPart::Thumb
+            };
+            return Some((orientation, part));
+        }
+        None
+    }
+
+    ///
+    /// Scroll so that the thumb starts at `thumb_start` along the track, while dragging.
+    ///
+    fn drag_thumb_to(&mut self, orientation: Orientation, thumb_start: f64) {
+        let Some(track) = self.track_bounds(orientation) else {
+            return;
+        };
+        let (_, length) = self.thumb_extent(orientation, &track);
+        let (track_start, track_length, max_scroll) = match orientation {
+            Orientation::Horizontal => {
+                (track.point.x, track.size.width as f64, self.max_scroll().0)
+            }
+            Orientation::Vertical => (track.point.y, track.size.height as f64, self.max_scroll().1),
+        };
+        let value =
+            scrollbar::offset_for_thumb(thumb_start, length, track_start, track_length, max_scroll);
+        self.set_scroll_for(orientation, value);
+    }
+
+    fn page_size(&self, orientation: Orientation) -> f64 {
+        let viewport = self.viewport();
+        match orientation {
+            Orientation::Horizontal => viewport.size.width as f64,
+            Orientation::Vertical => viewport.size.height as f64,
+        }
+    }
+
+    fn draw_scrollbar(&self, context: &mut BufferContext, orientation: Orientation) {
+        let (Some(track), Some(thumb)) = (
+            self.track_bounds(orientation),
+            self.thumb_bounds(orientation),
+        ) else {
+            return;
+        };
+        let thumb_state = if self
+            .drag
+            .as_ref()
+            .is_some_and(|d| d.orientation == orientation)
+        {
+            ComponentState::Pressed
+        } else if self.hovered == Some((orientation, ScrollbarPart::Thumb)) {
+            ComponentState::Hovered
+        } else {
+            ComponentState::Active
+        };
+        let track_color = context
+            .theme
+            .style_for(&ComponentState::Active)
+            .back_color
+            .lighten(ColorFactor::double())
+            .value;
+        let thumb_color = context.theme.style_for(&thumb_state).back_color.value;
+        context.fill_rect(
+            track.point.x,
+            track.point.y,
+            track.size.width,
+            track.size.height,
+            track_color,
+        );
+        context.fill_rect(
+            thumb.point.x,
+            thumb.point.y,
+            thumb.size.width,
+            thumb.size.height,
+            thumb_color,
+        );
+    }
+}
+
+impl Container for ScrollView {
+    ///
+    /// Set the specified `Component` as the scrolled child, replacing any existing child.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.component = Some(component);
+        self.update_layout();
+    }
+
+    ///
+    /// The `ScrollView` always fills its parent `Container`.
+    ///
+    fn fills_parent_container(&self) -> bool {
+        true
+    }
+
+    ///
+    /// Called when the parent `Container` resizes.
+    ///
+    /// Passes in the parent container `width` and `height`.
+    ///
+    fn resize(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    ///
+    /// Clamp the scroll offset to the child size, and position the child at the viewport
+    /// origin minus the scroll offset.
+    ///
+    fn update_layout(&mut self) {
+        let (max_x, max_y) = self.max_scroll();
+        self.scroll_x = self.scroll_x.clamp(0.0, max_x);
+        self.scroll_y = self.scroll_y.clamp(0.0, max_y);
+        let (x, y) = (
+            self.position.x - self.scroll_x,
+            self.position.y - self.scroll_y,
+        );
+        if let Some(comp) = self.component.as_mut() {
+            comp.set_position(x, y);
+        }
+    }
+}
+
+impl Component for ScrollView {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let (x, y) = (input.position.x, input.position.y);
+        self.cursor = Some(input.position);
+        let part = self.hit_test(x, y);
+
+        if input.pressed {
+            //
+            // A focusable child that is clicked takes the focus back when it sees the press.
+            //
+            if self.viewport().contains(x, y) || part.is_some() {
+                focus::set_focus(self.focus_id);
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+            match part {
+                Some((orientation, ScrollbarPart::Thumb)) => {
+                    if let Some(thumb) = self.thumb_bounds(orientation) {
+                        let grab_offset = match orientation {
+                            Orientation::Horizontal => x - thumb.point.x,
+                            Orientation::Vertical => y - thumb.point.y,
+                        };
+                        self.drag = Some(ThumbDrag {
+                            orientation,
+                            grab_offset,
+                        });
+                    }
+                }
+                Some((orientation, ScrollbarPart::TrackBefore)) => {
+                    let page = self.page_size(orientation);
+                    self.set_scroll_for(orientation, self.scroll_for(orientation) - page);
+                }
+                Some((orientation, ScrollbarPart::TrackAfter)) => {
+                    let page = self.page_size(orientation);
+                    self.set_scroll_for(orientation, self.scroll_for(orientation) + page);
+                }
+                None => {}
+            }
+        } else if let Some(drag) = self.drag.as_ref() {
+            let orientation = drag.orientation;
+            let thumb_start = match orientation {
+                Orientation::Horizontal => x - drag.grab_offset,
+                Orientation::Vertical => y - drag.grab_offset,
+            };
+            self.drag_thumb_to(orientation, thumb_start);
+        }
+        self.hovered = part;
+
+        let dragging = self.drag.is_some();
+        if input.just_released {
+            self.drag = None;
+        }
+        if dragging && !input.just_released {
+            return;
+        }
+
+        //
+        // Presses outside the viewport must not reach parts of the child that are scrolled
+        // out of view, so they are moved out of reach of every child.
+        //
+        let child_input = if input.pressed && !self.viewport().contains(x, y) {
+            input.elsewhere()
+        } else {
+            input
+        };
+        if let Some(comp) = self.component.as_mut() {
+            comp.handle_mouse_event(child_input);
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        let over_viewport = self
+            .cursor
+            .is_some_and(|cursor| self.bounds().contains(cursor.x, cursor.y));
+        if over_viewport {
+            let (mut dx, mut dy) = match delta {
+                MouseScrollDelta::LineDelta(x, y) => (
+                    -*x as f64 * self.line_scroll_amount,
+                    -*y as f64 * self.line_scroll_amount,
+                ),
+                MouseScrollDelta::PixelDelta(p) => (-p.x, -p.y),
+            };
+            if crate::event::modifiers().shift_key() && dx == 0.0 {
+                (dx, dy) = (dy, 0.0);
+            }
+            let before = (self.scroll_x, self.scroll_y);
+            self.scroll_by(dx, dy);
+            if (self.scroll_x, self.scroll_y) != before {
+                return;
+            }
+        }
+        if let Some(comp) = self.component.as_mut() {
+            comp.handle_mouse_wheel_event(delta, phase);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        if focus::is_focused(self.focus_id) && event.state == ElementState::Pressed {
+            let line = self.line_scroll_amount;
+            let page = self.page_size(Orientation::Vertical);
+            match &event.logical_key {
+                Key::Named(NamedKey::ArrowUp) => self.scroll_by(0.0, -line),
+                Key::Named(NamedKey::ArrowDown) => self.scroll_by(0.0, line),
+                Key::Named(NamedKey::ArrowLeft) => self.scroll_by(-line, 0.0),
+                Key::Named(NamedKey::ArrowRight) => self.scroll_by(line, 0.0),
+                Key::Named(NamedKey::PageUp) => self.scroll_by(0.0, -page),
+                Key::Named(NamedKey::PageDown) => self.scroll_by(0.0, page),
+                Key::Named(NamedKey::Home) => self.scroll_to(self.scroll_x, 0.0),
+                Key::Named(NamedKey::End) => self.scroll_to(self.scroll_x, self.max_scroll().1),
+                _ => {}
+            }
+        }
+        if let Some(comp) = self.component.as_mut() {
+            comp.handle_keyboard_event(event);
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        if let Some(comp) = self.component.as_mut() {
+            comp.animate(now);
+        }
+    }
+
+    ///
+    /// Keeps the scroll offset.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.scroll_offset()))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(offset) = state.downcast::<Point>() {
+            self.scroll_to(offset.x, offset.y);
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        if let Some(comp) = self.component.as_deref() {
+            context.with_clip(&self.viewport(), |context| {
+                comp.draw(context);
+            });
+        }
+        self.draw_scrollbar(context, Orientation::Horizontal);
+        self.draw_scrollbar(context, Orientation::Vertical);
+        let (horizontal, vertical) = self.scrollbars_shown();
+        if horizontal && vertical {
+            let viewport = self.viewport();
+            let corner_color = context
+                .theme
+                .style_for(&ComponentState::Disabled)
+                .back_color
+                .value;
+            context.fill_rect(
+                viewport.point.x + viewport.size.width as f64,
+                viewport.point.y + viewport.size.height as f64,
+                self.scrollbar_width,
+                self.scrollbar_width,
+                corner_color,
+            );
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position.x = x;
+        self.position.y = y;
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,443 @@
+//!
+//! Structura: StatusBar ContainerComponent.
+//!
+//! A bar of segments along the bottom of a window, showing the state of the application.
+//! It is meant for the South of a `BorderLayout`, which stretches it to the window width.
+//!
+//! ```ignore
+//! let position = StatusSegment::new("Ln 1, Col 1");
+//! let mode = StatusSegment::new("INSERT").on_click(|| toggle_mode());
+//! let status_bar = StatusBar::new()
+//!     .segment(&StatusSegment::new("Ready"), SegmentWidth::Fill)
+//!     .segment(&position, SegmentWidth::Fixed(160))
+//!     .segment(&StatusSegment::new("UTF-8"), SegmentWidth::Auto)
+//!     .segment(&mode, SegmentWidth::Auto)
+//!     .component(Box::new(progress), SegmentWidth::Fixed(120));
+//! position.set_text("Ln 12, Col 5");
+//! ```
+//!
+//! - A `StatusSegment` shows text, an `Icon`, or both. It is a handle shared with the code
+//!   that updates it, and the bar lays out its segments again when they change.
+//! - A segment with an `on_click` handler is highlighted under the cursor, and clickable.
+//! - Segments can also hold a small `Component`, such as a `ProgressBar`.
+//! - `Fixed` segments have a width in pixels, `Auto` segments fit their content, and `Fill`
+//!   segments share the width that is left.
+//!
+
+use crate::animation;
+use crate::component::icon::Icon;
+use crate::component::{Component, ComponentState, metrics};
+use crate::container::Container;
+use crate::event::MouseInput;
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// Space left and right of the content of each segment.
+const PADDING: f64 = 8.0;
+
+/// Space above and below the content.
+const MARGIN: f64 = 4.0;
+
+/// Space between the icon and the text of a segment.
+const ICON_GAP: f64 = 4.0;
+
+///
+/// How the width of a segment of a `StatusBar` is decided.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SegmentWidth {
+    /// A width in pixels.
+    Fixed(u32),
+
+    /// The width of the content.
+    Auto,
+
+    /// An equal share of the width left by the other segments.
+    Fill,
+}
+
+struct SegmentState {
+    text: RefCell<String>,
+    icon: RefCell<Option<Icon>>,
+    on_click: RefCell<Option<Box<dyn FnMut()>>>,
+}
+
+///
+/// Text and an optional `Icon`, shown in a segment of a `StatusBar`.
+///
+/// Clones share the same segment, so that the code updating the status can keep one.
+///
+#[derive(Clone)]
+pub struct StatusSegment {
+    state: Rc<SegmentState>,
+}
+
+impl StatusSegment {
+    ///
+    /// Constructor.
+    ///
+    pub fn new(text: &str) -> Self {
+        Self {
+            state: Rc::new(SegmentState {
+                text: RefCell::new(text.to_string()),
+                icon: RefCell::new(None),
+                on_click: RefCell::new(None),
+            }),
+        }
+    }
+
+    pub fn icon(self, icon: Icon) -> Self {
+        self.set_icon(Some(icon));
+        self
+    }
+
+    ///
+    /// Add event handler, called when the segment is clicked.
+    ///
+    pub fn on_click<F: FnMut() + 'static>(self, f: F) -> Self {
+        *self.state.on_click.borrow_mut() = Some(Box::new(f));
+        self
+    }
+
+    pub fn text(&self) -> String {
+        self.state.text.borrow().clone()
+    }
+
+    ///
+    /// Replace the text. The `StatusBar` lays out its segments again before the next frame.
+    ///
+    pub fn set_text(&self, text: &str) {
+        if *self.state.text.borrow() != text {
+            *self.state.text.borrow_mut() = text.to_string();
+            animation::request_frame();
+        }
+    }
+
+    pub fn set_icon(&self, icon: Option<Icon>) {
+        *self.state.icon.borrow_mut() = icon;
+        animation::request_frame();
+    }
+
+    fn is_clickable(&self) -> bool {
+        self.state.on_click.borrow().is_some()
+    }
+
+    fn click(&self) {
+        if let Some(handler) = self.state.on_click.borrow_mut().as_mut() {
+            handler();
+        }
+    }
+
+    ///
+    /// Returns the width of the icon and text.
+    ///
+    fn content_width(&self) -> f64 {
+        let text = self.state.text.borrow();
+        let mut width = metrics::text_width(&text) as f64;
+        if let Some(icon) = self.state.icon.borrow().as_ref() {
+            width += icon.width() as f64;
+            if !text.is_empty() {
+                width += ICON_GAP;
+            }
+        }
+        width
+    }
+
+    fn draw(&self, context: &mut BufferContext, bounds: &Rectangle, color: u32) {
+        let mut x = bounds.point.x + PADDING;
+        let center_y = bounds.point.y + bounds.size.height as f64 / 2.0;
+        if let Some(icon) = self.state.icon.borrow().as_ref() {
+            let icon_y = (center_y - icon.height() as f64 / 2.0).round();
+            icon.draw(context, x, icon_y, color);
+            x += icon.width() as f64 + ICON_GAP;
+        }
+        let text_y = center_y - context.line_height() as f64 / 2.0;
+        context.draw_text(&self.state.text.borrow(), x, text_y, color);
+    }
+}
+
+enum SegmentContent {
+    Status(StatusSegment),
+    Component(Box<dyn Component>),
+}
+
+struct Segment {
+    width: SegmentWidth,
+    content: SegmentContent,
+    bounds: Rectangle,
+}
+
+impl Segment {
+    fn content_width(&self) -> f64 {
+        match &self.content {
+            SegmentContent::Status(status) => status.content_width(),
+            SegmentContent::Component(component) => component.get_size().width as f64,
+        }
+    }
+}
+
+///
+/// Horizontal bar of status segments.
+///
+pub struct StatusBar {
+    position: Point,
+    size: Size,
+    segments: Vec<Segment>,
+    hovered: Option<usize>,
+
+    /// The clickable segment the mouse was pressed on.
+    pressed: Option<usize>,
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatusBar {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: (metrics::line_height() as f64 + 2.0 * MARGIN).ceil() as u32,
+            },
+            segments: vec![],
+            hovered: None,
+            pressed: None,
+        }
+    }
+
+    ///
+    /// Add a segment showing `status`.
+    ///
+    pub fn segment(mut self, status: &StatusSegment, width: SegmentWidth) -> Self {
+        self.add(SegmentContent::Status(status.clone()), width);
+        self
+    }
+
+    ///
+    /// Add a segment holding `component`, which is given the width of the segment unless
+    /// `width` is `Auto`.
+    ///
+    pub fn component(mut self, component: Box<dyn Component>, width: SegmentWidth) -> Self {
+        self.add(SegmentContent::Component(component), width);
+        self
+    }
+
+    ///
+    /// Returns the bounds of the segment at `index`, as last laid out.
+    ///
+    pub fn segment_bounds(&self, index: usize) -> Option<Rectangle> {
+        self.segments
+            .get(index)
+            .map(|segment| segment.bounds.clone())
+    }
+
+    fn add(&mut self, content: SegmentContent, width: SegmentWidth) {
+        if let SegmentContent::Component(component) = &content {
+            let height = (component.get_size().height as f64 + 2.0 * MARGIN) as u32;
+            self.size.height = self.size.height.max(height);
+        }
+        self.segments.push(Segment {
+            width,
+            content,
+            bounds: Rectangle::new(0.0, 0.0, 0, 0),
+        });
+        self.update_layout();
+    }
+
+    fn segment_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.segments
+            .iter()
+            .position(|segment| segment.bounds.contains(x, y))
+    }
+
+    fn clickable(&self, index: usize) -> Option<&StatusSegment> {
+        match &self.segments.get(index)?.content {
+            SegmentContent::Status(status) if status.is_clickable() => Some(status),
+            _ => None,
+        }
+    }
+}
+
+impl Container for StatusBar {
+    ///
+    /// Add the specified `Component` in a segment that fits its width.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.add(SegmentContent::Component(component), SegmentWidth::Auto);
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        false
+    }
+
+    ///
+    /// The `StatusBar` stretches to the `width` of its parent, keeping its height.
+    ///
+    fn resize(&mut self, width: usize, _height: usize) {
+        self.size.width = width as u32;
+        self.update_layout();
+    }
+
+    ///
+    /// Give `Fixed` and `Auto` segments their width, then share what is left between the
+    /// `Fill` segments.
+    ///
+    fn update_layout(&mut self) {
+        let widths: Vec<Option<f64>> = self
+            .segments
+            .iter()
+            .map(|segment| match segment.width {
+                SegmentWidth::Fixed(width) => Some(width as f64),
+                SegmentWidth::Auto => Some((segment.content_width() + 2.0 * PADDING).ceil()),
+                SegmentWidth::Fill => None,
+            })
+            .collect();
+        let used: f64 = widths.iter().flatten().sum();
+        let fill_count = widths.iter().filter(|width| width.is_none()).count();
+        let fill = ((self.size.width as f64 - used) / fill_count.max(1) as f64).max(0.0);
+
+        let mut x = self.position.x;
+        let (y, height) = (self.position.y, self.size.height);
+        for (segment, width) in self.segments.iter_mut().zip(widths) {
+            let width = width.unwrap_or(fill.floor());
+            segment.bounds = Rectangle::new(x, y, width as u32, height);
+            if let SegmentContent::Component(component) = &mut segment.content {
+                if segment.width != SegmentWidth::Auto {
+                    let component_height = component.get_size().height as usize;
+                    let inner_width = (width - 2.0 * PADDING).max(0.0);
+                    component.set_size(inner_width as usize, component_height);
+                }
+                let component_y = y + (height as f64 - component.get_size().height as f64) / 2.0;
+                component.set_position(x + PADDING, component_y.round());
+            }
+            x += width;
+        }
+    }
+}
+
+impl Component for StatusBar {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        for segment in self.segments.iter_mut() {
+            if let SegmentContent::Component(component) = &mut segment.content {
+                component.handle_mouse_event(input);
+            }
+        }
+
+        let index = self.segment_at(input.position.x, input.position.y);
+        self.hovered = index.filter(|&index| self.clickable(index).is_some());
+        if input.pressed {
+            self.pressed = self.hovered;
+        }
+        if input.just_released
+            && let Some(pressed) = self.pressed.take()
+            && self.hovered == Some(pressed)
+            && let Some(status) = self.clickable(pressed)
+        {
+            status.click();
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        for segment in self.segments.iter_mut() {
+            if let SegmentContent::Component(component) = &mut segment.content {
+                component.handle_mouse_wheel_event(delta, phase);
+            }
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        for segment in self.segments.iter_mut() {
+            if let SegmentContent::Component(component) = &mut segment.content {
+                component.handle_keyboard_event(event);
+            }
+        }
+    }
+
+    ///
+    /// Segments may change width with their text, so the layout is updated after each frame.
+    ///
+    fn animate(&mut self, now: Instant) {
+        for segment in self.segments.iter_mut() {
+            if let SegmentContent::Component(component) = &mut segment.content {
+                component.animate(now);
+            }
+        }
+        self.update_layout();
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width, self.size.height);
+        context.fill_rect(x, y, width, height, style.back_color.value);
+        let border_color = style.border_color.value;
+        context.draw_line(x, y, x + width as f64, y, border_color);
+
+        let hovered = context.theme.style_for(&ComponentState::Hovered);
+        let pressed = context.theme.style_for(&ComponentState::Pressed);
+        for (index, segment) in self.segments.iter().enumerate() {
+            let bounds = &segment.bounds;
+            let mut color = style.fore_color.value;
+            if self.hovered == Some(index) {
+                let back_style = if self.pressed == Some(index) {
+                    &pressed
+                } else {
+                    &hovered
+                };
+                //
+                // Below the top border line.
+                //
+                let (segment_x, segment_y) = (bounds.point.x, bounds.point.y + 1.0);
+                let segment_width = bounds.size.width;
+                let segment_height = bounds.size.height.saturating_sub(1);
+                let back = back_style.back_color.value;
+                context.fill_rect(segment_x, segment_y, segment_width, segment_height, back);
+                color = back_style.fore_color.value;
+            }
+            context.with_clip(bounds, |context| match &segment.content {
+                SegmentContent::Status(status) => status.draw(context, bounds, color),
+                SegmentContent::Component(component) => component.draw(context),
+            });
+            if index + 1 < self.segments.len() {
+                let right = bounds.point.x + bounds.size.width as f64 - 1.0;
+                let (top, bottom) = (y + MARGIN, y + height as f64 - MARGIN);
+                context.draw_line(right, top, right, bottom, border_color);
+            }
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
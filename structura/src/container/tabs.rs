@@ -0,0 +1,725 @@
+//!
+//! # **Structura**: Tabs
+//!
+//! Implements `Container` and `Component`.
+//!
+//! Shows one child `Component` at a time, below a strip of tab titles:
+//!
+//! ```text
+//! |-----------------------------|
+//! | Tab 1 | Tab 2 | Tab 3 |     |
+//! |-----------------------------|
+//! |                             |
+//! |  Selected child Component   |
+//! |                             |
+//! |-----------------------------|
+//! ```
+//!
+//! - Click a tab, or use `Ctrl+Tab` / `Ctrl+Shift+Tab`, to change the selected tab. The keys
+//!   work once the `Tabs` has been clicked, unless the click gave focus to a child.
+//! - Optional close buttons on each tab. See `Tabs::closable()`.
+//! - Optional drag-to-reorder of tabs. See `Tabs::reorderable()`.
+//! - When the tabs do not fit in the strip, scroll buttons are shown and the mouse wheel
+//!   scrolls the strip.
+//!
+
+use crate::component::{Component, ComponentState};
+use crate::container::Container;
+use crate::event::MouseInput;
+use crate::event::focus::{self, FocusId};
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::any::Any;
+use std::time::Instant;
+use winit::event::{ElementState, MouseScrollDelta};
+use winit::keyboard::{Key, NamedKey};
+
+/// Width of each of the two scroll buttons shown when the tabs overflow the strip.
+const SCROLL_BUTTON_WIDTH: u32 = 30;
+
+/// Size of the square close button drawn on each tab when `closable` is set.
+const CLOSE_BUTTON_SIZE: u32 = 20;
+
+/// Distance in pixels the mouse must move before a pressed tab starts dragging.
+const DRAG_THRESHOLD: f64 = 8.0;
+
+/// Text color for tab titles.
+const TITLE_COLOR: u32 = 0xFFFFFFFF;
+
+///
+/// A single tab: a title in the tab strip, and the child `Component` shown when selected.
+///
+struct Tab {
+    title: String,
+    component: Box<dyn Component>,
+}
+
+///
+/// The part of the tab strip under the mouse.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TabPart {
+    Title(usize),
+    Close(usize),
+    ScrollLeft,
+    ScrollRight,
+}
+
+///
+/// A tab that has been pressed, and may be dragged to a new position.
+///
+struct TabDrag {
+    index: usize,
+    start_x: f64,
+    dragging: bool,
+}
+
+///
+/// Tabs: Implements `Container` and `Component`.
+///
+pub struct Tabs {
+    position: Point,
+    size: Size,
+    tabs: Vec<Tab>,
+    selected: usize,
+    strip_height: u32,
+    tab_width: u32,
+    closable: bool,
+    reorderable: bool,
+    strip_scroll: f64,
+    cursor: Option<Point>,
+    hovered: Option<TabPart>,
+    pressed: Option<TabPart>,
+    drag: Option<TabDrag>,
+    focus_id: FocusId,
+    on_selection_change: Option<Box<dyn FnMut(usize)>>,
+    on_close_request: Option<Box<dyn FnMut(usize) -> bool>>,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tabs {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            tabs: vec![],
+            selected: 0,
+            strip_height: 50,
+            tab_width: 200,
+            closable: false,
+            reorderable: false,
+            strip_scroll: 0.0,
+            cursor: None,
+            hovered: None,
+            pressed: None,
+            drag: None,
+            focus_id: FocusId::new(),
+            on_selection_change: None,
+            on_close_request: None,
+        }
+    }
+
+    ///
+    /// Show a close button on each tab.
+    ///
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    ///
+    /// Allow tabs to be reordered by dragging them along the tab strip.
+    ///
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    ///
+    /// Set the width of each tab in the tab strip.
+    ///
+    pub fn set_tab_width(mut self, tab_width: u32) -> Self {
+        self.tab_width = tab_width.max(CLOSE_BUTTON_SIZE * 2);
+        self
+    }
+
+    ///
+    /// Set the height of the tab strip.
+    ///
+    pub fn set_strip_height(mut self, strip_height: u32) -> Self {
+        self.strip_height = strip_height;
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Add event handler, called with the index of the newly selected tab.
+    ///
+    pub fn on_selection_change<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_selection_change = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Add event handler, called with the index of a tab when its close button is clicked.
+    ///
+    /// Return `true` to close the tab, or `false` to keep it open. Without a handler,
+    /// tabs are closed immediately.
+    ///
+    pub fn on_close_request<F: FnMut(usize) -> bool + 'static>(mut self, f: F) -> Self {
+        self.on_close_request = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Add a tab with the specified `title`, showing `component` when selected.
+    ///
+    pub fn add_tab(&mut self, title: String, component: Box<dyn Component>) {
+        self.tabs.push(Tab { title, component });
+        self.update_layout();
+    }
+
+    ///
+    /// Remove the tab at `index`, returning its `Component`.
+    ///
+    pub fn remove_tab(&mut self, index: usize) -> Option<Box<dyn Component>> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+        let tab = self.tabs.remove(index);
+        if index < self.selected {
+            self.selected -= 1;
+        } else if index == self.selected {
+            self.selected = self.selected.min(self.tabs.len().saturating_sub(1));
+            if !self.tabs.is_empty() {
+                self.handle_event_on_selection_change();
+            }
+        }
+        self.strip_scroll = self.strip_scroll.clamp(0.0, self.max_strip_scroll());
+        Some(tab.component)
+    }
+
+    ///
+    /// Set the title of the tab at `index`.
+    ///
+    pub fn set_title(&mut self, index: usize, title: String) {
+        if let Some(tab) = self.tabs.get_mut(index) {
+            tab.title = title;
+        }
+    }
+
+    ///
+    /// Returns the number of tabs.
+    ///
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len()
+    }
+
+    ///
+    /// Returns the index of the selected tab.
+    ///
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn focus_id(&self) -> FocusId {
+        self.focus_id
+    }
+
+    ///
+    /// Select the tab at `index`, scrolling the tab strip to show it.
+    ///
+    pub fn select(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        self.ensure_visible(index);
+        if index != self.selected {
+            self.selected = index;
+            self.handle_event_on_selection_change();
+        }
+    }
+
+    ///
+    /// Select the next tab, wrapping to the first.
+    ///
+    pub fn select_next(&mut self) {
+        if !self.tabs.is_empty() {
+            self.select((self.selected + 1) % self.tabs.len());
+        }
+    }
+
+    ///
+    /// Select the previous tab, wrapping to the last.
+    ///
+    pub fn select_previous(&mut self) {
+        if !self.tabs.is_empty() {
+            self.select((self.selected + self.tabs.len() - 1) % self.tabs.len());
+        }
+    }
+
+    fn handle_event_on_selection_change(&mut self) {
+        if let Some(handler) = self.on_selection_change.as_mut() {
+            handler(self.selected);
+        }
+    }
+
+    ///
+    /// Ask the `on_close_request` handler whether the tab at `index` may close, and close it.
+    ///
+    fn request_close(&mut self, index: usize) {
+        let close = match self.on_close_request.as_mut() {
+            Some(handler) => handler(index),
+            None => true,
+        };
+        if close {
+            self.remove_tab(index);
+        }
+    }
+
+    ///
+    /// Move the tab at `from` to `to`, keeping the same tab selected.
+    ///
+    fn move_tab(&mut self, from: usize, to: usize) {
+        let tab = self.tabs.remove(from);
+        self.tabs.insert(to, tab);
+        if self.selected == from {
+            self.selected = to;
+        } else if from < self.selected && to >= self.selected {
+            self.selected -= 1;
+        } else if from > self.selected && to <= self.selected {
+            self.selected += 1;
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.size.height,
+        )
+    }
+
+    fn content_bounds(&self) -> Rectangle {
+        let strip_height = self.strip_height.min(self.size.height);
+        Rectangle::new(
+            self.position.x,
+            self.position.y + strip_height as f64,
+            self.size.width,
+            self.size.height - strip_height,
+        )
+    }
+
+    fn strip_overflows(&self) -> bool {
+        self.tabs.len() as u64 * self.tab_width as u64 > self.size.width as u64
+    }
+
+    ///
+    /// The area of the tab strip where tabs are drawn, excluding any scroll buttons.
+    ///
+    fn tab_area(&self) -> Rectangle {
+        let width = if self.strip_overflows() {
+            self.size.width.saturating_sub(SCROLL_BUTTON_WIDTH * 2)
+        } else {
+            self.size.width
+        };
+        Rectangle::new(self.position.x, self.position.y, width, self.strip_height)
+    }
+
+    fn max_strip_scroll(&self) -> f64 {
+        let total = self.tabs.len() as f64 * self.tab_width as f64;
+        (total - self.tab_area().size.width as f64).max(0.0)
+    }
+
+    fn scroll_strip(&mut self, delta: f64) {
+        self.strip_scroll = (self.strip_scroll + delta).clamp(0.0, self.max_strip_scroll());
+    }
+
+    ///
+    /// Scroll the tab strip so that the tab at `index` is fully visible.
+    ///
+    fn ensure_visible(&mut self, index: usize) {
+        let left = index as f64 * self.tab_width as f64;
+        let right = left + self.tab_width as f64;
+        let visible = self.tab_area().size.width as f64;
+        if left < self.strip_scroll {
+            self.strip_scroll = left;
+        } else if right > self.strip_scroll + visible {
+            self.strip_scroll = right - visible;
+        }
+        self.strip_scroll = self.strip_scroll.clamp(0.0, self.max_strip_scroll());
+    }
+
+    fn tab_bounds(&self, index: usize) -> Rectangle {
+        Rectangle::new(
+            self.position.x + index as f64 * self.tab_width as f64 - self.strip_scroll,
+            self.position.y,
+            self.tab_width,
+            self.strip_height,
+        )
+    }
+
+    fn close_bounds(&self, index: usize) -> Rectangle {
+        let tab = self.tab_bounds(index);
+        let margin = (self.strip_height.saturating_sub(CLOSE_BUTTON_SIZE)) as f64 / 2.0;
+        Rectangle::new(
+            tab.point.x + tab.size.width as f64 - CLOSE_BUTTON_SIZE as f64 - margin,
+            tab.point.y + margin,
+            CLOSE_BUTTON_SIZE,
+            CLOSE_BUTTON_SIZE,
+        )
+    }
+
+    fn scroll_button_bounds(&self, part: TabPart) -> Rectangle {
+        let area = self.tab_area();
+        let x = area.point.x + area.size.width as f64;
+        let x = match part {
+            TabPart::ScrollRight => x + SCROLL_BUTTON_WIDTH as f64,
+            _ => x,
+        };
+        Rectangle::new(x, self.position.y, SCROLL_BUTTON_WIDTH, self.strip_height)
+    }
+
+    ///
+    /// Find the part of the tab strip at the specified coordinates.
+    ///
+    fn hit_test(&self, x: f64, y: f64) -> Option<TabPart> {
+        if self.strip_overflows() {
+            for part in [TabPart::ScrollLeft, TabPart::ScrollRight] {
+                if self.scroll_button_bounds(part).contains(x, y) {
+                    return Some(part);
+                }
+            }
+        }
+        if !self.tab_area().contains(x, y) {
+            return None;
+        }
+        let index = self.tab_index_at(x);
+        if index >= self.tabs.len() || !self.tab_bounds(index).contains(x, y) {
+            return None;
+        }
+        if self.closable && self.close_bounds(index).contains(x, y) {
+            return Some(TabPart::Close(index));
+        }
+        Some(TabPart::Title(index))
+    }
+
+    ///
+    /// Returns the index of the tab under the `x` coordinate, clamped to the existing tabs.
+    ///
+    fn tab_index_at(&self, x: f64) -> usize {
+        let offset = x - self.position.x + self.strip_scroll;
+        let index = (offset / self.tab_width as f64).floor().max(0.0) as usize;
+        index.min(self.tabs.len().saturating_sub(1))
+    }
+
+    fn draw_tab(&self, context: &mut BufferContext, index: usize) {
+        let bounds = self.tab_bounds(index);
+        let state = if index == self.selected {
+            ComponentState::Focused
+        } else if self.hovered == Some(TabPart::Title(index)) {
+            ComponentState::Hovered
+        } else {
+            ComponentState::Active
+        };
+        let style = context.theme.style_for(&state);
+        let (x, y, w, h) = (
+            bounds.point.x,
+            bounds.point.y,
+            bounds.size.width,
+            bounds.size.height,
+        );
+        context.fill_rect(x, y, w, h, style.back_color.value);
+        context.draw_border(x, y, w, h, style.border_width, style.border_color.value);
+
+        let title_width = if self.closable {
+            w.saturating_sub(CLOSE_BUTTON_SIZE * 2)
+        } else {
+            w
+        };
+        let title_bounds = Rectangle::new(x + 10.0, y, title_width.saturating_sub(20), h);
+        let text_y = y + (h as f64 - context.line_height() as f64) / 2.0;
+        context.with_clip(&title_bounds, |context| {
+            context.draw_text(&self.tabs[index].title, x + 10.0, text_y, TITLE_COLOR);
+        });
+
+        if self.closable {
+            let close = self.close_bounds(index);
+            if self.hovered == Some(TabPart::Close(index)) {
+                let hovered = context.theme.style_for(&ComponentState::Hovered);
+                context.fill_rect(
+                    close.point.x,
+                    close.point.y,
+                    close.size.width,
+                    close.size.height,
+                    hovered.back_color.value,
+                );
+            }
+            let (cx0, cy0) = (close.point.x + 5.0, close.point.y + 5.0);
+            let (cx1, cy1) = (
+                close.point.x + close.size.width as f64 - 5.0,
+                close.point.y + close.size.height as f64 - 5.0,
+            );
+            context.draw_line(cx0, cy0, cx1, cy1, TITLE_COLOR);
+            context.draw_line(cx0, cy1, cx1, cy0, TITLE_COLOR);
+        }
+    }
+
+    fn draw_scroll_buttons(&self, context: &mut BufferContext) {
+        for part in [TabPart::ScrollLeft, TabPart::ScrollRight] {
+            let bounds = self.scroll_button_bounds(part);
+            let state = if self.hovered == Some(part) {
+                ComponentState::Hovered
+            } else {
+                ComponentState::Active
+            };
+            let style = context.theme.style_for(&state);
+            let (x, y, w, h) = (
+                bounds.point.x,
+                bounds.point.y,
+                bounds.size.width,
+                bounds.size.height,
+            );
+            context.fill_rect(x, y, w, h, style.back_color.value);
+            context.draw_border(x, y, w, h, style.border_width, style.border_color.value);
+            let cx = x + w as f64 / 2.0;
+            let cy = y + h as f64 / 2.0;
+            let dx = if part == TabPart::ScrollLeft { 4.0 } else { -4.0 };
+            context.draw_line(cx - dx, cy - 8.0, cx + dx, cy, TITLE_COLOR);
+            context.draw_line(cx + dx, cy, cx - dx, cy + 8.0, TITLE_COLOR);
+        }
+    }
+}
+
+impl Container for Tabs {
+    ///
+    /// Add the specified `Component` as a new tab, with a default title.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        let title = format!("Tab {}", self.tabs.len() + 1);
+        self.add_tab(title, component);
+    }
+
+    ///
+    /// The `Tabs` always fills its parent `Container`.
+    ///
+    fn fills_parent_container(&self) -> bool {
+        true
+    }
+
+    ///
+    /// Called when the parent `Container` resizes.
+    ///
+    /// Passes in the parent container `width` and `height`.
+    ///
+    fn resize(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    ///
+    /// Position each child in the content area below the tab strip.
+    ///
+    fn update_layout(&mut self) {
+        let content = self.content_bounds();
+        for tab in self.tabs.iter_mut() {
+            tab.component.set_position(content.point.x, content.point.y);
+            tab.component
+                .set_size(content.size.width as usize, content.size.height as usize);
+        }
+        self.strip_scroll = self.strip_scroll.clamp(0.0, self.max_strip_scroll());
+    }
+}
+
+impl Component for Tabs {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let (x, y) = (input.position.x, input.position.y);
+        self.cursor = Some(input.position);
+        let part = self.hit_test(x, y);
+
+        if input.pressed {
+            //
+            // A focusable child that is clicked takes the focus back when it sees the press.
+            //
+            if self.bounds().contains(x, y) {
+                focus::set_focus(self.focus_id);
+            } else {
+                focus::clear_focus(self.focus_id);
+            }
+            self.pressed = part;
+            match part {
+                Some(TabPart::Title(index)) => {
+                    self.select(index);
+                    if self.reorderable {
+                        self.drag = Some(TabDrag {
+                            index,
+                            start_x: x,
+                            dragging: false,
+                        });
+                    }
+                }
+                Some(TabPart::ScrollLeft) => self.scroll_strip(-(self.tab_width as f64)),
+                Some(TabPart::ScrollRight) => self.scroll_strip(self.tab_width as f64),
+                _ => {}
+            }
+        } else if let Some(drag) = self.drag.as_mut() {
+            if !drag.dragging && (x - drag.start_x).abs() > DRAG_THRESHOLD {
+                drag.dragging = true;
+            }
+            if drag.dragging {
+                let from = drag.index;
+                let to = self.tab_index_at(x);
+                if from != to {
+                    self.move_tab(from, to);
+                    if let Some(drag) = self.drag.as_mut() {
+                        drag.index = to;
+                    }
+                    self.ensure_visible(to);
+                }
+            }
+        }
+        self.hovered = part;
+
+        let dragging = self.drag.as_ref().is_some_and(|drag| drag.dragging);
+        if input.just_released {
+            if let Some(TabPart::Close(index)) = self.pressed
+                && part == Some(TabPart::Close(index))
+            {
+                self.request_close(index);
+                self.hovered = self.hit_test(x, y);
+            }
+            self.pressed = None;
+            self.drag = None;
+        }
+
+        if !dragging && let Some(tab) = self.tabs.get_mut(self.selected) {
+            tab.component.handle_mouse_event(input);
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        let over_strip = self
+            .cursor
+            .is_some_and(|cursor| self.tab_area().contains(cursor.x, cursor.y));
+        if over_strip && self.strip_overflows() {
+            let amount = match delta {
+                MouseScrollDelta::LineDelta(x, y) => {
+                    (if *x != 0.0 { *x } else { -*y }) as f64 * self.tab_width as f64 / 2.0
+                }
+                MouseScrollDelta::PixelDelta(p) => {
+                    if p.x != 0.0 { p.x } else { -p.y }
+                }
+            };
+            self.scroll_strip(amount);
+            return;
+        }
+        if let Some(tab) = self.tabs.get_mut(self.selected) {
+            tab.component.handle_mouse_wheel_event(delta, phase);
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        let modifiers = crate::event::modifiers();
+        if focus::is_focused(self.focus_id)
+            && modifiers.control_key()
+            && event.logical_key == Key::Named(NamedKey::Tab)
+        {
+            if event.state == ElementState::Pressed {
+                if modifiers.shift_key() {
+                    self.select_previous();
+                } else {
+                    self.select_next();
+                }
+            }
+            return;
+        }
+        if let Some(tab) = self.tabs.get_mut(self.selected) {
+            tab.component.handle_keyboard_event(event);
+        }
+    }
+
+    fn animate(&mut self, now: Instant) {
+        for tab in self.tabs.iter_mut() {
+            tab.component.animate(now);
+        }
+    }
+
+    ///
+    /// Keeps the selected tab.
+    ///
+    fn save_state(&self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.selected))
+    }
+
+    fn restore_state(&mut self, state: Box<dyn Any>) {
+        if let Ok(selected) = state.downcast::<usize>()
+            && *selected < self.tabs.len()
+        {
+            self.selected = *selected;
+            self.ensure_visible(self.selected);
+            self.update_layout();
+        }
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        if let Some(tab) = self.tabs.get(self.selected) {
+            context.with_clip(&self.content_bounds(), |context| {
+                tab.component.draw(context);
+            });
+        }
+
+        let strip_style = context.theme.style_for(&ComponentState::Disabled);
+        context.fill_rect(
+            self.position.x,
+            self.position.y,
+            self.size.width,
+            self.strip_height.min(self.size.height),
+            strip_style.back_color.value,
+        );
+        context.with_clip(&self.tab_area(), |context| {
+            for index in 0..self.tabs.len() {
+                self.draw_tab(context, index);
+            }
+        });
+        if self.strip_overflows() {
+            self.draw_scroll_buttons(context);
+        }
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position.x = x;
+        self.position.y = y;
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size.width = width as u32;
+        self.size.height = height as u32;
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
@@ -0,0 +1,472 @@
+//!
+//! Structura: Toolbar ContainerComponent.
+//!
+//! A row of buttons for the most used `Command`s, usually in the North of a `BorderLayout`.
+//!
+//! ```ignore
+//! let toolbar = Toolbar::new()
+//!     .button(Icon::folder_open(), "&Open", &open)
+//!     .button(Icon::file(), "&Save", &save)
+//!     .separator()
+//!     .text_button("&Word Wrap", &word_wrap);
+//! ```
+//!
+//! - Each button triggers its `Command`, and shows a tooltip with the text of the button and
+//!   the shortcut of the `Command`. Buttons of checkable `Command`s are toggle buttons.
+//! - Buttons show their icon only, unless `show_text(true)` is set.
+//! - Other `Component`s, such as a `ComboBox`, can be pushed as items too.
+//! - When the items do not fit the width of the toolbar, the last ones are hidden, and a
+//!   chevron at the right opens a `Menu` of the hidden buttons.
+//!
+
+use crate::command::Command;
+use crate::component::button::{Button, ButtonContent};
+use crate::component::icon::Icon;
+use crate::component::label::parse_mnemonic;
+use crate::component::menu::{Menu, MenuSession};
+use crate::component::tooltip::WithTooltip;
+use crate::component::{Component, ComponentState};
+use crate::container::Container;
+use crate::event::MouseInput;
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// Space between items.
+const SPACING: f64 = 2.0;
+
+/// Width of a separator between items.
+const SEPARATOR_WIDTH: f64 = 9.0;
+
+/// Width of the overflow chevron.
+const CHEVRON_WIDTH: u32 = 24;
+
+/// Space above and below the items, and above and below each separator line.
+const MARGIN: f64 = 4.0;
+
+enum ToolItem {
+    Button {
+        button: Box<WithTooltip<Button>>,
+
+        /// The text of the button, with its mnemonic marker, for the overflow menu.
+        text: String,
+        icon: Option<Icon>,
+        command: Command,
+    },
+    Separator,
+    Component(Box<dyn Component>),
+}
+
+impl ToolItem {
+    fn component(&self) -> Option<&dyn Component> {
+        match self {
+            ToolItem::Button { button, .. } => Some(button.as_ref()),
+            ToolItem::Component(component) => Some(component.as_ref()),
+            ToolItem::Separator => None,
+        }
+    }
+
+    fn component_mut(&mut self) -> Option<&mut dyn Component> {
+        match self {
+            ToolItem::Button { button, .. } => Some(button.as_mut()),
+            ToolItem::Component(component) => Some(component.as_mut()),
+            ToolItem::Separator => None,
+        }
+    }
+}
+
+///
+/// Horizontal bar of command buttons, separators and other components.
+///
+pub struct Toolbar {
+    position: Point,
+    size: Size,
+    items: Vec<ToolItem>,
+    show_text: bool,
+
+    /// The index of each separator, and its x position relative to the toolbar.
+    separators: Vec<(usize, f64)>,
+
+    /// Items from this index on do not fit, and are shown in the overflow menu.
+    overflow_from: usize,
+    chevron_hovered: bool,
+
+    /// The overflow menu, opened from the chevron.
+    session: Rc<MenuSession>,
+}
+
+impl Default for Toolbar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toolbar {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+            items: vec![],
+            show_text: false,
+            separators: vec![],
+            overflow_from: 0,
+            chevron_hovered: false,
+            session: Rc::new(MenuSession::default()),
+        }
+    }
+
+    ///
+    /// Add a button that shows `icon` and triggers `command`. An `&` in `text` marks the
+    /// mnemonic of the button in the overflow menu.
+    ///
+    pub fn button(self, icon: Icon, text: &str, command: &Command) -> Self {
+        self.add_button(Some(icon), text, command)
+    }
+
+    ///
+    /// Add a button that shows `text` and triggers `command`.
+    ///
+    pub fn text_button(self, text: &str, command: &Command) -> Self {
+        self.add_button(None, text, command)
+    }
+
+    ///
+    /// Add a line between groups of items.
+    ///
+    pub fn separator(mut self) -> Self {
+        self.items.push(ToolItem::Separator);
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Show the text of buttons next to their icons.
+    ///
+    pub fn show_text(mut self, show_text: bool) -> Self {
+        self.show_text = show_text;
+        for item in self.items.iter_mut() {
+            if let ToolItem::Button {
+                button, text, icon, ..
+            } = item
+            {
+                let content = Self::content(icon.clone(), text, show_text);
+                button.inner_mut().set_content(content);
+            }
+        }
+        self.update_layout();
+        self
+    }
+
+    ///
+    /// Returns the number of items that are hidden in the overflow menu.
+    ///
+    pub fn overflow_count(&self) -> usize {
+        self.items.len() - self.overflow_from
+    }
+
+    fn add_button(mut self, icon: Option<Icon>, text: &str, command: &Command) -> Self {
+        let content = Self::content(icon.clone(), text, self.show_text);
+        let button = Button::default().content(content).command(command);
+        let (plain_text, _) = parse_mnemonic(text);
+        let tooltip = match command.get_shortcut() {
+            Some(shortcut) => format!("{plain_text} ({shortcut})"),
+            None => plain_text,
+        };
+        self.items.push(ToolItem::Button {
+            button: Box::new(WithTooltip::new(button, &tooltip)),
+            text: text.to_string(),
+            icon,
+            command: command.clone(),
+        });
+        self.update_layout();
+        self
+    }
+
+    fn content(icon: Option<Icon>, text: &str, show_text: bool) -> ButtonContent {
+        let (text, _) = parse_mnemonic(text);
+        match icon {
+            Some(icon) if show_text => ButtonContent::IconText {
+                icon,
+                text,
+                placement: Default::default(),
+            },
+            Some(icon) => ButtonContent::Icon(icon),
+            None => ButtonContent::Text(text),
+        }
+    }
+
+    ///
+    /// Returns the width of an item along the toolbar.
+    ///
+    fn item_width(item: &ToolItem) -> f64 {
+        match item {
+            ToolItem::Button { button, .. } => button.inner().preferred_size().width as f64,
+            ToolItem::Separator => SEPARATOR_WIDTH,
+            ToolItem::Component(component) => component.get_size().width as f64,
+        }
+    }
+
+    fn chevron_bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x + self.size.width.saturating_sub(CHEVRON_WIDTH) as f64,
+            self.position.y,
+            CHEVRON_WIDTH,
+            self.size.height,
+        )
+    }
+
+    ///
+    /// Returns the overflow menu, of the hidden buttons.
+    ///
+    fn overflow_menu(&self) -> Menu {
+        let mut menu = Menu::new();
+        let mut separator = false;
+        for item in self.items[self.overflow_from..].iter() {
+            match item {
+                ToolItem::Button { text, command, .. } => {
+                    if separator && !menu.is_empty() {
+                        menu = menu.separator();
+                    }
+                    separator = false;
+                    menu = menu.item(text, command);
+                }
+                ToolItem::Separator => separator = true,
+                ToolItem::Component(_) => {}
+            }
+        }
+        menu
+    }
+
+    fn open_overflow_menu(&self) {
+        let menu = self.overflow_menu();
+        if menu.is_empty() {
+            return;
+        }
+        *self.session.bar.borrow_mut() = vec![(menu, self.chevron_bounds())];
+        self.session.open_bar(0, false);
+    }
+
+    fn draw_chevron(&self, context: &mut BufferContext) {
+        let bounds = self.chevron_bounds();
+        let style = context.theme.style_for(&ComponentState::Active);
+        let mut color = style.fore_color.value;
+        if self.chevron_hovered || self.session.is_open() {
+            let hovered = context.theme.style_for(&ComponentState::Hovered);
+            let (width, height) = (bounds.size.width, bounds.size.height);
+            let back = hovered.back_color.value;
+            context.fill_rect(bounds.point.x, bounds.point.y, width, height, back);
+            color = hovered.fore_color.value;
+        }
+        let x = bounds.point.x + (bounds.size.width / 2) as f64 - 4.0;
+        let y = bounds.point.y + (bounds.size.height / 2) as f64;
+        for offset in [0.0, 5.0] {
+            let x = x + offset;
+            context.draw_line(x, y - 4.0, x + 4.0, y, color);
+            context.draw_line(x + 4.0, y, x, y + 4.0, color);
+        }
+    }
+}
+
+impl Drop for Toolbar {
+    fn drop(&mut self) {
+        self.session.close_all();
+    }
+}
+
+impl Container for Toolbar {
+    ///
+    /// Add the specified `Component` as an item of the `Toolbar`.
+    ///
+    fn push(&mut self, component: Box<dyn Component>) {
+        self.items.push(ToolItem::Component(component));
+        self.update_layout();
+    }
+
+    fn fills_parent_container(&self) -> bool {
+        false
+    }
+
+    ///
+    /// The `Toolbar` stretches to the `width` of its parent, keeping its height.
+    ///
+    fn resize(&mut self, width: usize, _height: usize) {
+        self.size.width = width as u32;
+        self.update_layout();
+    }
+
+    ///
+    /// Place the items from the left, hiding those that do not fit.
+    ///
+    fn update_layout(&mut self) {
+        let height = self
+            .items
+            .iter()
+            .map(|item| match item {
+                ToolItem::Button { button, .. } => button.inner().preferred_size().height,
+                ToolItem::Separator => 0,
+                ToolItem::Component(component) => component.get_size().height,
+            })
+            .max()
+            .unwrap_or(0);
+        self.size.height = self.size.height.max(height + (2.0 * MARGIN) as u32);
+
+        let widths: Vec<f64> = self.items.iter().map(Self::item_width).collect();
+        let total = widths.iter().sum::<f64>() + SPACING * widths.len() as f64;
+        let mut available = self.size.width as f64;
+        if total > available {
+            available -= CHEVRON_WIDTH as f64;
+        }
+        let mut x = SPACING;
+        self.overflow_from = self.items.len();
+        for (index, width) in widths.iter().enumerate() {
+            if total > self.size.width as f64 && x + width > available {
+                self.overflow_from = index;
+                break;
+            }
+            x += width + SPACING;
+        }
+        while self.overflow_from > 0
+            && matches!(self.items[self.overflow_from - 1], ToolItem::Separator)
+        {
+            self.overflow_from -= 1;
+        }
+
+        let (left, top) = (self.position.x, self.position.y);
+        let inner_height = self.size.height as f64 - 2.0 * MARGIN;
+        let mut x = left + SPACING;
+        self.separators.clear();
+        for (index, (item, width)) in self.items.iter_mut().zip(widths).enumerate() {
+            match item {
+                ToolItem::Button { button, .. } => {
+                    button.set_size(width as usize, inner_height.max(0.0) as usize);
+                    button.set_position(x, top + MARGIN);
+                }
+                ToolItem::Separator => {
+                    let separator = x - left + SEPARATOR_WIDTH / 2.0;
+                    self.separators.push((index, separator.round()));
+                }
+                ToolItem::Component(component) => {
+                    let y =
+                        top + (self.size.height as f64 - component.get_size().height as f64) / 2.0;
+                    component.set_position(x, y.round());
+                }
+            }
+            x += width + SPACING;
+        }
+    }
+}
+
+impl Component for Toolbar {
+    fn handle_mouse_event(&mut self, input: MouseInput) {
+        let overflow_from = self.overflow_from;
+        for (index, item) in self.items.iter_mut().enumerate() {
+            if let Some(component) = item.component_mut() {
+                if index < overflow_from {
+                    component.handle_mouse_event(input);
+                } else {
+                    component.handle_mouse_event(input.elsewhere());
+                }
+            }
+        }
+
+        let dismissed = self.session.dismissed.take();
+        let on_chevron = self.overflow_count() > 0
+            && self
+                .chevron_bounds()
+                .contains(input.position.x, input.position.y);
+        self.chevron_hovered = on_chevron;
+        if input.pressed && on_chevron && dismissed.is_none() && !self.session.is_open() {
+            self.open_overflow_menu();
+        }
+    }
+
+    fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+    ) {
+        for item in self.items[..self.overflow_from].iter_mut() {
+            if let Some(component) = item.component_mut() {
+                component.handle_mouse_wheel_event(delta, phase);
+            }
+        }
+    }
+
+    fn handle_keyboard_event(&mut self, event: &winit::event::KeyEvent) {
+        for item in self.items[..self.overflow_from].iter_mut() {
+            if let Some(component) = item.component_mut() {
+                component.handle_keyboard_event(event);
+            }
+        }
+    }
+
+    ///
+    /// Items may change size while animating, so the layout is updated after each frame.
+    ///
+    fn animate(&mut self, now: Instant) {
+        for item in self.items.iter_mut() {
+            if let Some(component) = item.component_mut() {
+                component.animate(now);
+            }
+        }
+        self.update_layout();
+    }
+
+    fn draw(&self, context: &mut BufferContext) {
+        let style = context.theme.style_for(&ComponentState::Active);
+        let (x, y) = (self.position.x, self.position.y);
+        let (width, height) = (self.size.width, self.size.height);
+        context.fill_rect(x, y, width, height, style.back_color.value);
+        let bottom = y + height as f64 - 1.0;
+        let border_color = style.border_color.value;
+        context.draw_line(x, bottom, x + width as f64, bottom, border_color);
+
+        let bounds = Rectangle::new(x, y, width, height);
+        context.with_clip(&bounds, |context| {
+            for item in self.items[..self.overflow_from].iter() {
+                if let Some(component) = item.component() {
+                    component.draw(context);
+                }
+            }
+            let separators = self
+                .separators
+                .iter()
+                .take_while(|(index, _)| *index < self.overflow_from);
+            for (_, separator) in separators {
+                let (top, bottom) = (y + 2.0 * MARGIN, bottom - 2.0 * MARGIN);
+                context.draw_line(x + separator, top, x + separator, bottom, border_color);
+            }
+            if self.overflow_count() > 0 {
+                self.draw_chevron(context);
+            }
+        });
+    }
+
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.position = Point { x, y };
+        self.update_layout();
+    }
+
+    fn get_position(&self) -> Point {
+        self.position
+    }
+
+    fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        self.update_layout();
+    }
+
+    fn get_size(&self) -> Size {
+        self.size.clone()
+    }
+}
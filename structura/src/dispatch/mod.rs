@@ -0,0 +1,73 @@
+//!
+//! Structura
+//!
+//! Posting work to the UI loop from other threads.
+//!
+//! `Component`s live on the UI thread, so background work cannot change them directly.
+//! Instead it calls `post()` with a closure, which the `Application` runs on the UI thread at
+//! its next chance, then redraws. `request_redraw()` only asks for the redraw, e.g. after
+//! changing state that is shared with a `Component` through an `Arc`.
+//!
+//! Both wake the `Application` if it is waiting for events. Work posted before the
+//! `Application` runs is kept until it starts.
+//!
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use winit::event_loop::EventLoopProxy;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+static TASKS: Mutex<Vec<Task>> = Mutex::new(Vec::new());
+static REDRAW_REQUESTED: AtomicBool = AtomicBool::new(false);
+static PROXY: Mutex<Option<EventLoopProxy<()>>> = Mutex::new(None);
+
+///
+/// Run `task` on the UI thread, then redraw. Can be called from any thread.
+///
+pub fn post<F: FnOnce() + Send + 'static>(task: F) {
+    TASKS.lock().unwrap().push(Box::new(task));
+    wake();
+}
+
+///
+/// Ask the `Application` to redraw. Can be called from any thread.
+///
+pub fn request_redraw() {
+    REDRAW_REQUESTED.store(true, Ordering::Release);
+    wake();
+}
+
+///
+/// Wake the event loop, so that it runs the pending work.
+///
+fn wake() {
+    if let Some(proxy) = PROXY.lock().unwrap().as_ref() {
+        //
+        // Fails only when the event loop has exited, when there is nothing left to wake.
+        //
+        let _ = proxy.send_event(());
+    }
+}
+
+///
+/// Set the proxy that wakes the event loop. Called by the `Application` before it runs.
+///
+pub(crate) fn set_event_loop_proxy(proxy: EventLoopProxy<()>) {
+    *PROXY.lock().unwrap() = Some(proxy);
+}
+
+///
+/// Run the posted tasks, in the order they were posted. Returns true if anything ran, or a
+/// redraw was requested.
+///
+/// Tasks may post more tasks, which run at the next call.
+///
+pub(crate) fn run_pending() -> bool {
+    let tasks = std::mem::take(&mut *TASKS.lock().unwrap());
+    let ran = !tasks.is_empty();
+    for task in tasks {
+        task();
+    }
+    REDRAW_REQUESTED.swap(false, Ordering::Acquire) || ran
+}
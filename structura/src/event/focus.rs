@@ -0,0 +1,104 @@
+//!
+//! Structura: Keyboard focus.
+//!
+//! At most one `Component` has keyboard focus. Each `Component` that can take focus owns a
+//! `FocusId`, and gives itself focus with `set_focus()`, e.g. when it is clicked. Other
+//! `Component`s can move the focus too, such as a `Label` when its mnemonic is pressed.
+//!
+//! Keyboard events go to the whole tree, so a focusable `Component` checks `is_focused()`
+//! before reacting to them.
+//!
+//! A `Component` that takes typed text, such as a `TextField`, owns a `FocusId` from
+//! `FocusId::for_text_input()`, so that plain keys reach it rather than keyboard shortcuts.
+//!
+
+use std::cell::Cell;
+
+thread_local! {
+    static NEXT_FOCUS_ID: Cell<u64> = const { Cell::new(1) };
+    static FOCUSED: Cell<Option<FocusId>> = const { Cell::new(None) };
+}
+
+///
+/// Identifies a `Component` that can take keyboard focus.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FocusId {
+    id: u64,
+    text_input: bool,
+}
+
+impl FocusId {
+    ///
+    /// Allocate a new, unique `FocusId`.
+    ///
+    pub fn new() -> Self {
+        Self::allocate(false)
+    }
+
+    ///
+    /// Allocate a new, unique `FocusId` for a `Component` that takes typed text. See
+    /// `text_input_focused()`.
+    ///
+    pub fn for_text_input() -> Self {
+        Self::allocate(true)
+    }
+
+    pub fn is_text_input(&self) -> bool {
+        self.text_input
+    }
+
+    fn allocate(text_input: bool) -> Self {
+        NEXT_FOCUS_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            FocusId { id, text_input }
+        })
+    }
+}
+
+impl Default for FocusId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///
+/// Give keyboard focus to `id`, taking it from the `Component` that had it.
+///
+pub fn set_focus(id: FocusId) {
+    FOCUSED.with(|focused| focused.set(Some(id)));
+}
+
+///
+/// Remove keyboard focus from `id`, if it has focus.
+///
+pub fn clear_focus(id: FocusId) {
+    FOCUSED.with(|focused| {
+        if focused.get() == Some(id) {
+            focused.set(None);
+        }
+    });
+}
+
+///
+/// Returns the `FocusId` that has keyboard focus, if any.
+///
+pub fn focused() -> Option<FocusId> {
+    FOCUSED.with(|focused| focused.get())
+}
+
+///
+/// Returns true if `id` has keyboard focus.
+///
+pub fn is_focused(id: FocusId) -> bool {
+    focused() == Some(id)
+}
+
+///
+/// Returns true if a `Component` that takes typed text has keyboard focus, e.g. so that the
+/// `Application` leaves it the keys that type or edit text.
+///
+pub fn text_input_focused() -> bool {
+    focused().is_some_and(|id| id.text_input)
+}
@@ -2,8 +2,12 @@
 //! Structura
 //!
 
+pub mod focus;
+
 use crate::geometry::Point;
+use std::cell::Cell;
 use winit::event::MouseScrollDelta;
+use winit::keyboard::ModifiersState;
 
 // #[derive(Clone)]
 // pub struct Callback<Handler> {
@@ -13,11 +17,81 @@ use winit::event::MouseScrollDelta;
 ///
 /// Mouse state for mouse events.
 ///
+/// New fields may be added, so outside this crate a `MouseInput` is built with
+/// `MouseInput::new()` and then changed, rather than with a struct literal.
+///
 #[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
 pub struct MouseInput {
     pub scale_factor: f64,
     pub position: Point,
     pub pressed: bool,
     pub just_released: bool,
+
+    /// True when the secondary (right) button was pressed, e.g. to open a context menu.
+    /// `pressed` is only set for the primary button.
+    pub secondary_pressed: bool,
     pub mouse_scroll: Option<MouseScrollDelta>,
 }
+
+impl MouseInput {
+    ///
+    /// Constructor, for the cursor at `position` with no button pressed or released.
+    ///
+    pub fn new(position: Point, scale_factor: f64) -> Self {
+        Self {
+            scale_factor,
+            position,
+            pressed: false,
+            just_released: false,
+            secondary_pressed: false,
+            mouse_scroll: None,
+        }
+    }
+
+    ///
+    /// Returns a copy of the input, moved to a position that is outside every `Component`.
+    ///
+    /// Used to tell a `Component` about a mouse event that happened somewhere it cannot see,
+    /// such as over a popup layer, so that it can update its state (e.g. stop hovering or
+    /// dragging) without reacting to the position.
+    ///
+    pub fn elsewhere(&self) -> MouseInput {
+        MouseInput {
+            position: Point {
+                x: f64::MIN,
+                y: f64::MIN,
+            },
+            ..*self
+        }
+    }
+
+    ///
+    /// Returns true if the input was moved out of reach by `elsewhere()`, so its position is
+    /// meaningless, e.g. for a `Component` that is being dragged.
+    ///
+    pub fn is_elsewhere(&self) -> bool {
+        self.position.x == f64::MIN && self.position.y == f64::MIN
+    }
+}
+
+thread_local! {
+    static MODIFIERS: Cell<ModifiersState> = const { Cell::new(ModifiersState::empty()) };
+}
+
+///
+/// Returns the keyboard modifiers (Shift, Control, Alt, Super) that are currently held down.
+///
+/// `winit` reports modifier changes separately from key events, so the `Application` records
+/// them here for `Component`s to query while handling a `KeyEvent`.
+///
+pub fn modifiers() -> ModifiersState {
+    MODIFIERS.with(|m| m.get())
+}
+
+///
+/// Record the current keyboard modifiers. Called by the `Application`.
+///
+pub(crate) fn set_modifiers(state: ModifiersState) {
+    MODIFIERS.with(|m| m.set(state));
+}
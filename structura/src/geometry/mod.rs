@@ -28,3 +28,69 @@ pub struct Rectangle {
     pub point: Point,
     pub size: Size,
 }
+
+impl Rectangle {
+    ///
+    /// Constructor.
+    ///
+    pub fn new(x: f64, y: f64, width: u32, height: u32) -> Self {
+        Self {
+            point: Point { x, y },
+            size: Size { width, height },
+        }
+    }
+
+    ///
+    /// Checks if the specified `x` and `y` coordinates are inside the `Rectangle`.
+    ///
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.point.x
+            && x < self.point.x + self.size.width as f64
+            && y >= self.point.y
+            && y < self.point.y + self.size.height as f64
+    }
+
+    ///
+    /// Returns the overlapping area of `self` and `other`, or an empty `Rectangle` when
+    /// the two do not overlap.
+    ///
+    pub fn intersect(&self, other: &Rectangle) -> Rectangle {
+        let x0 = self.point.x.max(other.point.x);
+        let y0 = self.point.y.max(other.point.y);
+        let x1 = (self.point.x + self.size.width as f64).min(other.point.x + other.size.width as f64);
+        let y1 =
+            (self.point.y + self.size.height as f64).min(other.point.y + other.size.height as f64);
+        Rectangle::new(x0, y0, (x1 - x0).max(0.0) as u32, (y1 - y0).max(0.0) as u32)
+    }
+}
+
+///
+/// The direction of a layout, scrollbar, or other directional `Component`.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+///
+/// Horizontal placement of content, such as text, within a `Component`.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum HorizontalAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+///
+/// Vertical placement of content, such as text, within a `Component`.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
@@ -14,6 +14,12 @@ pub mod view;
 #[path = "mvc/controller.rs"]
 pub mod controller;
 
+#[path = "mvc/layer.rs"]
+pub mod layer;
+
+#[path = "mvc/reload.rs"]
+pub mod reload;
+
 #[path = "draw/device.rs"]
 pub mod device;
 
@@ -23,10 +29,24 @@ pub mod primitive;
 #[path = "draw/render.rs"]
 pub mod render;
 
+mod macros;
+
+pub mod animation;
+
+pub mod clipboard;
+
+pub mod command;
+
 pub mod component;
 
+pub mod dispatch;
+
 pub mod event;
 
 pub mod geometry;
 
+pub mod markup;
+
+pub mod timer;
+
 pub mod container;
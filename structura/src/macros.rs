@@ -0,0 +1,109 @@
+//!
+//! Structura
+//!
+//! The `ui!` macro, for building trees of containers and components in code.
+//!
+
+///
+/// Build a tree of containers and components, expanding to the fluent constructors and
+/// `Container::push()` calls that would otherwise be written by hand.
+///
+/// ```ignore
+/// ui! {
+///     let root = BorderLayout::new() => {
+///         .set_splitters(true),
+///         #[set_north] Row::new(20.0, 20.0, 1, 60) => {
+///             Button::default().set_text("Save".to_string()).on_click(|| println!("save")),
+///             let add = Button::default().set_text("Add".to_string()),
+///         },
+///         #[set_center] Tabs::new().closable(true) => {
+///             #[add_tab("Notes".to_string())] let notes = TextArea::new(),
+///         },
+///     };
+/// }
+/// notes.borrow_mut().insert_str("Hello");
+/// let mut application = Application::new(Box::new(root));
+/// ```
+///
+/// Each node is an expression that builds a component, usually a constructor followed by
+/// fluent setters and handler closures, optionally followed by `=> { ... }` holding a
+/// comma-separated list of:
+///
+/// - Child nodes, added with `Container::push()`.
+/// - `#[method] child` or `#[method(args)] child`, adding the child with
+///   `container.method(args, Box::new(child))` instead, e.g. `#[set_north]` in a
+///   `BorderLayout` or `#[add_tab("Title".to_string())]` in `Tabs`.
+/// - `.method(args)`, calling a `&mut self` method on the container, e.g.
+///   `.set_splitters(true)`.
+///
+/// `let name = child` binds `name` to an `Rc<RefCell<_>>` of the child, which is added to
+/// its parent inside a `ComponentHandle`, so it can still be changed from other closures. The
+/// bindings are in scope after the macro, which must then be used as a statement starting with
+/// `let root = ...;`. The root is bound as a plain value, ready to be boxed for the
+/// `Application`. Without `let`, `ui!(node)` is an expression that returns the root.
+///
+/// Errors in the expressions are reported by the compiler at the expression itself, and
+/// malformed nodes at the first unexpected token.
+///
+#[macro_export]
+macro_rules! ui {
+    //
+    // Body items: a comma-separated list of `.method(args)` calls, and child nodes with an
+    // optional `#[method(args)]` placement.
+    //
+    (@body $parent:ident;) => {};
+    (@body $parent:ident; . $method:ident ( $($arg:tt)* ) $(, $($rest:tt)*)?) => {
+        $parent.$method($($arg)*);
+        $crate::ui!(@body $parent; $($($rest)*)?);
+    };
+    (@body $parent:ident; #[$place:ident $(( $($place_arg:expr),* $(,)? ))?] $($rest:tt)*) => {
+        $crate::ui!(@child $parent, $place, ($($($place_arg),*)?); $($rest)*);
+    };
+    (@body $parent:ident; $($rest:tt)+) => {
+        $crate::ui!(@child $parent, push, (); $($rest)+);
+    };
+
+    //
+    // Child nodes: build the node and its children, then add it to the parent.
+    //
+    (@child $parent:ident, $place:ident, ($($place_arg:expr),*);
+        let $name:ident = $node:expr $(=> { $($body:tt)* })? $(, $($rest:tt)*)?
+    ) => {
+        #[allow(unused_mut)]
+        let mut node = $node;
+        $crate::ui!(@body node; $($($body)*)?);
+        let $name = ::std::rc::Rc::new(::std::cell::RefCell::new(node));
+        $parent.$place(
+            $($place_arg,)*
+            ::std::boxed::Box::new($crate::component::ComponentHandle::new($name.clone())),
+        );
+        $crate::ui!(@body $parent; $($($rest)*)?);
+    };
+    (@child $parent:ident, $place:ident, ($($place_arg:expr),*);
+        $node:expr $(=> { $($body:tt)* })? $(, $($rest:tt)*)?
+    ) => {
+        #[allow(unused_mut)]
+        let mut node = $node;
+        $crate::ui!(@body node; $($($body)*)?);
+        $parent.$place($($place_arg,)* ::std::boxed::Box::new(node));
+        $crate::ui!(@body $parent; $($($rest)*)?);
+    };
+
+    //
+    // Entry points: a `let` statement binding the root, or an expression returning it.
+    //
+    (let $name:ident = $node:expr $(=> { $($body:tt)* })? $(;)?) => {
+        use $crate::container::Container as _;
+        #[allow(unused_mut)]
+        let mut node = $node;
+        $crate::ui!(@body node; $($($body)*)?);
+        let $name = node;
+    };
+    ($node:expr $(=> { $($body:tt)* })?) => {{
+        use $crate::container::Container as _;
+        #[allow(unused_mut)]
+        let mut node = $node;
+        $crate::ui!(@body node; $($($body)*)?);
+        node
+    }};
+}
@@ -0,0 +1,754 @@
+//!
+//! Structura
+//!
+//! Builds containers and components from parsed markup elements.
+//!
+
+use crate::component::ComponentState;
+use crate::component::button::Button;
+use crate::component::style::{DefaultComponentTheme, StateTheme, StyleClass};
+use crate::component::styled::Styled;
+use crate::component::textarea::TextArea;
+use crate::component::{Component, ComponentHandle};
+use crate::container::accordion::Accordion;
+use crate::container::anchor::{Anchor, AnchorPane, Anchors};
+use crate::container::border::{BorderLayout, Region};
+use crate::container::column::Column;
+use crate::container::expander::Expander;
+use crate::container::panel::Panel;
+use crate::container::row::Row;
+use crate::container::scroll::ScrollView;
+use crate::container::tabs::Tabs;
+use crate::container::{Container, ContainerComponent};
+use crate::markup::{Callback, CallbackArg, ComponentIds, MarkupError, MarkupLoader, Ui};
+use roxmltree::{Document, Node};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// Element names of the containers that markup can build.
+const CONTAINERS: &[&str] = &[
+    "Accordion",
+    "AnchorPane",
+    "BorderLayout",
+    "Column",
+    "Expander",
+    "Panel",
+    "Row",
+    "ScrollView",
+    "Tabs",
+];
+
+/// Element names of the components that markup can build.
+const COMPONENTS: &[&str] = &["Button", "TextArea"];
+
+type Result<T> = std::result::Result<T, MarkupError>;
+
+///
+/// Parse `source` and build the `Ui`.
+///
+pub(crate) fn build(loader: &MarkupLoader, source: &str) -> Result<Ui> {
+    let document = parse_document(source)?;
+    let mut builder = Builder {
+        loader,
+        ids: ComponentIds::default(),
+        classes: HashMap::new(),
+    };
+
+    let root = Element::new(document.root_element());
+    let container = if root.name() == "Ui" {
+        let mut container = None;
+        for child in root.children()? {
+            match child.name() {
+                "Styles" => builder.load_styles(&child)?,
+                _ if container.is_none() => container = Some(child),
+                _ => return Err(child.error("<Ui> holds a single root container")),
+            }
+        }
+        root.finish()?;
+        container.ok_or_else(|| root.error("<Ui> needs a root container"))?
+    } else {
+        root
+    };
+    let root = builder.build_container(&container)?;
+
+    Ok(Ui {
+        root,
+        ids: builder.ids,
+        classes: builder.classes,
+    })
+}
+
+///
+/// Parse `source` and build a `StateTheme` over the `DefaultComponentTheme`.
+///
+pub(crate) fn build_theme(source: &str) -> Result<StateTheme> {
+    let document = parse_document(source)?;
+    let root = Element::new(document.root_element());
+    if root.name() != "Theme" {
+        return Err(root.error("the root element of a theme is <Theme>"));
+    }
+    root.finish()?;
+    let mut styles = vec![];
+    for style in root.children()? {
+        if style.name() != "Style" {
+            return Err(style.error("<Theme> can only hold <Style> elements"));
+        }
+        let state = match style.attr("state") {
+            None => None,
+            Some((value, offset)) => Some(match value.trim() {
+                "active" => ComponentState::Active,
+                "hovered" => ComponentState::Hovered,
+                "pressed" => ComponentState::Pressed,
+                "focused" => ComponentState::Focused,
+                "disabled" => ComponentState::Disabled,
+                _ => {
+                    return Err(style.error_at(
+                        offset,
+                        format!(
+                            "invalid state `{value}`: expected `active`, `hovered`, `pressed`, `focused` or `disabled`"
+                        ),
+                    ));
+                }
+            }),
+        };
+        let class = style_class(&style, "theme")?;
+        style.finish()?;
+        styles.push((state, class));
+    }
+    Ok(StateTheme {
+        base: Rc::new(DefaultComponentTheme),
+        styles,
+    })
+}
+
+fn parse_document(source: &str) -> Result<Document<'_>> {
+    Document::parse(source).map_err(|error| {
+        let pos = error.pos();
+        let message = error.to_string();
+        let message = message.trim_end_matches(&format!(" at {pos}"));
+        MarkupError::new(pos.row, pos.col, message.to_string())
+    })
+}
+
+///
+/// Read the color and border attributes of a `<Style>` element.
+///
+fn style_class(style: &Element, name: &str) -> Result<StyleClass> {
+    Ok(StyleClass {
+        name: name.to_string(),
+        edit_text_color: style.color("edit-text-color")?,
+        edit_back_color: style.color("edit-back-color")?,
+        fore_color: style.color("fore-color")?,
+        back_color: style.color("back-color")?,
+        cursor_color: style.color("cursor-color")?,
+        border_color: style.color("border-color")?,
+        border_width: style.size("border-width")?,
+    })
+}
+
+///
+/// A markup element, tracking which attributes have been read so that unknown attributes
+/// can be reported.
+///
+struct Element<'a, 'input> {
+    node: Node<'a, 'input>,
+    used: RefCell<HashSet<String>>,
+}
+
+impl<'a, 'input> Element<'a, 'input> {
+    fn new(node: Node<'a, 'input>) -> Self {
+        Self {
+            node,
+            used: RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn name(&self) -> &'a str {
+        self.node.tag_name().name()
+    }
+
+    fn error_at(&self, offset: usize, message: String) -> MarkupError {
+        let pos = self.node.document().text_pos_at(offset);
+        MarkupError::new(pos.row, pos.col, message)
+    }
+
+    ///
+    /// An error at the start of the element.
+    ///
+    fn error(&self, message: &str) -> MarkupError {
+        self.error_at(self.node.range().start, message.to_string())
+    }
+
+    ///
+    /// Returns the raw value of an attribute, and its position, marking it as read.
+    ///
+    fn attr(&self, name: &str) -> Option<(&'a str, usize)> {
+        let attribute = self.node.attribute_node(name)?;
+        self.used.borrow_mut().insert(name.to_string());
+        Some((attribute.value(), attribute.range().start))
+    }
+
+    fn string(&self, name: &str) -> Option<String> {
+        self.attr(name).map(|(value, _)| value.to_string())
+    }
+
+    ///
+    /// Parse an attribute with `FromStr`, describing the expected value as `expected` in
+    /// errors.
+    ///
+    fn parse<T: FromStr>(&self, name: &str, expected: &str) -> Result<Option<T>> {
+        match self.attr(name) {
+            None => Ok(None),
+            Some((value, offset)) => value.trim().parse().map(Some).map_err(|_| {
+                self.error_at(
+                    offset,
+                    format!("invalid value `{value}` for `{name}`: expected {expected}"),
+                )
+            }),
+        }
+    }
+
+    fn number(&self, name: &str) -> Result<Option<f64>> {
+        self.parse(name, "a number")
+    }
+
+    fn size(&self, name: &str) -> Result<Option<usize>> {
+        self.parse(name, "a whole number")
+    }
+
+    fn flag(&self, name: &str) -> Result<Option<bool>> {
+        self.parse(name, "`true` or `false`")
+    }
+
+    ///
+    /// Parse a color attribute: `#RRGGBB`, `#AARRGGBB` or `0xAARRGGBB`.
+    ///
+    fn color(&self, name: &str) -> Result<Option<u32>> {
+        let Some((value, offset)) = self.attr(name) else {
+            return Ok(None);
+        };
+        let digits = value
+            .trim()
+            .strip_prefix('#')
+            .or_else(|| value.trim().strip_prefix("0x"));
+        let parsed = digits.and_then(|digits| match digits.len() {
+            6 => u32::from_str_radix(digits, 16)
+                .ok()
+                .map(|rgb| 0xFF000000 | rgb),
+            8 => u32::from_str_radix(digits, 16).ok(),
+            _ => None,
+        });
+        parsed.map(Some).ok_or_else(|| {
+            self.error_at(
+                offset,
+                format!("invalid color `{value}` for `{name}`: expected `#RRGGBB` or `#AARRGGBB`"),
+            )
+        })
+    }
+
+    ///
+    /// Parse an anchor attribute: a pixel offset such as `10`, a percentage such as `50%`, or
+    /// both, such as `50% - 10`.
+    ///
+    fn anchor(&self, name: &str) -> Result<Option<Anchor>> {
+        let Some((value, offset)) = self.attr(name) else {
+            return Ok(None);
+        };
+        let invalid = || {
+            self.error_at(
+                offset,
+                format!(
+                    "invalid anchor `{value}` for `{name}`: expected e.g. `10`, `50%` or `50% - 10`"
+                ),
+            )
+        };
+        let anchor = match value.split_once('%') {
+            None => Anchor::offset(value.trim().parse().map_err(|_| invalid())?),
+            Some((percent, rest)) => {
+                let percent = percent.trim().parse().map_err(|_| invalid())?;
+                let rest: String = rest.chars().filter(|c| !c.is_whitespace()).collect();
+                let offset = if rest.is_empty() {
+                    0.0
+                } else {
+                    let offset: f64 = rest
+                        .trim_start_matches('+')
+                        .parse()
+                        .map_err(|_| invalid())?;
+                    if rest.starts_with('+') || rest.starts_with('-') {
+                        offset
+                    } else {
+                        return Err(invalid());
+                    }
+                };
+                Anchor::percent(percent, offset)
+            }
+        };
+        Ok(Some(anchor))
+    }
+
+    ///
+    /// Returns the child elements. Text other than whitespace is an error.
+    ///
+    fn children(&self) -> Result<Vec<Element<'a, 'input>>> {
+        let mut children = vec![];
+        for node in self.node.children() {
+            if node.is_element() {
+                children.push(Element::new(node));
+            } else if node.is_text() && !node.text().unwrap_or("").trim().is_empty() {
+                return Err(self.error_at(
+                    node.range().start,
+                    format!("unexpected text in <{}>", self.name()),
+                ));
+            }
+        }
+        Ok(children)
+    }
+
+    ///
+    /// Report the first attribute that was never read.
+    ///
+    fn finish(&self) -> Result<()> {
+        let used = self.used.borrow();
+        match self
+            .node
+            .attributes()
+            .find(|attribute| !used.contains(attribute.name()))
+        {
+            Some(attribute) => Err(self.error_at(
+                attribute.range().start,
+                format!(
+                    "unknown attribute `{}` on <{}>",
+                    attribute.name(),
+                    self.name()
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+///
+/// State collected while building: style classes and components with ids.
+///
+struct Builder<'l> {
+    loader: &'l MarkupLoader,
+    ids: ComponentIds,
+    classes: HashMap<String, Rc<StyleClass>>,
+}
+
+impl Builder<'_> {
+    ///
+    /// Load the `<Style>` elements in a `<Styles>` element.
+    ///
+    fn load_styles(&mut self, styles: &Element) -> Result<()> {
+        styles.finish()?;
+        for style in styles.children()? {
+            if style.name() != "Style" {
+                return Err(style.error("<Styles> can only hold <Style> elements"));
+            }
+            let Some((name, _)) = style.attr("class") else {
+                return Err(style.error("<Style> needs a `class` name"));
+            };
+            let class = style_class(&style, name)?;
+            style.finish()?;
+            self.classes.insert(name.to_string(), Rc::new(class));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Look up the callback named by an `on-...` attribute.
+    ///
+    fn callback(&self, element: &Element, name: &str) -> Result<Option<Callback>> {
+        let Some((value, offset)) = element.attr(name) else {
+            return Ok(None);
+        };
+        match self.loader.callback(value) {
+            Some(callback) => Ok(Some(callback)),
+            None => Err(element.error_at(
+                offset,
+                format!("no callback named `{value}` is bound for `{name}`"),
+            )),
+        }
+    }
+
+    ///
+    /// Read the `class` attribute.
+    ///
+    fn classes_for(&self, element: &Element) -> Result<Vec<Rc<StyleClass>>> {
+        let Some((value, offset)) = element.attr("class") else {
+            return Ok(vec![]);
+        };
+        value
+            .split_whitespace()
+            .map(|name| {
+                self.classes.get(name).cloned().ok_or_else(|| {
+                    element.error_at(offset, format!("unknown style class `{name}`"))
+                })
+            })
+            .collect()
+    }
+
+    ///
+    /// Read the `id` attribute, checking that it is unique.
+    ///
+    fn id_for(&self, element: &Element) -> Result<Option<String>> {
+        let Some((id, offset)) = element.attr("id") else {
+            return Ok(None);
+        };
+        if self.ids.contains(id) {
+            return Err(element.error_at(offset, format!("duplicate id `{id}`")));
+        }
+        Ok(Some(id.to_string()))
+    }
+
+    ///
+    /// Apply the `x`, `y`, `width` and `height` attributes.
+    ///
+    fn apply_geometry(&self, element: &Element, component: &mut dyn Component) -> Result<()> {
+        let position = component.get_position();
+        let size = component.get_size();
+        let x = element.number("x")?;
+        let y = element.number("y")?;
+        let width = element.size("width")?;
+        let height = element.size("height")?;
+        if x.is_some() || y.is_some() {
+            component.set_position(x.unwrap_or(position.x), y.unwrap_or(position.y));
+        }
+        if width.is_some() || height.is_some() {
+            component.set_size(
+                width.unwrap_or(size.width as usize),
+                height.unwrap_or(size.height as usize),
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// Apply the attributes common to all elements, and box the component.
+    ///
+    fn finish_component<T: Component + 'static>(
+        &mut self,
+        element: &Element,
+        mut component: T,
+    ) -> Result<Box<dyn Component>> {
+        self.apply_geometry(element, &mut component)?;
+        let classes = self.classes_for(element)?;
+        let id = self.id_for(element)?;
+        element.finish()?;
+        Ok(match id {
+            Some(id) => {
+                let inner = Rc::new(RefCell::new(component));
+                self.ids.insert(id, inner.clone());
+                styled_component(ComponentHandle::new(inner), classes)
+            }
+            None => styled_component(component, classes),
+        })
+    }
+
+    ///
+    /// Apply the attributes common to all elements, and box the container.
+    ///
+    fn finish_container<T: ContainerComponent + 'static>(
+        &mut self,
+        element: &Element,
+        mut container: T,
+    ) -> Result<Box<dyn ContainerComponent>> {
+        self.apply_geometry(element, &mut container)?;
+        let classes = self.classes_for(element)?;
+        let id = self.id_for(element)?;
+        element.finish()?;
+        Ok(match id {
+            Some(id) => {
+                let inner = Rc::new(RefCell::new(container));
+                self.ids.insert(id, inner.clone());
+                styled_container(ComponentHandle::new(inner), classes)
+            }
+            None => styled_container(container, classes),
+        })
+    }
+
+    ///
+    /// Build any container or component element.
+    ///
+    fn build_component(&mut self, element: &Element) -> Result<Box<dyn Component>> {
+        match element.name() {
+            "Button" => self.build_button(element),
+            "TextArea" => self.build_textarea(element),
+            name if CONTAINERS.contains(&name) => {
+                let container = self.build_container(element)?;
+                Ok(Box::new(container))
+            }
+            name => Err(element.error(&format!(
+                "unknown element <{name}>, expected one of: {}",
+                CONTAINERS
+                    .iter()
+                    .chain(COMPONENTS.iter())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    ///
+    /// Build a container element.
+    ///
+    fn build_container(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        match element.name() {
+            "Accordion" => self.build_accordion(element),
+            "AnchorPane" => self.build_anchor_pane(element),
+            "BorderLayout" => self.build_border_layout(element),
+            "Column" => self.build_column(element),
+            "Expander" => self.build_expander(element),
+            "Panel" => self.build_panel(element),
+            "Row" => self.build_row(element),
+            "ScrollView" => self.build_scroll_view(element),
+            "Tabs" => self.build_tabs(element),
+            name => Err(element.error(&format!(
+                "<{name}> is not a container, expected one of: {}",
+                CONTAINERS.join(", ")
+            ))),
+        }
+    }
+
+    ///
+    /// Build the only child of a single-child container.
+    ///
+    fn build_only_child(&mut self, element: &Element) -> Result<Option<Box<dyn Component>>> {
+        let mut children = element.children()?;
+        if children.len() > 1 {
+            return Err(children[1].error(&format!("<{}> holds a single child", element.name())));
+        }
+        children
+            .pop()
+            .map(|child| self.build_component(&child))
+            .transpose()
+    }
+
+    fn build_button(&mut self, element: &Element) -> Result<Box<dyn Component>> {
+        let mut button = Button::default();
+        if let Some(text) = element.string("text") {
+            button = button.set_text(text);
+        }
+        if let Some(callback) = self.callback(element, "on-click")? {
+            button = button.on_click(move || (callback.borrow_mut())(CallbackArg::None));
+        }
+        no_children(element)?;
+        self.finish_component(element, button)
+    }
+
+    fn build_textarea(&mut self, element: &Element) -> Result<Box<dyn Component>> {
+        let mut textarea = TextArea::new();
+        if let Some(text) = element.string("text") {
+            textarea.insert_str(&text);
+        }
+        if let Some(callback) = self.callback(element, "on-text-change")? {
+            textarea = textarea.on_text_change(move || (callback.borrow_mut())(CallbackArg::None));
+        }
+        no_children(element)?;
+        self.finish_component(element, textarea)
+    }
+
+    fn build_panel(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut panel = Panel::new();
+        if let Some(child) = self.build_only_child(element)? {
+            panel.push(child);
+        }
+        self.finish_container(element, panel)
+    }
+
+    fn build_row(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut row = Row::new(
+            element.number("x")?.unwrap_or(0.0),
+            element.number("y")?.unwrap_or(0.0),
+            element.size("spacing")?.unwrap_or(0),
+            element.size("height")?.unwrap_or(0),
+        );
+        for child in element.children()? {
+            row.push(self.build_component(&child)?);
+        }
+        self.finish_container(element, row)
+    }
+
+    fn build_column(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut column = Column::new(
+            element.number("x")?.unwrap_or(0.0),
+            element.number("y")?.unwrap_or(0.0),
+            element.size("spacing")?.unwrap_or(0),
+            element.size("width")?.unwrap_or(0) as u32,
+            element.size("height")?.unwrap_or(0) as u32,
+        );
+        for child in element.children()? {
+            column.push(self.build_component(&child)?);
+        }
+        self.finish_container(element, column)
+    }
+
+    fn build_border_layout(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut border = BorderLayout::new();
+        let hgap = element.size("hgap")?.unwrap_or(0) as u32;
+        let vgap = element.size("vgap")?.unwrap_or(0) as u32;
+        border.set_gap(hgap, vgap);
+        if let Some(splitters) = element.flag("splitters")? {
+            border.set_splitters(splitters);
+        }
+        for child in element.children()? {
+            let region = match child.attr("region") {
+                None => None,
+                Some((value, offset)) => Some(parse_region(value).ok_or_else(|| {
+                    child.error_at(
+                        offset,
+                        format!(
+                            "invalid region `{value}`: expected north, west, center, east or south"
+                        ),
+                    )
+                })?),
+            };
+            let component = self.build_component(&child)?;
+            match region {
+                Some(region) => border.set_region(region, component),
+                None => {
+                    if border.try_push(component).is_err() {
+                        return Err(child.error("all <BorderLayout> regions are already filled"));
+                    }
+                }
+            }
+        }
+        self.finish_container(element, border)
+    }
+
+    fn build_anchor_pane(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut pane = AnchorPane::new();
+        for child in element.children()? {
+            let anchors = Anchors {
+                left: child.anchor("left")?,
+                top: child.anchor("top")?,
+                right: child.anchor("right")?,
+                bottom: child.anchor("bottom")?,
+            };
+            let component = self.build_component(&child)?;
+            pane.add(component, anchors);
+        }
+        self.finish_container(element, pane)
+    }
+
+    fn build_scroll_view(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut scroll = ScrollView::new();
+        if let Some(width) = element.size("scrollbar-width")? {
+            scroll = scroll.set_scrollbar_width(width as u32);
+        }
+        if let Some(amount) = element.number("line-scroll")? {
+            scroll = scroll.set_line_scroll_amount(amount);
+        }
+        if let Some(child) = self.build_only_child(element)? {
+            scroll.push(child);
+        }
+        self.finish_container(element, scroll)
+    }
+
+    fn build_tabs(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut tabs = Tabs::new()
+            .closable(element.flag("closable")?.unwrap_or(false))
+            .reorderable(element.flag("reorderable")?.unwrap_or(false));
+        if let Some(width) = element.size("tab-width")? {
+            tabs = tabs.set_tab_width(width as u32);
+        }
+        if let Some(height) = element.size("strip-height")? {
+            tabs = tabs.set_strip_height(height as u32);
+        }
+        if let Some(callback) = self.callback(element, "on-selection-change")? {
+            tabs = tabs.on_selection_change(move |index| {
+                (callback.borrow_mut())(CallbackArg::Index(index))
+            });
+        }
+        for child in element.children()? {
+            let title = child
+                .string("title")
+                .unwrap_or_else(|| format!("Tab {}", tabs.tab_count() + 1));
+            let component = self.build_component(&child)?;
+            tabs.add_tab(title, component);
+        }
+        self.finish_container(element, tabs)
+    }
+
+    fn build_expander(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let title = element.string("title").unwrap_or_default();
+        let Some(child) = self.build_only_child(element)? else {
+            return Err(element.error("<Expander> needs a child"));
+        };
+        let mut expander =
+            Expander::new(title, child).expanded(element.flag("expanded")?.unwrap_or(false));
+        if let Some(height) = element.size("header-height")? {
+            expander = expander.set_header_height(height as u32);
+        }
+        if let Some(callback) = self.callback(element, "on-toggle")? {
+            expander = expander
+                .on_toggle(move |expanded| (callback.borrow_mut())(CallbackArg::Bool(expanded)));
+        }
+        self.finish_container(element, expander)
+    }
+
+    fn build_accordion(&mut self, element: &Element) -> Result<Box<dyn ContainerComponent>> {
+        let mut accordion = Accordion::new();
+        if let Some(callback) = self.callback(element, "on-change")? {
+            accordion = accordion.on_change(move |index| {
+                let arg = match index {
+                    Some(index) => CallbackArg::Index(index),
+                    None => CallbackArg::None,
+                };
+                (callback.borrow_mut())(arg)
+            });
+        }
+        for child in element.children()? {
+            let title = child
+                .string("title")
+                .unwrap_or_else(|| format!("Section {}", accordion.section_count() + 1));
+            let component = self.build_component(&child)?;
+            accordion.add_section(title, component);
+        }
+        self.finish_container(element, accordion)
+    }
+}
+
+fn styled_component<T: Component + 'static>(
+    component: T,
+    classes: Vec<Rc<StyleClass>>,
+) -> Box<dyn Component> {
+    if classes.is_empty() {
+        Box::new(component)
+    } else {
+        Box::new(Styled::new(component, classes))
+    }
+}
+
+fn styled_container<T: ContainerComponent + 'static>(
+    container: T,
+    classes: Vec<Rc<StyleClass>>,
+) -> Box<dyn ContainerComponent> {
+    if classes.is_empty() {
+        Box::new(container)
+    } else {
+        Box::new(Styled::new(container, classes))
+    }
+}
+
+fn no_children(element: &Element) -> Result<()> {
+    match element.children()?.first() {
+        Some(child) => Err(child.error(&format!("<{}> cannot hold children", element.name()))),
+        None => Ok(()),
+    }
+}
+
+fn parse_region(value: &str) -> Option<Region> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "north" => Some(Region::North),
+        "west" => Some(Region::West),
+        "center" => Some(Region::Center),
+        "east" => Some(Region::East),
+        "south" => Some(Region::South),
+        _ => None,
+    }
+}
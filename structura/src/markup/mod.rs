@@ -0,0 +1,309 @@
+//!
+//! Structura
+//!
+//! UI markup: build a tree of containers and components from an XML description.
+//!
+//! ```xml
+//! <Ui>
+//!   <Styles>
+//!     <Style class="danger" back-color="#CC3333" border-width="3"/>
+//!   </Styles>
+//!   <BorderLayout splitters="true">
+//!     <Tabs region="center" closable="true">
+//!       <TextArea title="Notes" id="notes"/>
+//!     </Tabs>
+//!     <Row region="south" spacing="8" height="60">
+//!       <Button text="Save" on-click="save"/>
+//!       <Button text="Delete" class="danger" on-click="delete"/>
+//!     </Row>
+//!   </BorderLayout>
+//! </Ui>
+//! ```
+//!
+//! - Element names are the container and component type names. The root element is either
+//!   `<Ui>`, holding optional `<Styles>` and a single container, or the container itself.
+//! - Attributes set properties. `x`, `y`, `width` and `height` work on every element.
+//! - `id="name"` makes the component available from `Ui::get()`.
+//! - `class="a b"` draws the component with the named `<Style>` classes applied on top of
+//!   the theme. See `StyleClass`.
+//! - `on-...` attributes name a callback bound with `MarkupLoader::bind()`.
+//! - Some attributes are read by the parent container, such as `region` in a
+//!   `<BorderLayout>`, `title` in `<Tabs>` and `<Accordion>`, and `left` / `top` / `right` /
+//!   `bottom` anchors in an `<AnchorPane>`.
+//!
+//! Errors report the line and column of the offending element or attribute.
+//!
+//! Themes can be loaded from a separate file with `load_theme_file()`:
+//!
+//! ```xml
+//! <Theme>
+//!   <Style back-color="#224488" border-color="#112244"/>
+//!   <Style state="focused" border-color="#FFCC00" border-width="3"/>
+//! </Theme>
+//! ```
+//!
+//! A `<Style>` without a `state` applies to all states, with colors lightened when hovered and
+//! darkened when pressed. A `<Style state="...">` applies to that `ComponentState` only, as is.
+//!
+
+mod builder;
+
+use crate::component::Component;
+use crate::component::style::{StateTheme, StyleClass};
+use crate::container::ContainerComponent;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+///
+/// The value passed to a callback bound with `MarkupLoader::bind()`. Which variant is passed
+/// depends on the event: e.g. `on-click` passes `None`, `on-selection-change` passes an
+/// `Index`, and `on-toggle` passes a `Bool`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallbackArg {
+    None,
+    Bool(bool),
+    Index(usize),
+    Number(f64),
+    Text(String),
+}
+
+///
+/// A callback that markup can refer to by name.
+///
+pub type Callback = Rc<RefCell<dyn FnMut(CallbackArg)>>;
+
+///
+/// An error found while loading markup, with the position it was found at.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupError {
+    /// The file the markup was loaded from, if any.
+    pub file: Option<PathBuf>,
+
+    /// Line number, starting from 1. `0` when the error has no position, e.g. the file could
+    /// not be read.
+    pub line: u32,
+
+    /// Column number, starting from 1.
+    pub column: u32,
+
+    pub message: String,
+}
+
+impl MarkupError {
+    pub(crate) fn new(line: u32, column: u32, message: String) -> Self {
+        Self {
+            file: None,
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = self.file.as_ref() {
+            write!(f, "{}:", file.display())?;
+        }
+        if self.line > 0 {
+            write!(f, "{}:{}: ", self.line, self.column)?;
+        } else if self.file.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+///
+/// The result of loading markup: the root container, and the components that have an `id`.
+///
+pub struct Ui {
+    pub root: Box<dyn ContainerComponent>,
+    pub ids: ComponentIds,
+    classes: HashMap<String, Rc<StyleClass>>,
+}
+
+impl Ui {
+    ///
+    /// Returns the component with the specified `id`, if it exists and has the type `T`.
+    ///
+    /// ```ignore
+    /// let notes = ui.get::<TextArea>("notes").unwrap();
+    /// notes.borrow_mut().insert_str("Hello");
+    /// ```
+    ///
+    pub fn get<T: 'static>(&self, id: &str) -> Option<Rc<RefCell<T>>> {
+        self.ids.get(id)
+    }
+
+    ///
+    /// Returns the style class with the specified name.
+    ///
+    pub fn class(&self, name: &str) -> Option<Rc<StyleClass>> {
+        self.classes.get(name).cloned()
+    }
+}
+
+///
+/// A component with an `id`, both as its concrete type (for `ComponentIds::get()`) and as a
+/// `Component`.
+///
+#[derive(Clone)]
+struct Identified {
+    any: Rc<dyn Any>,
+    component: Rc<RefCell<dyn Component>>,
+}
+
+///
+/// The components built from markup that have an `id`.
+///
+#[derive(Clone, Default)]
+pub struct ComponentIds {
+    components: HashMap<String, Identified>,
+}
+
+impl ComponentIds {
+    fn insert<T: Component + 'static>(&mut self, id: String, component: Rc<RefCell<T>>) {
+        let identified = Identified {
+            any: component.clone(),
+            component,
+        };
+        self.components.insert(id, identified);
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.components.contains_key(id)
+    }
+
+    ///
+    /// Returns the component with the specified `id`, if it exists and has the type `T`.
+    ///
+    pub fn get<T: 'static>(&self, id: &str) -> Option<Rc<RefCell<T>>> {
+        self.components
+            .get(id)?
+            .any
+            .clone()
+            .downcast::<RefCell<T>>()
+            .ok()
+    }
+
+    ///
+    /// Returns all ids.
+    ///
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.components.keys().map(String::as_str)
+    }
+
+    ///
+    /// Collect `Component::save_state()` from each component, by id.
+    ///
+    pub fn save_states(&self) -> HashMap<String, Box<dyn Any>> {
+        self.components
+            .iter()
+            .filter_map(|(id, identified)| {
+                let state = identified.component.borrow().save_state()?;
+                Some((id.clone(), state))
+            })
+            .collect()
+    }
+
+    ///
+    /// Give each saved state back to the component with the same id, if there is one.
+    ///
+    pub fn restore_states(&self, states: HashMap<String, Box<dyn Any>>) {
+        for (id, state) in states {
+            if let Some(identified) = self.components.get(&id) {
+                identified.component.borrow_mut().restore_state(state);
+            }
+        }
+    }
+}
+
+///
+/// Builds a `Ui` from markup, using the callbacks bound to it by name.
+///
+/// ```ignore
+/// let ui = MarkupLoader::new()
+///     .bind("save", |_| println!("save"))
+///     .load_file("ui/main.xml")?;
+/// let mut application = Application::new(ui.root);
+/// ```
+///
+#[derive(Default)]
+pub struct MarkupLoader {
+    callbacks: HashMap<String, Callback>,
+}
+
+impl MarkupLoader {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Bind a callback to `name`, for use in `on-...` attributes.
+    ///
+    pub fn bind<F: FnMut(CallbackArg) + 'static>(mut self, name: &str, callback: F) -> Self {
+        self.callbacks
+            .insert(name.to_string(), Rc::new(RefCell::new(callback)));
+        self
+    }
+
+    pub(crate) fn callback(&self, name: &str) -> Option<Callback> {
+        self.callbacks.get(name).cloned()
+    }
+
+    ///
+    /// Build a `Ui` from markup text.
+    ///
+    pub fn load_str(&self, source: &str) -> Result<Ui, MarkupError> {
+        builder::build(self, source)
+    }
+
+    ///
+    /// Build a `Ui` from a markup file. Errors include the file path.
+    ///
+    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Ui, MarkupError> {
+        load_from_file(path.as_ref(), |source| self.load_str(source))
+    }
+}
+
+///
+/// Build a `StateTheme` from theme markup text.
+///
+pub fn load_theme_str(source: &str) -> Result<StateTheme, MarkupError> {
+    builder::build_theme(source)
+}
+
+///
+/// Build a `StateTheme` from a theme markup file. Errors include the file path.
+///
+pub fn load_theme_file<P: AsRef<Path>>(path: P) -> Result<StateTheme, MarkupError> {
+    load_from_file(path.as_ref(), load_theme_str)
+}
+
+///
+/// Read `path` and pass its contents to `load`, adding the path to errors.
+///
+fn load_from_file<T, F>(path: &Path, load: F) -> Result<T, MarkupError>
+where
+    F: FnOnce(&str) -> Result<T, MarkupError>,
+{
+    let with_file = |mut error: MarkupError| {
+        error.file = Some(path.to_path_buf());
+        error
+    };
+    let source = std::fs::read_to_string(path)
+        .map_err(|error| with_file(MarkupError::new(0, 0, error.to_string())))?;
+    load(&source).map_err(with_file)
+}
@@ -2,20 +2,28 @@
 //! Structura: Application wrapper/helper struct.
 //!
 
+use crate::animation;
 use crate::component;
 use crate::component::style::{ComponentTheme, DefaultComponentTheme};
 use crate::container::ContainerComponent;
+use crate::container::panel::Panel;
+use crate::dispatch;
+use crate::event::MouseInput;
 use crate::geometry::{Point, Size};
+use crate::layer::LayerStack;
+use crate::reload::HotReload;
+use crate::timer;
 use crate::view::BufferContext;
 use softbuffer::{Buffer, Context, Surface};
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::time::Instant;
 use winit::application::ApplicationHandler;
 use winit::dpi::LogicalSize;
 use winit::event::{ElementState, Event, MouseButton, WindowEvent};
-use winit::event_loop::ActiveEventLoop;
 use winit::event_loop::EventLoop;
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
 use winit::window::{Window, WindowAttributes, WindowId};
 //
 // TODO: Track the component that currently has focus?
@@ -26,9 +34,19 @@ use winit::window::{Window, WindowAttributes, WindowId};
 ///
 pub struct Application {
     pub root: Box<dyn ContainerComponent>,
+
+    /// Floating content drawn above `root`, such as popups and modal dialogs.
+    pub layers: LayerStack,
+
     pub cursor_pos: Option<Point>,
     pub mouse_pressed: bool,
-    pub theme: Box<dyn ComponentTheme>,
+    pub theme: Rc<dyn ComponentTheme>,
+
+    /// Time of the last animation frame. See `crate::animation`.
+    last_frame: Instant,
+
+    /// Watched markup and theme files. See `crate::reload`.
+    hot_reload: Option<HotReload>,
     //
     // TODO: Separate UI rendering handle?
     //
@@ -48,13 +66,36 @@ impl Application {
     pub fn new(root: Box<dyn ContainerComponent>) -> Self {
         Self {
             root,
+            layers: LayerStack::new(),
             cursor_pos: None,
             mouse_pressed: false,
-            theme: Box::new(DefaultComponentTheme::default()),
+            theme: Rc::new(DefaultComponentTheme::default()),
+            last_frame: Instant::now(),
+            hot_reload: None,
             //message_join_handle: None,
         }
     }
 
+    ///
+    /// Constructor, building `root` (and the theme, if any) from the files watched by
+    /// `hot_reload`, and loading them again whenever they change.
+    ///
+    /// If the markup file fails to load, `root` starts as an empty `Panel`, and the error is
+    /// shown until the file is fixed.
+    ///
+    pub fn with_hot_reload(mut hot_reload: HotReload) -> Self {
+        let reloaded = hot_reload.poll(Instant::now());
+        let root = reloaded
+            .root
+            .unwrap_or_else(|| Box::new(Panel::new()) as Box<dyn ContainerComponent>);
+        let mut application = Application::new(root);
+        if let Some(theme) = reloaded.theme {
+            application.theme = theme;
+        }
+        application.hot_reload = Some(hot_reload);
+        application
+    }
+
     ///
     /// Initialize and run.
     ///
@@ -74,9 +115,58 @@ impl Application {
         });
 
         let event_loop = EventLoop::new().unwrap();
+        dispatch::set_event_loop_proxy(event_loop.create_proxy());
         event_loop.run_app(&mut app).unwrap();
     }
 
+    ///
+    /// Size and lay out `root` for the window size.
+    ///
+    fn layout_root(&mut self, width: usize, height: usize) {
+        if self.root.fills_parent_container() {
+            self.root.set_size(width, height);
+        }
+        self.root.resize(width, height);
+    }
+
+    ///
+    /// Load the watched files that changed, replacing `root` and the theme. Returns true if
+    /// anything needs to be redrawn.
+    ///
+    fn poll_hot_reload(&mut self, now: Instant, window: &Window) -> bool {
+        let Some(hot_reload) = self.hot_reload.as_mut() else {
+            return false;
+        };
+        if now < hot_reload.next_poll() {
+            return false;
+        }
+        let reloaded = hot_reload.poll(now);
+        let mut changed = reloaded.error_changed;
+        if let Some(root) = reloaded.root {
+            self.root = root;
+            let size = window.inner_size();
+            self.layout_root(size.width as usize, size.height as usize);
+            changed = true;
+        }
+        if let Some(theme) = reloaded.theme {
+            self.theme = theme;
+            changed = true;
+        }
+        changed
+    }
+
+    ///
+    /// Send a mouse event to the layers first, then to `root` if no layer consumed it.
+    ///
+    fn dispatch_mouse_event(&mut self, input: MouseInput) {
+        if self.layers.handle_mouse_event(input) {
+            self.root.handle_mouse_event(input.elsewhere());
+        } else {
+            self.root.handle_mouse_event(input);
+        }
+        self.layers.apply_pending();
+    }
+
     ///
     /// Used by `Application::run()` in the closure that calls `WinitAppBuilder::create_winit_app(...)`.
     ///
@@ -152,6 +242,16 @@ impl Application {
                     )
                     .unwrap();
 
+                self.layers
+                    .resize(size.width as usize, size.height as usize);
+                self.layers.apply_pending();
+
+                if animation::take_frame_request() {
+                    self.last_frame = Instant::now();
+                    self.root.animate(self.last_frame);
+                    self.layers.animate(self.last_frame);
+                }
+
                 let mut buffer: Buffer<Rc<Window>, Rc<Window>> = surface.buffer_mut().unwrap();
                 for y in 0..size.height {
                     //
@@ -180,13 +280,56 @@ impl Application {
                     buffer: buffer,
                     screen_size: size,
                     font: &component::load_font(),
-                    font_size: 32.0,
-                    theme: &self.theme,
+                    font_size: component::metrics::FONT_SIZE,
+                    theme: self.theme.clone(),
+                    clip: None,
                 };
                 self.root.draw(&mut buffer_context);
+                self.layers.draw(&mut buffer_context);
+                if let Some(hot_reload) = self.hot_reload.as_ref() {
+                    hot_reload.draw_errors(&mut buffer_context);
+                }
                 buffer_context.buffer.present().unwrap();
             }
 
+            Event::AboutToWait => {
+                let now = Instant::now();
+                let posted = dispatch::run_pending();
+                if timer::run_due(now) || posted {
+                    self.layers.apply_pending();
+                    window.request_redraw();
+                }
+                if self.poll_hot_reload(now, window) {
+                    window.request_redraw();
+                }
+                //
+                // Pace animation frames: redraw once the frame interval has passed, and sleep
+                // until then otherwise. Wake up for the next timer and hot reload poll too.
+                //
+                let mut wake_at = [
+                    timer::next_deadline(),
+                    self.hot_reload.as_ref().map(HotReload::next_poll),
+                ]
+                .into_iter()
+                .flatten()
+                .min();
+                if animation::frame_requested() {
+                    let next_frame = self.last_frame + animation::FRAME_INTERVAL;
+                    if now >= next_frame {
+                        window.request_redraw();
+                    } else {
+                        wake_at =
+                            Some(wake_at.map_or(next_frame, |wake_at| wake_at.min(next_frame)));
+                    }
+                }
+                match wake_at {
+                    Some(wake_at) => {
+                        active_event_loop.set_control_flow(ControlFlow::WaitUntil(wake_at))
+                    }
+                    None => active_event_loop.set_control_flow(ControlFlow::Wait),
+                }
+            }
+
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 window_id,
@@ -202,18 +345,15 @@ impl Application {
                     },
                 window_id,
             } if window_id == window.id() => {
-                let mouse_input = crate::event::MouseInput {
-                    scale_factor,
-                    position: Point {
+                let mouse_input = crate::event::MouseInput::new(
+                    Point {
                         x: position.x,
                         y: position.y,
                     },
-                    pressed: false,
-                    just_released: false,
-                    mouse_scroll: None,
-                };
+                    scale_factor,
+                );
                 self.cursor_pos = Some(mouse_input.position);
-                self.root.handle_mouse_event(mouse_input);
+                self.dispatch_mouse_event(mouse_input);
                 window.request_redraw();
             }
 
@@ -227,10 +367,22 @@ impl Application {
                 window_id,
             } if window_id == window.id() => {
                 //println!("{:?} {:?}", event, window_id);
-                self.root.handle_keyboard_event(&event);
+                if !self.layers.handle_keyboard_event(&event)
+                    && !crate::command::handle_shortcut(&event)
+                {
+                    self.root.handle_keyboard_event(&event);
+                }
+                self.layers.apply_pending();
                 window.request_redraw();
             }
 
+            Event::WindowEvent {
+                event: WindowEvent::ModifiersChanged(modifiers),
+                window_id,
+            } if window_id == window.id() => {
+                crate::event::set_modifiers(modifiers.state());
+            }
+
             Event::WindowEvent {
                 event:
                     WindowEvent::MouseInput {
@@ -241,13 +393,7 @@ impl Application {
                 window_id,
             } if window_id == window.id() => {
                 if let Some(pos) = self.cursor_pos {
-                    let mut mouse_input = crate::event::MouseInput {
-                        scale_factor,
-                        position: Point { x: pos.x, y: pos.y },
-                        pressed: true,
-                        just_released: false,
-                        mouse_scroll: None,
-                    };
+                    let mut mouse_input = crate::event::MouseInput::new(pos, scale_factor);
 
                     // TODO: This could be a lot more compact... `mouse_input.pressed = ...`
                     match state {
@@ -259,7 +405,24 @@ impl Application {
                             mouse_input.just_released = true;
                         }
                     }
-                    self.root.handle_mouse_event(mouse_input);
+                    self.dispatch_mouse_event(mouse_input);
+                }
+                window.request_redraw();
+            }
+
+            Event::WindowEvent {
+                event:
+                    WindowEvent::MouseInput {
+                        state: ElementState::Pressed,
+                        button: MouseButton::Right,
+                        ..
+                    },
+                window_id,
+            } if window_id == window.id() => {
+                if let Some(pos) = self.cursor_pos {
+                    let mut mouse_input = crate::event::MouseInput::new(pos, scale_factor);
+                    mouse_input.secondary_pressed = true;
+                    self.dispatch_mouse_event(mouse_input);
                 }
                 window.request_redraw();
             }
@@ -273,7 +436,10 @@ impl Application {
                     },
                 window_id,
             } if window_id == window.id() => {
-                self.root.handle_mouse_wheel_event(&delta, &phase);
+                if !self.layers.handle_mouse_wheel_event(&delta, &phase) {
+                    self.root.handle_mouse_wheel_event(&delta, &phase);
+                }
+                self.layers.apply_pending();
                 window.request_redraw();
             }
 
@@ -281,11 +447,7 @@ impl Application {
                 event: WindowEvent::Resized(size),
                 window_id,
             } if window_id == window.id() => {
-                if self.root.fills_parent_container() {
-                    self.root
-                        .set_size(size.width as usize, size.height as usize);
-                }
-                self.root.resize(size.width as usize, size.height as usize);
+                self.layout_root(size.width as usize, size.height as usize);
                 window.request_redraw();
             }
 
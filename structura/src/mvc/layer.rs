@@ -0,0 +1,473 @@
+//!
+//! Structura: Layers for floating content, such as popups, tooltips, and modal dialogs.
+//!
+//! The `Application` owns a `LayerStack`, which is drawn above the `root` container, with the
+//! most recently opened layer on top. Mouse and keyboard input goes to the topmost layer
+//! first:
+//!
+//! - A layer that contains the cursor consumes mouse input, so the layers below and `root`
+//!   see the event as happening elsewhere (see `MouseInput::elsewhere()`).
+//! - A modal layer consumes all input, wherever the cursor is, and dims the content below.
+//! - A light-dismiss layer closes when a mouse button is pressed outside of it, or on
+//!   `Escape`.
+//!
+//! `Component`s do not have access to the `Application`, so they open and close layers with
+//! `layer::open()` and `layer::close()`. Requests are queued, and applied by the `Application`
+//! after the current event has been handled.
+//!
+
+use crate::component::Component;
+use crate::event::MouseInput;
+use crate::geometry::{Point, Rectangle, Size};
+use crate::view::BufferContext;
+use std::cell::{Cell, RefCell};
+use std::time::Instant;
+use winit::event::{ElementState, KeyEvent, MouseScrollDelta, TouchPhase};
+use winit::keyboard::{Key, NamedKey};
+
+/// Color used to dim the content below a modal layer.
+const MODAL_SCRIM_COLOR: u32 = 0xFF000000;
+
+/// Opacity of the modal scrim, from `0.0` to `1.0`.
+const MODAL_SCRIM_ALPHA: f32 = 0.4;
+
+thread_local! {
+    static NEXT_LAYER_ID: Cell<u64> = const { Cell::new(1) };
+    static PENDING: RefCell<Vec<LayerRequest>> = const { RefCell::new(Vec::new()) };
+}
+
+///
+/// Identifies an open `Layer`, so that it can be closed later.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LayerId(u64);
+
+impl LayerId {
+    fn next() -> LayerId {
+        NEXT_LAYER_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            LayerId(id)
+        })
+    }
+}
+
+///
+/// The preferred side of the anchor rectangle on which an anchored layer is placed.
+///
+/// When the layer does not fit on the preferred side, it flips to the opposite side if there
+/// is room there, and is then clamped to stay inside the window.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Placement {
+    Below,
+    Above,
+    Right,
+    Left,
+}
+
+///
+/// A `Component` floating above the `root` container.
+///
+pub struct Layer {
+    id: LayerId,
+    component: Box<dyn Component>,
+    anchor: Option<(Rectangle, Placement)>,
+    centered: bool,
+    modal: bool,
+    light_dismiss: bool,
+    interactive: bool,
+    keyboard_input: bool,
+    on_dismiss: Option<Box<dyn FnMut()>>,
+}
+
+impl Layer {
+    ///
+    /// Constructor. The layer is shown at the position of the `component`, unless anchored.
+    ///
+    pub fn new(component: Box<dyn Component>) -> Self {
+        Self {
+            id: LayerId::next(),
+            component,
+            anchor: None,
+            centered: false,
+            modal: false,
+            light_dismiss: false,
+            interactive: true,
+            keyboard_input: true,
+            on_dismiss: None,
+        }
+    }
+
+    ///
+    /// Place the layer next to the `anchor` rectangle, on the preferred side.
+    ///
+    pub fn anchored(mut self, anchor: Rectangle, placement: Placement) -> Self {
+        self.anchor = Some((anchor, placement));
+        self
+    }
+
+    ///
+    /// Center the layer in the window, e.g. for a dialog. Ignored when anchored.
+    ///
+    pub fn centered(mut self, centered: bool) -> Self {
+        self.centered = centered;
+        self
+    }
+
+    ///
+    /// Block all mouse and keyboard input to the layers and `root` container below.
+    ///
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    ///
+    /// Close the layer when the mouse is pressed outside of it, or `Escape` is pressed.
+    ///
+    pub fn light_dismiss(mut self, light_dismiss: bool) -> Self {
+        self.light_dismiss = light_dismiss;
+        self
+    }
+
+    ///
+    /// Whether the layer receives input. Non-interactive layers, such as tooltips, are only
+    /// drawn, and input passes through to the layers below.
+    ///
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    ///
+    /// Whether the layer receives keyboard input. When `false`, keyboard input passes through
+    /// to the layers below, e.g. so a popup list can be driven by the field that opened it.
+    ///
+    pub fn keyboard_input(mut self, keyboard_input: bool) -> Self {
+        self.keyboard_input = keyboard_input;
+        self
+    }
+
+    ///
+    /// Add event handler, called when the layer is closed by a light-dismiss.
+    ///
+    pub fn on_dismiss<F: FnMut() + 'static>(mut self, f: F) -> Self {
+        self.on_dismiss = Some(Box::new(f));
+        self
+    }
+
+    ///
+    /// Returns the `LayerId` used to close the layer.
+    ///
+    pub fn id(&self) -> LayerId {
+        self.id
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let position = self.component.get_position();
+        let size = self.component.get_size();
+        Rectangle::new(position.x, position.y, size.width, size.height)
+    }
+
+    fn dismiss(&mut self) {
+        if let Some(handler) = self.on_dismiss.as_mut() {
+            handler();
+        }
+    }
+}
+
+///
+/// A request to change the `LayerStack`, queued by `layer::open()` or `layer::close()`.
+///
+enum LayerRequest {
+    Open(Layer),
+    Close(LayerId),
+}
+
+///
+/// Queue `layer` to be opened above all other layers, once the current event is handled.
+///
+pub fn open(layer: Layer) -> LayerId {
+    let id = layer.id();
+    PENDING.with(|pending| pending.borrow_mut().push(LayerRequest::Open(layer)));
+    id
+}
+
+///
+/// Queue the layer with the specified `id` to be closed, once the current event is handled.
+///
+pub fn close(id: LayerId) {
+    PENDING.with(|pending| pending.borrow_mut().push(LayerRequest::Close(id)));
+}
+
+///
+/// Returns the top-left position for a layer of `size`, placed next to `anchor` on the
+/// preferred side, flipped to the opposite side when it does not fit, and clamped to the
+/// `screen`.
+///
+pub fn place_popup(anchor: &Rectangle, size: &Size, placement: Placement, screen: &Size) -> Point {
+    let (w, h) = (size.width as f64, size.height as f64);
+    let (sw, sh) = (screen.width as f64, screen.height as f64);
+    let left = anchor.point.x;
+    let top = anchor.point.y;
+    let right = left + anchor.size.width as f64;
+    let bottom = top + anchor.size.height as f64;
+
+    let (x, y) = match placement {
+        Placement::Below if bottom + h > sh && top - h >= 0.0 => (left, top - h),
+        Placement::Below => (left, bottom),
+        Placement::Above if top - h < 0.0 && bottom + h <= sh => (left, bottom),
+        Placement::Above => (left, top - h),
+        Placement::Right if right + w > sw && left - w >= 0.0 => (left - w, top),
+        Placement::Right => (right, top),
+        Placement::Left if left - w < 0.0 && right + w <= sw => (right, top),
+        Placement::Left => (left - w, top),
+    };
+    Point {
+        x: x.min(sw - w).max(0.0),
+        y: y.min(sh - h).max(0.0),
+    }
+}
+
+///
+/// The stack of `Layer`s drawn above the `root` container, owned by the `Application`.
+///
+pub struct LayerStack {
+    layers: Vec<Layer>,
+    screen_size: Size,
+    cursor: Option<Point>,
+}
+
+impl Default for LayerStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayerStack {
+    ///
+    /// Constructor.
+    ///
+    pub fn new() -> Self {
+        Self {
+            layers: vec![],
+            screen_size: Size {
+                width: 0,
+                height: 0,
+            },
+            cursor: None,
+        }
+    }
+
+    ///
+    /// Open `layer` above all other layers.
+    ///
+    pub fn push(&mut self, mut layer: Layer) -> LayerId {
+        let id = layer.id();
+        self.place(&mut layer);
+        self.layers.push(layer);
+        id
+    }
+
+    ///
+    /// Close the layer with the specified `id`, returning it.
+    ///
+    pub fn remove(&mut self, id: LayerId) -> Option<Layer> {
+        let index = self.layers.iter().position(|layer| layer.id == id)?;
+        Some(self.layers.remove(index))
+    }
+
+    ///
+    /// Returns `true` when the layer with the specified `id` is open.
+    ///
+    pub fn contains(&self, id: LayerId) -> bool {
+        self.layers.iter().any(|layer| layer.id == id)
+    }
+
+    ///
+    /// Returns `true` when no layers are open.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    ///
+    /// Apply the requests queued by `layer::open()` and `layer::close()`.
+    ///
+    /// Returns `true` when any layer was opened or closed.
+    ///
+    pub fn apply_pending(&mut self) -> bool {
+        let requests: Vec<LayerRequest> =
+            PENDING.with(|pending| pending.borrow_mut().drain(..).collect());
+        let changed = !requests.is_empty();
+        for request in requests {
+            match request {
+                LayerRequest::Open(layer) => {
+                    self.push(layer);
+                }
+                LayerRequest::Close(id) => {
+                    self.remove(id);
+                }
+            }
+        }
+        changed
+    }
+
+    ///
+    /// Called when the window resizes, to keep anchored and centered layers inside the window.
+    ///
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.screen_size = Size {
+            width: width as u32,
+            height: height as u32,
+        };
+        let mut layers = std::mem::take(&mut self.layers);
+        for layer in layers.iter_mut() {
+            self.place(layer);
+        }
+        self.layers = layers;
+    }
+
+    fn place(&self, layer: &mut Layer) {
+        if self.screen_size.width == 0 || self.screen_size.height == 0 {
+            return;
+        }
+        if let Some((anchor, placement)) = layer.anchor.as_ref() {
+            let size = layer.component.get_size();
+            let position = place_popup(anchor, &size, *placement, &self.screen_size);
+            layer.component.set_position(position.x, position.y);
+        } else if layer.centered {
+            let size = layer.component.get_size();
+            let x = (self.screen_size.width as f64 - size.width as f64) / 2.0;
+            let y = (self.screen_size.height as f64 - size.height as f64) / 2.0;
+            layer.component.set_position(x.max(0.0).round(), y.max(0.0).round());
+        }
+    }
+
+    ///
+    /// Close light-dismiss layers at the specified indices, calling their `on_dismiss`.
+    ///
+    fn dismiss(&mut self, mut indices: Vec<usize>) {
+        indices.sort_unstable();
+        for index in indices.into_iter().rev() {
+            let mut layer = self.layers.remove(index);
+            layer.dismiss();
+        }
+    }
+
+    ///
+    /// Send a mouse event to the layers, from the top down.
+    ///
+    /// Returns `true` when the event was consumed by a layer, in which case the `root`
+    /// container should only see it as happening elsewhere.
+    ///
+    pub fn handle_mouse_event(&mut self, input: MouseInput) -> bool {
+        let (x, y) = (input.position.x, input.position.y);
+        self.cursor = Some(input.position);
+        let mut consumed = false;
+        let mut dismissed = vec![];
+        for (index, layer) in self.layers.iter_mut().enumerate().rev() {
+            if !layer.interactive {
+                continue;
+            }
+            if consumed {
+                layer.component.handle_mouse_event(input.elsewhere());
+                continue;
+            }
+            layer.component.handle_mouse_event(input);
+            let inside = layer.bounds().contains(x, y);
+            let pressed = input.pressed || input.secondary_pressed;
+            if pressed && !inside && layer.light_dismiss {
+                dismissed.push(index);
+            }
+            if inside || layer.modal {
+                consumed = true;
+            }
+        }
+        self.dismiss(dismissed);
+        consumed
+    }
+
+    ///
+    /// Send a mouse wheel event to the topmost layer under the cursor, or the topmost modal
+    /// layer.
+    ///
+    /// Returns `true` when the event was consumed by a layer.
+    ///
+    pub fn handle_mouse_wheel_event(
+        &mut self,
+        delta: &MouseScrollDelta,
+        phase: &TouchPhase,
+    ) -> bool {
+        let cursor = self.cursor;
+        for layer in self.layers.iter_mut().rev() {
+            if !layer.interactive {
+                continue;
+            }
+            let inside = cursor.is_some_and(|c| layer.bounds().contains(c.x, c.y));
+            if inside || layer.modal {
+                layer.component.handle_mouse_wheel_event(delta, phase);
+                return true;
+            }
+        }
+        false
+    }
+
+    ///
+    /// Send a keyboard event to the topmost layer that receives keyboard input.
+    ///
+    /// Returns `true` when the event was consumed by a layer.
+    ///
+    pub fn handle_keyboard_event(&mut self, event: &KeyEvent) -> bool {
+        let Some(index) = self
+            .layers
+            .iter()
+            .rposition(|layer| layer.interactive && (layer.keyboard_input || layer.modal))
+        else {
+            return false;
+        };
+        let layer = &mut self.layers[index];
+        if layer.light_dismiss
+            && event.state == ElementState::Pressed
+            && event.logical_key == Key::Named(NamedKey::Escape)
+        {
+            self.dismiss(vec![index]);
+            return true;
+        }
+        if layer.keyboard_input {
+            layer.component.handle_keyboard_event(event);
+            return true;
+        }
+        layer.modal
+    }
+
+    ///
+    /// Advance animations in the content of each layer. See `Component::animate()`.
+    ///
+    pub fn animate(&mut self, now: Instant) {
+        for layer in self.layers.iter_mut() {
+            layer.component.animate(now);
+        }
+    }
+
+    ///
+    /// Draw all layers, from the bottom up. Modal layers dim everything below them.
+    ///
+    pub fn draw(&self, context: &mut BufferContext) {
+        for layer in self.layers.iter() {
+            if layer.modal {
+                let (width, height) = (context.screen_size.width, context.screen_size.height);
+                context.blend_rect(
+                    0.0,
+                    0.0,
+                    width,
+                    height,
+                    MODAL_SCRIM_COLOR,
+                    MODAL_SCRIM_ALPHA,
+                );
+            }
+            layer.component.draw(context);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//!
+//! Structura: Hot reload of markup and theme files.
+//!
+//! The `Application` polls the watched files while it runs. When a file changes:
+//!
+//! - A markup file is loaded again, and replaces the `root` container. Components with the same
+//!   `id` in the old and new trees keep their state, such as the text in a `TextArea` or the
+//!   scroll position of a `ScrollView` (see `Component::save_state()`).
+//! - A theme file is loaded again, and replaces the `Application` theme.
+//!
+//! When a file fails to load, the current tree or theme is kept, and the error is shown in an
+//! overlay at the bottom of the window until the file is fixed.
+//!
+//! ```ignore
+//! let loader = MarkupLoader::new().bind("save", |_| println!("save"));
+//! let hot_reload = HotReload::new(loader, "ui/main.xml")
+//!     .theme_file("ui/theme.xml")
+//!     .on_load(|ids| {
+//!         // Called after each load: look up components with `ids.get::<T>(id)`.
+//!     });
+//! let mut application = Application::with_hot_reload(hot_reload);
+//! application.run();
+//! ```
+//!
+
+use crate::component::style::ComponentTheme;
+use crate::container::ContainerComponent;
+use crate::geometry::Rectangle;
+use crate::markup::{self, ComponentIds, MarkupError, MarkupLoader};
+use crate::view::BufferContext;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+///
+/// Time between checks for changed files.
+///
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Background color of the error overlay.
+const ERROR_BACK_COLOR: u32 = 0xFF661111;
+
+/// Border color of the error overlay.
+const ERROR_BORDER_COLOR: u32 = 0xFFFF5555;
+
+/// Text color of the error overlay.
+const ERROR_TEXT_COLOR: u32 = 0xFFFFFFFF;
+
+/// Space between the error overlay and the window edges, and around the text.
+const ERROR_MARGIN: f64 = 12.0;
+
+/// Callback for `HotReload::on_load()`.
+type OnLoad = Box<dyn FnMut(&ComponentIds)>;
+
+///
+/// A file and its modification time when it was last loaded.
+///
+struct WatchedFile {
+    path: PathBuf,
+
+    /// `None` until the first check. Then the modification time, or `None` while the file is
+    /// missing or unreadable.
+    modified: Option<Option<SystemTime>>,
+}
+
+impl WatchedFile {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            modified: None,
+        }
+    }
+
+    ///
+    /// Returns true if the file changed since the last call, or if this is the first call. A
+    /// file that stays missing is reported once, not on every call.
+    ///
+    fn changed(&mut self) -> bool {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let changed = self.modified != Some(modified);
+        self.modified = Some(modified);
+        changed
+    }
+}
+
+///
+/// What changed in a call to `HotReload::poll()`.
+///
+#[derive(Default)]
+pub(crate) struct Reloaded {
+    pub root: Option<Box<dyn ContainerComponent>>,
+    pub theme: Option<Rc<dyn ComponentTheme>>,
+
+    /// True if an error was shown or cleared.
+    pub error_changed: bool,
+}
+
+///
+/// Watches a markup file, and optionally a theme file, for the `Application`.
+///
+pub struct HotReload {
+    loader: MarkupLoader,
+    markup: WatchedFile,
+    theme: Option<WatchedFile>,
+    ids: ComponentIds,
+    markup_error: Option<MarkupError>,
+    theme_error: Option<MarkupError>,
+    next_poll: Instant,
+    on_load: Option<OnLoad>,
+}
+
+impl HotReload {
+    ///
+    /// Constructor, watching the markup file at `path`, built with `loader`.
+    ///
+    pub fn new<P: AsRef<Path>>(loader: MarkupLoader, path: P) -> Self {
+        Self {
+            loader,
+            markup: WatchedFile::new(path.as_ref()),
+            theme: None,
+            ids: ComponentIds::default(),
+            markup_error: None,
+            theme_error: None,
+            next_poll: Instant::now(),
+            on_load: None,
+        }
+    }
+
+    ///
+    /// Also watch the theme file at `path`. See `markup::load_theme_file()`.
+    ///
+    pub fn theme_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.theme = Some(WatchedFile::new(path.as_ref()));
+        self
+    }
+
+    ///
+    /// Called with the components that have an `id` each time the markup file is loaded,
+    /// including the first time. Components from the previous load are no longer shown.
+    ///
+    pub fn on_load<F: FnMut(&ComponentIds) + 'static>(mut self, callback: F) -> Self {
+        self.on_load = Some(Box::new(callback));
+        self
+    }
+
+    ///
+    /// Returns the components with an `id` from the last successful load.
+    ///
+    pub fn ids(&self) -> &ComponentIds {
+        &self.ids
+    }
+
+    ///
+    /// Returns the errors from the last load of each file.
+    ///
+    pub fn errors(&self) -> impl Iterator<Item = &MarkupError> {
+        self.markup_error.iter().chain(self.theme_error.iter())
+    }
+
+    ///
+    /// Returns the time of the next check for changed files.
+    ///
+    pub(crate) fn next_poll(&self) -> Instant {
+        self.next_poll
+    }
+
+    ///
+    /// Load the files that changed since the last call. The first call loads all files.
+    ///
+    pub(crate) fn poll(&mut self, now: Instant) -> Reloaded {
+        self.next_poll = now + POLL_INTERVAL;
+        let mut reloaded = Reloaded::default();
+
+        if self.markup.changed() {
+            let had_error = self.markup_error.is_some();
+            match self.loader.load_file(&self.markup.path) {
+                Ok(ui) => {
+                    ui.ids.restore_states(self.ids.save_states());
+                    self.ids = ui.ids;
+                    self.markup_error = None;
+                    reloaded.root = Some(ui.root);
+                    if let Some(callback) = self.on_load.as_mut() {
+                        callback(&self.ids);
+                    }
+                }
+                Err(error) => {
+                    eprintln!("{error}");
+                    self.markup_error = Some(error);
+                }
+            }
+            reloaded.error_changed |= had_error || self.markup_error.is_some();
+        }
+
+        if let Some(theme) = self.theme.as_mut()
+            && theme.changed()
+        {
+            let had_error = self.theme_error.is_some();
+            match markup::load_theme_file(&theme.path) {
+                Ok(theme) => {
+                    self.theme_error = None;
+                    reloaded.theme = Some(Rc::new(theme));
+                }
+                Err(error) => {
+                    eprintln!("{error}");
+                    self.theme_error = Some(error);
+                }
+            }
+            reloaded.error_changed |= had_error || self.theme_error.is_some();
+        }
+
+        reloaded
+    }
+
+    ///
+    /// Draw the current errors, if any, in a box at the bottom of the window. Long messages are
+    /// wrapped at word boundaries.
+    ///
+    pub(crate) fn draw_errors(&self, context: &mut BufferContext) {
+        let max_width = context.screen_size.width as f64 - ERROR_MARGIN * 4.0;
+        let mut lines = vec![];
+        for error in self.errors() {
+            lines.extend(wrap(context, &error.to_string(), max_width));
+        }
+        if lines.is_empty() {
+            return;
+        }
+
+        let line_height = context.line_height() as f64;
+        let width = context.screen_size.width as f64 - ERROR_MARGIN * 2.0;
+        let height = line_height * lines.len() as f64 + ERROR_MARGIN * 2.0;
+        let x = ERROR_MARGIN;
+        let y = (context.screen_size.height as f64 - ERROR_MARGIN - height).max(0.0);
+        let bounds = Rectangle::new(x, y, width.max(0.0) as u32, height as u32);
+        context.fill_rect(
+            x,
+            y,
+            bounds.size.width,
+            bounds.size.height,
+            ERROR_BACK_COLOR,
+        );
+        context.draw_border(
+            x,
+            y,
+            bounds.size.width,
+            bounds.size.height,
+            2,
+            ERROR_BORDER_COLOR,
+        );
+        context.with_clip(&bounds, |context| {
+            for (i, line) in lines.iter().enumerate() {
+                let line_y = y + ERROR_MARGIN + line_height * i as f64;
+                context.draw_text(line, x + ERROR_MARGIN, line_y, ERROR_TEXT_COLOR);
+            }
+        });
+    }
+}
+
+///
+/// Split `text` into lines no wider than `max_width`, breaking between words. A single word
+/// wider than `max_width` gets a line of its own.
+///
+fn wrap(context: &BufferContext, text: &str, max_width: f64) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{line} {word}")
+        };
+        if !line.is_empty() && context.text_width(&candidate) as f64 > max_width {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        } else {
+            line = candidate;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
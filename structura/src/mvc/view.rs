@@ -2,9 +2,10 @@
 //! Structura. View types.
 //!
 
+use crate::component::metrics;
 use crate::component::style::ComponentTheme;
-use crate::geometry::Size;
-use rusttype::Font;
+use crate::geometry::{Rectangle, Size};
+use rusttype::{Font, Scale, point};
 use softbuffer::Buffer;
 use std::rc::Rc;
 use winit::window::Window;
@@ -17,5 +18,236 @@ pub struct BufferContext<'context> {
     pub screen_size: Size,
     pub font: &'context Font<'context>,
     pub font_size: f32,
-    pub theme: &'context Box<dyn ComponentTheme>,
+
+    /// The current theme. Replaced while drawing a `Styled` component, see
+    /// `crate::component::styled`.
+    pub theme: Rc<dyn ComponentTheme>,
+
+    /// Drawing is restricted to this area when set. See `BufferContext::with_clip()`.
+    pub clip: Option<Rectangle>,
+}
+
+impl BufferContext<'_> {
+    ///
+    /// Run `f` with drawing restricted to `rect`, intersected with any clip area that is already
+    /// in place. The previous clip area is restored afterwards.
+    ///
+    pub fn with_clip<F: FnOnce(&mut Self)>(&mut self, rect: &Rectangle, f: F) {
+        let previous = self.clip.clone();
+        let clip = match previous.as_ref() {
+            Some(current) => current.intersect(rect),
+            None => rect.clone(),
+        };
+        self.clip = Some(clip);
+        f(self);
+        self.clip = previous;
+    }
+
+    ///
+    /// Returns the drawable area as `(x0, y0, x1, y1)` pixel bounds: the screen, restricted by
+    /// the current clip area.
+    ///
+    fn drawable_bounds(&self) -> (i64, i64, i64, i64) {
+        let mut bounds = (
+            0,
+            0,
+            self.screen_size.width as i64,
+            (self.screen_size.height as i64).min(self.buffer.len() as i64 / self.screen_width()),
+        );
+        if let Some(clip) = self.clip.as_ref() {
+            bounds.0 = bounds.0.max(clip.point.x.floor() as i64);
+            bounds.1 = bounds.1.max(clip.point.y.floor() as i64);
+            bounds.2 = bounds
+                .2
+                .min((clip.point.x + clip.size.width as f64).ceil() as i64);
+            bounds.3 = bounds
+                .3
+                .min((clip.point.y + clip.size.height as f64).ceil() as i64);
+        }
+        bounds
+    }
+
+    fn screen_width(&self) -> i64 {
+        (self.screen_size.width as i64).max(1)
+    }
+
+    ///
+    /// Set a single pixel, respecting the screen bounds and the clip area.
+    ///
+    pub fn set_pixel(&mut self, x: i64, y: i64, color: u32) {
+        let (x0, y0, x1, y1) = self.drawable_bounds();
+        if x >= x0 && x < x1 && y >= y0 && y < y1 {
+            let idx = (y * self.screen_width() + x) as usize;
+            self.buffer[idx] = color;
+        }
+    }
+
+    ///
+    /// Blend `color` over a single pixel using `alpha` coverage (`0.0` to `1.0`).
+    ///
+    pub fn blend_pixel(&mut self, x: i64, y: i64, color: u32, alpha: f32) {
+        let (x0, y0, x1, y1) = self.drawable_bounds();
+        if x >= x0 && x < x1 && y >= y0 && y < y1 {
+            let idx = (y * self.screen_width() + x) as usize;
+            self.buffer[idx] = blend_color(self.buffer[idx], color, alpha);
+        }
+    }
+
+    ///
+    /// Fill a rectangle with a solid color.
+    ///
+    pub fn fill_rect(&mut self, x: f64, y: f64, width: u32, height: u32, color: u32) {
+        let (bx0, by0, bx1, by1) = self.drawable_bounds();
+        let x0 = (x.round() as i64).max(bx0);
+        let y0 = (y.round() as i64).max(by0);
+        let x1 = (x.round() as i64 + width as i64).min(bx1);
+        let y1 = (y.round() as i64 + height as i64).min(by1);
+        let screen_width = self.screen_width();
+        for py in y0..y1 {
+            for px in x0..x1 {
+                self.buffer[(py * screen_width + px) as usize] = color;
+            }
+        }
+    }
+
+    ///
+    /// Blend `color` over a rectangle using `alpha` coverage (`0.0` to `1.0`), e.g. to dim or
+    /// tint the area.
+    ///
+    pub fn blend_rect(&mut self, x: f64, y: f64, width: u32, height: u32, color: u32, alpha: f32) {
+        let (bx0, by0, bx1, by1) = self.drawable_bounds();
+        let x0 = (x.round() as i64).max(bx0);
+        let y0 = (y.round() as i64).max(by0);
+        let x1 = (x.round() as i64 + width as i64).min(bx1);
+        let y1 = (y.round() as i64 + height as i64).min(by1);
+        let screen_width = self.screen_width();
+        for py in y0..y1 {
+            for px in x0..x1 {
+                let idx = (py * screen_width + px) as usize;
+                self.buffer[idx] = blend_color(self.buffer[idx], color, alpha);
+            }
+        }
+    }
+
+    ///
+    /// Fill a circle centered at (`cx`, `cy`), blending its edge for anti-aliasing.
+    ///
+    pub fn fill_circle(&mut self, cx: f64, cy: f64, radius: f64, color: u32) {
+        if radius <= 0.0 {
+            return;
+        }
+        let (x0, x1) = ((cx - radius).floor() as i64, (cx + radius).ceil() as i64);
+        let (y0, y1) = ((cy - radius).floor() as i64, (cy + radius).ceil() as i64);
+        for py in y0..y1 {
+            for px in x0..x1 {
+                let dx = px as f64 + 0.5 - cx;
+                let dy = py as f64 + 0.5 - cy;
+                let coverage = (radius - (dx * dx + dy * dy).sqrt() + 0.5).clamp(0.0, 1.0);
+                if coverage >= 1.0 {
+                    self.set_pixel(px, py, color);
+                } else if coverage > 0.0 {
+                    self.blend_pixel(px, py, color, coverage as f32);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Draw a rectangular border of `border_width` pixels, inside the specified bounds.
+    ///
+    pub fn draw_border(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: u32,
+        height: u32,
+        border_width: usize,
+        color: u32,
+    ) {
+        let bw = (border_width as u32).min(width / 2).min(height / 2);
+        if bw == 0 {
+            return;
+        }
+        self.fill_rect(x, y, width, bw, color);
+        self.fill_rect(x, y + (height - bw) as f64, width, bw, color);
+        self.fill_rect(x, y + bw as f64, bw, height - bw * 2, color);
+        self.fill_rect(
+            x + (width - bw) as f64,
+            y + bw as f64,
+            bw,
+            height - bw * 2,
+            color,
+        );
+    }
+
+    ///
+    /// Draw a straight line from `(x0, y0)` to `(x1, y1)`, one pixel wide.
+    ///
+    pub fn draw_line(&mut self, x0: f64, y0: f64, x1: f64, y1: f64, color: u32) {
+        let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0) as i64;
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let x = x0 + (x1 - x0) * t;
+            let y = y0 + (y1 - y0) * t;
+            self.set_pixel(x.round() as i64, y.round() as i64, color);
+        }
+    }
+
+    ///
+    /// Draw a single line of `text`, where `x` and `y` are the top-left corner of the text.
+    ///
+    pub fn draw_text(&mut self, text: &str, x: f64, y: f64, color: u32) {
+        let font = self.font;
+        let scale = Scale::uniform(self.font_size);
+        let v_metrics = font.v_metrics(scale);
+        let start = point(x as f32, y as f32 + v_metrics.ascent);
+        for glyph in font.layout(text, scale, start) {
+            if let Some(bb) = glyph.pixel_bounding_box() {
+                glyph.draw(|gx, gy, v| {
+                    self.blend_pixel(
+                        gx as i64 + bb.min.x as i64,
+                        gy as i64 + bb.min.y as i64,
+                        color,
+                        v,
+                    );
+                });
+            }
+        }
+    }
+
+    ///
+    /// Returns the width in pixels of a single line of `text`, using the current font.
+    ///
+    pub fn text_width(&self, text: &str) -> f32 {
+        metrics::text_width_in(self.font, self.font_size, text)
+    }
+
+    ///
+    /// Returns the height in pixels of a single line of text, using the current font.
+    ///
+    pub fn line_height(&self) -> f32 {
+        metrics::line_height_in(self.font, self.font_size)
+    }
+}
+
+///
+/// Blend the `fg` color over the `bg` color using `alpha` coverage (`0.0` to `1.0`).
+///
+pub fn blend_color(bg: u32, fg: u32, alpha: f32) -> u32 {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let inv = 1.0 - alpha;
+
+    let br = ((bg >> 16) & 0xFF) as f32;
+    let bg_ = ((bg >> 8) & 0xFF) as f32;
+    let bb = (bg & 0xFF) as f32;
+
+    let fr = ((fg >> 16) & 0xFF) as f32;
+    let fg_ = ((fg >> 8) & 0xFF) as f32;
+    let fb = (fg & 0xFF) as f32;
+
+    let r = (br * inv + fr * alpha).round() as u32;
+    let g = (bg_ * inv + fg_ * alpha).round() as u32;
+    let b = (bb * inv + fb * alpha).round() as u32;
+
+    (r << 16) | (g << 8) | b
 }
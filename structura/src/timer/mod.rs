@@ -0,0 +1,120 @@
+//!
+//! Structura
+//!
+//! Timers.
+//!
+//! A `Component` starts a timer with `start()` or `start_repeating()`, and the `Application`
+//! calls the callback from its event loop once the delay has passed, then redraws. As with
+//! `crate::layer`, `Component`s do not need access to the `Application` to use timers.
+//!
+//! Timers are not precise: a callback runs at the first chance after its deadline.
+//!
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static NEXT_TIMER_ID: Cell<u64> = const { Cell::new(1) };
+    static TIMERS: RefCell<Vec<Timer>> = const { RefCell::new(Vec::new()) };
+}
+
+///
+/// Identifies a pending timer, so that it can be cancelled.
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TimerId(u64);
+
+struct Timer {
+    id: TimerId,
+    deadline: Instant,
+    interval: Option<Duration>,
+    callback: Rc<RefCell<dyn FnMut()>>,
+}
+
+fn add(delay: Duration, interval: Option<Duration>, callback: Rc<RefCell<dyn FnMut()>>) -> TimerId {
+    let id = NEXT_TIMER_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        TimerId(id)
+    });
+    let timer = Timer {
+        id,
+        deadline: Instant::now() + delay,
+        interval,
+        callback,
+    };
+    TIMERS.with(|timers| timers.borrow_mut().push(timer));
+    id
+}
+
+///
+/// Call `callback` once, after `delay`.
+///
+pub fn start<F: FnMut() + 'static>(delay: Duration, callback: F) -> TimerId {
+    add(delay, None, Rc::new(RefCell::new(callback)))
+}
+
+///
+/// Call `callback` every `interval`, until the timer is cancelled.
+///
+pub fn start_repeating<F: FnMut() + 'static>(interval: Duration, callback: F) -> TimerId {
+    add(interval, Some(interval), Rc::new(RefCell::new(callback)))
+}
+
+///
+/// Cancel the timer with the specified `id`. Does nothing if it has already run.
+///
+pub fn cancel(id: TimerId) {
+    TIMERS.with(|timers| timers.borrow_mut().retain(|timer| timer.id != id));
+}
+
+///
+/// Returns true if the timer with the specified `id` has not run yet, or is repeating.
+///
+pub fn is_pending(id: TimerId) -> bool {
+    TIMERS.with(|timers| timers.borrow().iter().any(|timer| timer.id == id))
+}
+
+///
+/// Returns the earliest deadline of the pending timers.
+///
+pub(crate) fn next_deadline() -> Option<Instant> {
+    TIMERS.with(|timers| timers.borrow().iter().map(|timer| timer.deadline).min())
+}
+
+///
+/// Run the callbacks of the timers whose deadline has passed. Returns true if any ran.
+///
+/// Callbacks may start and cancel timers, including the timers that are due.
+///
+pub(crate) fn run_due(now: Instant) -> bool {
+    let due: Vec<TimerId> = TIMERS.with(|timers| {
+        timers
+            .borrow()
+            .iter()
+            .filter(|timer| timer.deadline <= now)
+            .map(|timer| timer.id)
+            .collect()
+    });
+    let mut ran = false;
+    for id in due {
+        let callback = TIMERS.with(|timers| {
+            let mut timers = timers.borrow_mut();
+            let index = timers.iter().position(|timer| timer.id == id)?;
+            let callback = timers[index].callback.clone();
+            match timers[index].interval {
+                Some(interval) => timers[index].deadline = now + interval,
+                None => {
+                    timers.remove(index);
+                }
+            }
+            Some(callback)
+        });
+        if let Some(callback) = callback {
+            (callback.borrow_mut())();
+            ran = true;
+        }
+    }
+    ran
+}